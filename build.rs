@@ -0,0 +1,42 @@
+//! Compile the shaders to SPIR-V ahead of time. The library embeds them to fall back on
+//! when it can't compile the shaders itself. Nothing to do without the `render` feature.
+#[cfg(feature = "render")]
+use std::{env, fs, path};
+#[cfg(feature = "render")]
+use std::io::Read;
+
+#[cfg(feature = "render")]
+use glsl_to_spirv::ShaderType;
+
+#[cfg(feature = "render")]
+const SHADERS: [&str; 3] = ["flat.vert", "flat.frag", "pbr.frag"];
+
+#[cfg(not(feature = "render"))]
+fn main() {}
+
+#[cfg(feature = "render")]
+fn main() {
+    let out_dir = path::PathBuf::from(env::var("OUT_DIR").expect("Cargo sets OUT_DIR."));
+
+    for name in SHADERS.iter() {
+        let source_path = path::Path::new("shaders").join(name);
+        println!("cargo:rerun-if-changed={}", source_path.display());
+
+        let source = fs::read_to_string(&source_path)
+            .unwrap_or_else(|e| panic!("Can't read {}: {}", source_path.display(), e));
+        let kind = if name.ends_with(".vert") {
+            ShaderType::Vertex
+        } else {
+            ShaderType::Fragment
+        };
+
+        let mut spirv = Vec::new();
+        glsl_to_spirv::compile(&source, kind)
+            .unwrap_or_else(|e| panic!("Can't compile {}: {}", name, e))
+            .read_to_end(&mut spirv)
+            .expect("Compiled shader is readable.");
+
+        fs::write(out_dir.join(format!("{}.spv", name)), spirv)
+            .unwrap_or_else(|e| panic!("Can't write {}.spv: {}", name, e));
+    }
+}
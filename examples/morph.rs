@@ -0,0 +1,61 @@
+//! Demonstrate animating a Conway operation. Kis rises out of a dodecahedron then sinks back
+//! again, over and over.
+
+use std::time::Duration;
+
+use log::{error, info};
+
+use polyorb::{polyhedron, presenter};
+use polyorb::light::Light;
+use polyorb::scene::Scene;
+use polyorb::{shader, presentation};
+
+/// How long the kis takes to rise or sink.
+const MORPH_TIME: Duration = Duration::from_millis(1500);
+
+pub fn main() -> Result<(), Box<dyn std::error::Error>> {
+    dotenv::dotenv().ok();
+    env_logger::init();
+
+    info!("Running morph demo...");
+
+    let light1 = Light::new(
+        cgmath::Point3::new(7f32, -5f32, 10f32),
+        wgpu::Color { r: 0.5, g: 1.0, b: 0.5, a: 1.0 },
+        60.0,
+        1.0..20.0,
+    );
+    let light2 = Light::new(
+        cgmath::Point3::new(-5f32, -7f32, 10f32),
+        wgpu::Color { r: 1.0, g: 0.5, b: 0.5, a: 1.0 },
+        45.0,
+        1.0..20.0,
+    );
+
+    let spec = polyhedron::Specification::parse("kD")?;
+    let morph = spec.produce_morph().expect("kD has an operation to morph.");
+    let present = |p| presenter::SingleColour::new([0.0, 0.6, 1.0], p).to_cached();
+
+    let flat_shaders = shader::load_flat_shaders()?;
+
+    let scene = Scene::new()
+        .shaders(&flat_shaders)
+        .add_light(light1)
+        .add_light(light2)
+        .geometry(present(morph.at(0.0)));
+
+    presentation::run_animated("Morph", scene, |scene, elapsed, device, queue| {
+        // Rise on even runs and sink on odd ones.
+        let runs = elapsed.as_millis() / MORPH_TIME.as_millis();
+        let into_run = (elapsed.as_millis() % MORPH_TIME.as_millis()) as u64;
+        let into_run = Duration::from_millis(into_run);
+        let elapsed = if runs.is_multiple_of(2) { into_run } else { MORPH_TIME - into_run };
+
+        let geometry = present(morph.at_time(elapsed, MORPH_TIME));
+        if let Err(e) = scene.replace_geometry(&geometry, device, queue) {
+            error!("Can't morph: {}", e);
+        }
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+//! Demonstrate drawing a field of Goldberg orbs as instances of the one polyhedron.
+
+use log::info;
+use cgmath::{Matrix4, Vector3};
+
+use polyorb::{polyhedron, presenter};
+use polyorb::light::Light;
+use polyorb::scene::{Scene, Instance};
+use polyorb::{shader, presentation};
+
+const SIDE: i32 = 15;
+
+pub fn main() -> Result<(), Box<dyn std::error::Error>> {
+    dotenv::dotenv().ok();
+    env_logger::init();
+
+    info!("Running orb field demo...");
+
+    let sun = Light::directional(
+        cgmath::Vector3::new(-1f32, 1f32, -2f32),
+        wgpu::Color { r: 1.0, g: 1.0, b: 1.0, a: 1.0 },
+    );
+
+    let polyhedron = polyhedron::goldberg(2, 1, 0.2)?;
+    let present = presenter::DegreeColour::new(polyhedron);
+
+    let flat_shaders = shader::load_flat_shaders()?;
+
+    let mut scene = Scene::new()
+        .shaders(&flat_shaders)
+        .add_light(sun);
+    for x in -SIDE / 2..=SIDE / 2 {
+        for y in -SIDE / 2..=SIDE / 2 {
+            let offset = Vector3::new(x as f32 * 0.5, y as f32 * 0.5, 0.0);
+            let fade = (x + SIDE / 2) as f32 / SIDE as f32;
+            scene = scene.add_instance(
+                Instance::new(Matrix4::from_translation(offset), [1.0 - fade, 1.0, fade])
+            );
+        }
+    }
+
+    presentation::run("Orbs", scene.geometry(present.to_cached()))?;
+
+    Ok(())
+}
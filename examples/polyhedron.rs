@@ -25,7 +25,7 @@ pub fn main() -> Result<(), Box<dyn std::error::Error>> {
         45.0,
         1.0..20.0,
     );
-    let _light3 = Light::new(
+    let light3 = Light::new(
         cgmath::Point3::new(-5f32, -7f32, 10f32),
         wgpu::Color { r: 1.0, g: 0.5, b: 0.5, a: 1.0 },
         45.0,
@@ -81,10 +81,10 @@ pub fn main() -> Result<(), Box<dyn std::error::Error>> {
         .shaders(&flat_shaders)
         .add_light(light1)
         .add_light(light2)
-        //.add_light(light3)
+        .add_light(light3)
         .geometry(present.to_cached());
 
-    presentation::run("Polyhedron", scene)?;
+    presentation::run_picking("Polyhedron", scene, |face| info!("Picked face {:?}", face))?;
 
     Ok(())
 }
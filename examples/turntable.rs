@@ -0,0 +1,48 @@
+//! Demonstrate rendering a Goldberg polyhedron turning round to a sequence of PNGs.
+
+use log::info;
+
+use polyorb::{polyhedron, presenter};
+use polyorb::light::Light;
+use polyorb::scene::Scene;
+use polyorb::presentation::Turntable;
+use polyorb::shader;
+
+pub fn main() -> Result<(), Box<dyn std::error::Error>> {
+    dotenv::dotenv().ok();
+    env_logger::init();
+
+    info!("Running turntable demo...");
+
+    let light1 = Light::new(
+        cgmath::Point3::new(7f32, -5f32, 10f32),
+        wgpu::Color { r: 1.0, g: 1.0, b: 1.0, a: 1.0 },
+        60.0,
+        1.0..20.0,
+    );
+    let light2 = Light::new(
+        cgmath::Point3::new(-5f32, -7f32, 10f32),
+        wgpu::Color { r: 0.5, g: 0.5, b: 1.0, a: 1.0 },
+        45.0,
+        1.0..20.0,
+    );
+
+    let polyhedron = polyhedron::goldberg(3, 0, 1.0)?;
+    let present = presenter::DegreeColour::new(polyhedron);
+
+    let flat_shaders = shader::load_flat_shaders()?;
+
+    let scene = Scene::new()
+        .shaders(&flat_shaders)
+        .add_light(light1)
+        .add_light(light2)
+        .geometry(present.to_cached());
+
+    let frames = Turntable::new("turntable")
+        .frames(90)
+        .size(400, 400)
+        .render(scene)?;
+    println!("Wrote {} frames.", frames.len());
+
+    Ok(())
+}
@@ -1,12 +1,18 @@
 #version 450
 
-// Flat shader.
+// Flat shader. Blinn-Phong lighting.
 
 const int MAX_LIGHTS = 10;
 
+// Light kinds. Must match `light.rs`.
+const uint POINT = 0u;
+const uint DIRECTIONAL = 1u;
+const uint SPOT = 2u;
+
 layout(location = 0) in vec4 v_Position;
 layout(location = 1) in vec3 v_Normal;
 layout(location = 2) in vec3 f_Colour;
+layout(location = 3) in vec2 v_TexCoord;
 
 layout(location = 0) out vec4 o_Colour;
 
@@ -14,28 +20,70 @@ struct Light {
   mat4 projection;
   vec4 position;
   vec4 colour;
+  vec4 direction;
+  vec4 cone;
+  uvec4 kind;
 };
 
 layout(set = 0, binding = 2) uniform Lights {
-  Light u_Lights[2];
+  Light u_Lights[MAX_LIGHTS];
 };
 
 layout(set = 0, binding = 3) uniform NumberOfLights {
   int u_LightCount;
 };
 
+layout(set = 0, binding = 4) uniform Ambient {
+  vec4 u_Ambient;
+};
+
+layout(set = 0, binding = 5) uniform Material {
+  vec4 m_Diffuse;
+  vec4 m_Specular; // Shininess in w.
+  vec4 m_Emissive;
+  vec4 m_Pbr; // Roughness then metallic. Only for the PBR shaders.
+};
+
+layout(set = 0, binding = 6) uniform Eye {
+  vec4 u_Eye;
+};
+
+layout(set = 0, binding = 7) uniform texture2D t_Texture;
+layout(set = 0, binding = 8) uniform sampler s_Texture;
+
 void main() {
   vec3 normal = normalize(v_Normal);
-  vec3 ambient = vec3(0.05, 0.05, 0.05);
+  vec3 view_dir = normalize(u_Eye.xyz - v_Position.xyz);
+  vec3 texel = texture(sampler2D(t_Texture, s_Texture), v_TexCoord).rgb;
+  vec3 base = f_Colour * texel * m_Diffuse.xyz;
 
-  vec3 colour = ambient;
+  vec3 colour = u_Ambient.xyz * base + m_Emissive.xyz;
   for(int i = 0; i < u_LightCount && i < MAX_LIGHTS; ++i) {
     Light light = u_Lights[i];
-    vec4 light_local = light.projection * v_Position;
-    vec3 light_dir = normalize(light.position.xyz - v_Position.xyz);
+    vec3 light_dir;
+    float strength = 1.0;
+    if (light.kind.x == DIRECTIONAL) {
+      light_dir = normalize(-light.direction.xyz);
+    } else {
+      light_dir = normalize(light.position.xyz - v_Position.xyz);
+    }
+    if (light.kind.x == SPOT) {
+      // Fade from the inner cone to the outer cone. Both are stored as cosines.
+      float spot_cos = dot(-light_dir, normalize(light.direction.xyz));
+      if (light.cone.x > light.cone.y) {
+        strength = smoothstep(light.cone.y, light.cone.x, spot_cos);
+      } else {
+        strength = step(light.cone.y, spot_cos);
+      }
+    }
     float diffuse = max(0.0, dot(normal, light_dir));
-    colour += diffuse * light.colour.xyz;
+    float specular = 0.0;
+    if (diffuse > 0.0) {
+      vec3 halfway = normalize(light_dir + view_dir);
+      specular = pow(max(0.0, dot(normal, halfway)), m_Specular.w);
+    }
+    colour += strength * light.colour.xyz * (diffuse * base + specular * m_Specular.xyz);
   }
   
-  o_Colour = vec4(colour, 1.0) * vec4(f_Colour, 1.0);
+  o_Colour = vec4(colour, 1.0);
 }
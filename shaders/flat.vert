@@ -5,9 +5,18 @@
 layout(location = 0) in vec3 i_Position;
 layout(location = 1) in vec3 i_Normal;
 layout(location = 2) in vec3 i_Colour;
+layout(location = 3) in vec2 i_TexCoord;
 layout(location = 0) out vec4 v_Position;
 layout(location = 1) out vec3 v_Normal;
 layout(location = 2) out vec3 f_Colour;
+layout(location = 3) out vec2 v_TexCoord;
+
+// Per instance.
+layout(location = 4) in vec4 i_Model0;
+layout(location = 5) in vec4 i_Model1;
+layout(location = 6) in vec4 i_Model2;
+layout(location = 7) in vec4 i_Model3;
+layout(location = 8) in vec3 i_Tint;
 
 layout(set = 0, binding = 0) uniform Projection {
   mat4 u_Camera;
@@ -18,9 +27,11 @@ layout(set = 0, binding = 1) uniform Translate {
 };
 
 void main() {  
-  v_Position = u_Rotation * vec4(i_Position, 1.0);
-  v_Normal = mat3(u_Rotation) * i_Normal;
-  f_Colour = i_Colour;
+  mat4 model = mat4(i_Model0, i_Model1, i_Model2, i_Model3);
+  v_Position = u_Rotation * model * vec4(i_Position, 1.0);
+  v_Normal = mat3(u_Rotation * model) * i_Normal;
+  f_Colour = i_Colour * i_Tint;
+  v_TexCoord = i_TexCoord;
   gl_Position = u_Camera * v_Position;
   gl_Position.z = 0.5 * (gl_Position.z + gl_Position.w);
 }
@@ -0,0 +1,125 @@
+#version 450
+
+// Physically based shader. Metallic-roughness with a Cook-Torrance specular term. Uses the
+// flat vertex shader.
+
+const int MAX_LIGHTS = 10;
+const float PI = 3.14159265359;
+
+// Light kinds. Must match `light.rs`.
+const uint POINT = 0u;
+const uint DIRECTIONAL = 1u;
+const uint SPOT = 2u;
+
+layout(location = 0) in vec4 v_Position;
+layout(location = 1) in vec3 v_Normal;
+layout(location = 2) in vec3 f_Colour;
+layout(location = 3) in vec2 v_TexCoord;
+
+layout(location = 0) out vec4 o_Colour;
+
+struct Light {
+  mat4 projection;
+  vec4 position;
+  vec4 colour;
+  vec4 direction;
+  vec4 cone;
+  uvec4 kind;
+};
+
+layout(set = 0, binding = 2) uniform Lights {
+  Light u_Lights[MAX_LIGHTS];
+};
+
+layout(set = 0, binding = 3) uniform NumberOfLights {
+  int u_LightCount;
+};
+
+layout(set = 0, binding = 4) uniform Ambient {
+  vec4 u_Ambient;
+};
+
+layout(set = 0, binding = 5) uniform Material {
+  vec4 m_Diffuse;
+  vec4 m_Specular; // Only for the flat shaders.
+  vec4 m_Emissive;
+  vec4 m_Pbr; // Roughness then metallic.
+};
+
+layout(set = 0, binding = 6) uniform Eye {
+  vec4 u_Eye;
+};
+
+layout(set = 0, binding = 7) uniform texture2D t_Texture;
+layout(set = 0, binding = 8) uniform sampler s_Texture;
+
+// GGX normal distribution.
+float distribution(vec3 normal, vec3 halfway, float roughness) {
+  float a2 = pow(roughness, 4.0);
+  float n_dot_h = max(dot(normal, halfway), 0.0);
+  float d = n_dot_h * n_dot_h * (a2 - 1.0) + 1.0;
+  return a2 / (PI * d * d);
+}
+
+// Smith's method with Schlick-GGX for both the view and the light.
+float geometry(float n_dot_v, float n_dot_l, float roughness) {
+  float k = (roughness + 1.0) * (roughness + 1.0) / 8.0;
+  return n_dot_v / (n_dot_v * (1.0 - k) + k) * n_dot_l / (n_dot_l * (1.0 - k) + k);
+}
+
+// Schlick's approximation.
+vec3 fresnel(float cos_theta, vec3 f0) {
+  return f0 + (1.0 - f0) * pow(1.0 - cos_theta, 5.0);
+}
+
+void main() {
+  vec3 normal = normalize(v_Normal);
+  vec3 view_dir = normalize(u_Eye.xyz - v_Position.xyz);
+  vec3 texel = texture(sampler2D(t_Texture, s_Texture), v_TexCoord).rgb;
+  vec3 albedo = f_Colour * texel * m_Diffuse.xyz;
+  float roughness = clamp(m_Pbr.x, 0.04, 1.0);
+  float metallic = clamp(m_Pbr.y, 0.0, 1.0);
+  vec3 f0 = mix(vec3(0.04), albedo, metallic);
+  float n_dot_v = max(dot(normal, view_dir), 0.0001);
+
+  vec3 colour = u_Ambient.xyz * albedo + m_Emissive.xyz;
+  for(int i = 0; i < u_LightCount && i < MAX_LIGHTS; ++i) {
+    Light light = u_Lights[i];
+    vec3 light_dir;
+    float strength = 1.0;
+    if (light.kind.x == DIRECTIONAL) {
+      light_dir = normalize(-light.direction.xyz);
+    } else {
+      light_dir = normalize(light.position.xyz - v_Position.xyz);
+    }
+    if (light.kind.x == SPOT) {
+      // Fade from the inner cone to the outer cone. Both are stored as cosines.
+      float spot_cos = dot(-light_dir, normalize(light.direction.xyz));
+      if (light.cone.x > light.cone.y) {
+        strength = smoothstep(light.cone.y, light.cone.x, spot_cos);
+      } else {
+        strength = step(light.cone.y, spot_cos);
+      }
+    }
+
+    float n_dot_l = dot(normal, light_dir);
+    if (n_dot_l <= 0.0) {
+      continue;
+    }
+
+    vec3 halfway = normalize(light_dir + view_dir);
+    vec3 f = fresnel(max(dot(halfway, view_dir), 0.0), f0);
+    vec3 specular = distribution(normal, halfway, roughness)
+      * geometry(n_dot_v, n_dot_l, roughness)
+      * f
+      / (4.0 * n_dot_v * n_dot_l + 0.0001);
+    vec3 diffuse = (vec3(1.0) - f) * (1.0 - metallic) * albedo / PI;
+
+    // Scaled by pi so a white light on a white face is about as bright as the flat shaders.
+    colour += strength * PI * light.colour.xyz * (diffuse + specular) * n_dot_l;
+  }
+
+  // Reinhard tone mapping then gamma correction as the swap chain isn't sRGB.
+  colour = colour / (colour + vec3(1.0));
+  o_Colour = vec4(pow(colour, vec3(1.0 / 2.2)), 1.0);
+}
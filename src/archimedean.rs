@@ -0,0 +1,119 @@
+//! The thirteen Archimedean solids. Each is made by running Conway operations on a platonic
+//! solid and then put into canonical form, which evens out the edges to all be the same
+//! length. The edges all touch the unit sphere.
+//!
+//! Being plain `Polyhedron`s they can be presented or used as seeds for more operations.
+use crate::polyhedron::{Polyhedron, Specification, VtFc};
+
+fn canonical(notation: &str) -> Polyhedron<VtFc> {
+    Specification::parse(notation)
+        .expect("Archimedean notation is valid.")
+        .produce()
+        .canonicalize()
+}
+
+/// `tT`. Four triangles and four hexagons.
+pub fn truncated_tetrahedron() -> Polyhedron<VtFc> {
+    canonical("tT")
+}
+
+/// `aC`. Eight triangles and six squares.
+pub fn cuboctahedron() -> Polyhedron<VtFc> {
+    canonical("aC")
+}
+
+/// `tC`. Eight triangles and six octagons.
+pub fn truncated_cube() -> Polyhedron<VtFc> {
+    canonical("tC")
+}
+
+/// `tO`. Six squares and eight hexagons.
+pub fn truncated_octahedron() -> Polyhedron<VtFc> {
+    canonical("tO")
+}
+
+/// `eC`. Eight triangles and eighteen squares.
+pub fn rhombicuboctahedron() -> Polyhedron<VtFc> {
+    canonical("eC")
+}
+
+/// `bC`. Twelve squares, eight hexagons and six octagons.
+pub fn truncated_cuboctahedron() -> Polyhedron<VtFc> {
+    canonical("bC")
+}
+
+/// `sC`. Thirty two triangles and six squares.
+pub fn snub_cube() -> Polyhedron<VtFc> {
+    canonical("sC")
+}
+
+/// `aD`. Twenty triangles and twelve pentagons.
+pub fn icosidodecahedron() -> Polyhedron<VtFc> {
+    canonical("aD")
+}
+
+/// `tD`. Twenty triangles and twelve decagons.
+pub fn truncated_dodecahedron() -> Polyhedron<VtFc> {
+    canonical("tD")
+}
+
+/// `tI`. Twelve pentagons and twenty hexagons. The football.
+pub fn truncated_icosahedron() -> Polyhedron<VtFc> {
+    canonical("tI")
+}
+
+/// `eD`. Twenty triangles, thirty squares and twelve pentagons.
+pub fn rhombicosidodecahedron() -> Polyhedron<VtFc> {
+    canonical("eD")
+}
+
+/// `bD`. Thirty squares, twenty hexagons and twelve decagons.
+pub fn truncated_icosidodecahedron() -> Polyhedron<VtFc> {
+    canonical("bD")
+}
+
+/// `sD`. Eighty triangles and twelve pentagons.
+pub fn snub_dodecahedron() -> Polyhedron<VtFc> {
+    canonical("sD")
+}
+
+#[cfg(test)]
+mod test {
+    use cgmath::prelude::*;
+
+    use super::*;
+    use crate::polyhedron::VertexAndFaceOps;
+
+    #[test]
+    fn uniform_edges() {
+        let solids: Vec<(Polyhedron<VtFc>, usize, usize)> = vec![
+            (truncated_tetrahedron(), 12, 8),
+            (cuboctahedron(), 12, 14),
+            (truncated_cube(), 24, 14),
+            (truncated_octahedron(), 24, 14),
+            (rhombicuboctahedron(), 24, 26),
+            (truncated_cuboctahedron(), 48, 26),
+            (snub_cube(), 24, 38),
+            (icosidodecahedron(), 30, 32),
+            (truncated_dodecahedron(), 60, 32),
+            (truncated_icosahedron(), 60, 32),
+            (rhombicosidodecahedron(), 60, 62),
+            (truncated_icosidodecahedron(), 120, 62),
+            (snub_dodecahedron(), 60, 92),
+        ];
+
+        for (solid, vertex_count, face_count) in solids {
+            let (vertices, faces) = solid.vertices_and_faces();
+            assert!(vertices.len() == vertex_count && faces.len() == face_count);
+
+            let lengths: Vec<f64> = solid
+                .edges()
+                .into_iter()
+                .map(|(v1, v2)| vertices[v1].distance(vertices[v2]))
+                .collect();
+            let shortest = lengths.iter().cloned().fold(f64::MAX, f64::min);
+            let longest = lengths.iter().cloned().fold(0.0, f64::max);
+            assert!(longest - shortest < 1e-6, "{} to {}", shortest, longest);
+        }
+    }
+}
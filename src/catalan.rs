@@ -0,0 +1,128 @@
+//! The thirteen Catalan solids, the duals of the Archimedean solids. Each is made by taking
+//! the dual of an Archimedean solid's notation and putting it into canonical form so the
+//! faces all come out the same shape. The edges all touch the unit sphere.
+//!
+//! Being plain `Polyhedron`s they can be presented or used as seeds for more operations.
+use crate::polyhedron::{Polyhedron, Specification, VtFc};
+
+/// The dual of the Archimedean solid made by `notation`.
+fn canonical_dual(notation: &str) -> Polyhedron<VtFc> {
+    Specification::parse(&format!("d{}", notation))
+        .expect("Archimedean notation is valid.")
+        .produce()
+        .canonicalize()
+}
+
+/// `kT`, the dual of the truncated tetrahedron. Twelve triangles.
+pub fn triakis_tetrahedron() -> Polyhedron<VtFc> {
+    canonical_dual("tT")
+}
+
+/// `jC`, the dual of the cuboctahedron. Twelve rhombi.
+pub fn rhombic_dodecahedron() -> Polyhedron<VtFc> {
+    canonical_dual("aC")
+}
+
+/// `kO`, the dual of the truncated cube. Twenty four triangles.
+pub fn triakis_octahedron() -> Polyhedron<VtFc> {
+    canonical_dual("tC")
+}
+
+/// `kC`, the dual of the truncated octahedron. Twenty four triangles.
+pub fn tetrakis_hexahedron() -> Polyhedron<VtFc> {
+    canonical_dual("tO")
+}
+
+/// `oC`, the dual of the rhombicuboctahedron. Twenty four kites.
+pub fn deltoidal_icositetrahedron() -> Polyhedron<VtFc> {
+    canonical_dual("eC")
+}
+
+/// `mC`, the dual of the truncated cuboctahedron. Forty eight triangles.
+pub fn disdyakis_dodecahedron() -> Polyhedron<VtFc> {
+    canonical_dual("bC")
+}
+
+/// `gC`, the dual of the snub cube. Twenty four pentagons.
+pub fn pentagonal_icositetrahedron() -> Polyhedron<VtFc> {
+    canonical_dual("sC")
+}
+
+/// `jD`, the dual of the icosidodecahedron. Thirty rhombi.
+pub fn rhombic_triacontahedron() -> Polyhedron<VtFc> {
+    canonical_dual("aD")
+}
+
+/// `kI`, the dual of the truncated dodecahedron. Sixty triangles.
+pub fn triakis_icosahedron() -> Polyhedron<VtFc> {
+    canonical_dual("tD")
+}
+
+/// `kD`, the dual of the truncated icosahedron. Sixty triangles.
+pub fn pentakis_dodecahedron() -> Polyhedron<VtFc> {
+    canonical_dual("tI")
+}
+
+/// `oD`, the dual of the rhombicosidodecahedron. Sixty kites.
+pub fn deltoidal_hexecontahedron() -> Polyhedron<VtFc> {
+    canonical_dual("eD")
+}
+
+/// `mD`, the dual of the truncated icosidodecahedron. One hundred and twenty triangles.
+pub fn disdyakis_triacontahedron() -> Polyhedron<VtFc> {
+    canonical_dual("bD")
+}
+
+/// `gD`, the dual of the snub dodecahedron. Sixty pentagons.
+pub fn pentagonal_hexecontahedron() -> Polyhedron<VtFc> {
+    canonical_dual("sD")
+}
+
+#[cfg(test)]
+mod test {
+    use cgmath::prelude::*;
+
+    use super::*;
+    use crate::polyhedron::VertexAndFaceOps;
+
+    #[test]
+    fn congruent_faces() {
+        let solids: Vec<(Polyhedron<VtFc>, usize, usize)> = vec![
+            (triakis_tetrahedron(), 8, 12),
+            (rhombic_dodecahedron(), 14, 12),
+            (triakis_octahedron(), 14, 24),
+            (tetrakis_hexahedron(), 14, 24),
+            (deltoidal_icositetrahedron(), 26, 24),
+            (disdyakis_dodecahedron(), 26, 48),
+            (pentagonal_icositetrahedron(), 38, 24),
+            (rhombic_triacontahedron(), 32, 30),
+            (triakis_icosahedron(), 32, 60),
+            (pentakis_dodecahedron(), 32, 60),
+            (deltoidal_hexecontahedron(), 62, 60),
+            (disdyakis_triacontahedron(), 62, 120),
+            (pentagonal_hexecontahedron(), 92, 60),
+        ];
+
+        for (solid, vertex_count, face_count) in solids {
+            let (vertices, faces) = solid.vertices_and_faces();
+            assert!(vertices.len() == vertex_count && faces.len() == face_count);
+
+            // Every face has the same sides going around, if not starting from the same one.
+            let sides = |face: &[usize]| {
+                let mut sides: Vec<f64> = (0..face.len())
+                    .map(|i| vertices[face[i]].distance(vertices[face[(i + 1) % face.len()]]))
+                    .collect();
+                sides.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                sides
+            };
+            let first = sides(&faces[0]);
+            for face in faces.iter() {
+                let sides = sides(face);
+                assert!(sides.len() == first.len());
+                for (a, b) in sides.iter().zip(first.iter()) {
+                    assert!((a - b).abs() < 1e-6, "{} against {}", a, b);
+                }
+            }
+        }
+    }
+}
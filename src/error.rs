@@ -0,0 +1,99 @@
+//! One error for the whole crate so callers can handle failures from any part of it with
+//! `?`. The errors each part already gives are kept whole inside so they can still be
+//! matched on.
+use std::io;
+
+use thiserror::Error;
+#[cfg(feature = "render")]
+use winit::error::{EventLoopError, OsError};
+
+#[cfg(feature = "render")]
+use crate::input::{BindingsError, RecordingError};
+use crate::polyhedron::{NotationError, OpError, PolyhedronError, SeedError};
+use crate::polyhedron::import::obj::ImportError;
+use crate::polyhedron::repl::CommandError;
+#[cfg(feature = "render")]
+use crate::scene::ReplaceError;
+#[cfg(feature = "render")]
+use crate::shader::SpirvError;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Building up Conway operations.
+    #[error(transparent)]
+    Operation(#[from] OpError),
+    #[error(transparent)]
+    Notation(#[from] NotationError),
+
+    /// Vertices and faces that don't make a valid polyhedron.
+    #[error(transparent)]
+    Polyhedron(#[from] PolyhedronError),
+    #[error(transparent)]
+    Seed(#[from] SeedError),
+    #[error(transparent)]
+    Import(#[from] ImportError),
+    #[error(transparent)]
+    Command(#[from] CommandError),
+
+    /// Loading and compiling shaders.
+    #[cfg(feature = "shaderc")]
+    #[error("Shader failed: {0}")]
+    Shader(#[from] shaderc::Error),
+    #[cfg(feature = "render")]
+    #[error(transparent)]
+    Spirv(#[from] SpirvError),
+
+    /// Setting up the window and video device and getting things onto it.
+    #[cfg(feature = "render")]
+    #[error("Couldn't open a window: {0}")]
+    Window(#[from] OsError),
+    #[cfg(feature = "render")]
+    #[error("The window's event loop failed: {0}")]
+    EventLoop(#[from] EventLoopError),
+    #[cfg(feature = "render")]
+    #[error("Couldn't draw in the window: {0}")]
+    Surface(#[from] wgpu::CreateSurfaceError),
+    #[cfg(feature = "render")]
+    #[error("Couldn't find a video device to draw with.")]
+    NoAdapter,
+    #[cfg(feature = "render")]
+    #[error("Couldn't open the video device: {0}")]
+    Device(#[from] wgpu::RequestDeviceError),
+    #[cfg(feature = "render")]
+    #[error(transparent)]
+    Geometry(#[from] ReplaceError),
+    #[cfg(feature = "render")]
+    #[error("Timed out waiting for the captures to be read back.")]
+    CaptureTimeout,
+
+    #[cfg(feature = "render")]
+    #[error(transparent)]
+    Bindings(#[from] BindingsError),
+    #[cfg(feature = "render")]
+    #[error(transparent)]
+    Recording(#[from] RecordingError),
+
+    /// Reading and writing files such as exports.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::polyhedron::Specification;
+
+    #[test]
+    fn wraps_notation_errors() {
+        let parse = || -> Result<()> {
+            Specification::parse("kQ")?;
+            Ok(())
+        };
+        match parse() {
+            Err(Error::Notation(NotationError::UnknownSeed('Q', 1))) => (),
+            other => panic!("Unexpected {:?}", other),
+        }
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! Common geomtery data types and operations that are used in polyhedron generation.
 use std::cmp::Ordering;
+use std::collections::HashMap;
 
 use derive_getters::Getters;
 use cgmath::{Point3, Vector3, BaseFloat};
@@ -9,8 +10,16 @@ use cgmath::prelude::*;
 
 mod plane;
 //mod line;
+mod half_edge;
+mod hull;
+mod spherical;
+mod voronoi;
 
 pub use self::plane::Plane;
+pub use self::half_edge::HalfEdgeMesh;
+pub use self::hull::convex_hull;
+pub use self::spherical::{Spherical, great_circle_distance};
+pub use self::voronoi::spherical_voronoi;
 
 /// Produce the golden ratio of 1.6180339887...
 ///
@@ -72,19 +81,20 @@ pub fn sum_three_points<S: BaseFloat>(
 ///
 /// Using [this formula](http://paulbourke.net/geometry/polygonmesh/). You need to scroll
 /// down most of the page. It's 'Centroid of a 3D shell described by 3 vertex facets'.
-pub fn convex_planar_polygon_centroid(vertices: &[Point3<f64>]) -> Point3<f64> {
+pub fn convex_planar_polygon_centroid<S: BaseFloat>(vertices: &[Point3<S>]) -> Point3<S> {
     // Break into triangles by rotating on a starting axis. This works because it's
     // assumed to be a convex polygon.
     let p1 = vertices[0];
+    let three = S::from(3.0).unwrap();
 
-    let mut summed_area = 0.0;
-    let mut summed_point_area: Point3<f64> = Point3::new(0.0, 0.0, 0.0);
+    let mut summed_area = S::zero();
+    let mut summed_point_area: Point3<S> = Point3::new(S::zero(), S::zero(), S::zero());
     
     for i in 1..(vertices.len() - 1) {
         let p2 = vertices[i];
         let p3 = vertices[i + 1];
 
-        let average = sum_three_points(&p1, &p2, &p3) / 3.0;
+        let average = sum_three_points(&p1, &p2, &p3) / three;
         let area = (p2 - p1).cross(p3 - p1).magnitude();
         summed_point_area.x += area * average.x;
         summed_point_area.y += area * average.y;
@@ -145,7 +155,7 @@ pub fn clockwise<S: BaseFloat>(
     
     let ordering = rc
         .cross(cc)
-        .dot(normal.clone());
+        .dot(*normal);
 
     if ordering > S::zero() {
         Ordering::Greater
@@ -182,11 +192,104 @@ pub fn point_line_lengthen<S: BaseFloat>(point: &Point3<S>, distance: S) -> Poin
     Point3::new(magnified.x, magnified.y, magnified.z)
 }
 
+/// Merge points closer together than `epsilon`. Returns the merged points and for each of
+/// the original `points` the index of the merged point it became. Each merged point keeps
+/// the position of the first of the points merged into it.
+pub fn weld_vertices(points: &[Point3<f64>], epsilon: f64) -> (Vec<Point3<f64>>, Vec<usize>) {
+    // Points are bucketed into cubes `epsilon` wide so only the neighbouring cubes need to
+    // be searched for a match.
+    let cell = |p: &Point3<f64>| -> (i64, i64, i64) {
+        let size = epsilon.max(f64::MIN_POSITIVE);
+        ((p.x / size).floor() as i64, (p.y / size).floor() as i64, (p.z / size).floor() as i64)
+    };
+    let mut cells: HashMap<(i64, i64, i64), Vec<usize>> = HashMap::new();
+    let mut welded: Vec<Point3<f64>> = Vec::new();
+
+    let mapping = points
+        .iter()
+        .map(|point| {
+            let (x, y, z) = cell(point);
+            let neighbours = (x - 1..=x + 1)
+                .flat_map(|x| (y - 1..=y + 1).map(move |y| (x, y)))
+                .flat_map(|(x, y)| (z - 1..=z + 1).map(move |z| (x, y, z)));
+            let found = neighbours
+                .filter_map(|key| cells.get(&key))
+                .flat_map(|indexes| indexes.iter())
+                .find(|index| welded[**index].distance(*point) <= epsilon)
+                .cloned();
+
+            found.unwrap_or_else(|| {
+                welded.push(*point);
+                cells.entry((x, y, z)).or_default().push(welded.len() - 1);
+                welded.len() - 1
+            })
+        })
+        .collect();
+
+    (welded, mapping)
+}
+
+/// How far the furthest of `points` is from the plane that best fits them all. The plane
+/// passes through the mean of the points with the normal found by Newell's method so the
+/// points must go around a polygon in order.
+pub fn plane_deviation<S: BaseFloat>(points: &[Point3<S>]) -> S {
+    let len = points.len();
+    let normal = (0..len)
+        .fold(Vector3::zero(), |n, i| {
+            let (p1, p2) = (points[i], points[(i + 1) % len]);
+            n + Vector3::new(
+                (p1.y - p2.y) * (p1.z + p2.z),
+                (p1.z - p2.z) * (p1.x + p2.x),
+                (p1.x - p2.x) * (p1.y + p2.y),
+            )
+        })
+        .normalize();
+    let mean = Point3::centroid(points);
+
+    points
+        .iter()
+        .map(|point| (point - mean).dot(normal).abs())
+        .fold(S::zero(), S::max)
+}
 
+/// Where the ray from `origin` heading in `direction` crosses the `triangle`, given as how
+/// many lengths of `direction` along the ray it is. The triangle can be hit from either
+/// side. `None` when the ray misses or the triangle is behind the origin.
+///
+/// [Möller–Trumbore](https://en.wikipedia.org/wiki/M%C3%B6ller%E2%80%93Trumbore_intersection_algorithm)
+pub fn ray_triangle_intersection<S: BaseFloat>(
+    origin: Point3<S>, direction: Vector3<S>, triangle: [Point3<S>; 3],
+) -> Option<S> {
+    let edge1 = triangle[1] - triangle[0];
+    let edge2 = triangle[2] - triangle[0];
+    let p = direction.cross(edge2);
+    let determinant = edge1.dot(p);
+    if determinant.abs() <= S::epsilon() {
+        return None; // The ray runs along the plane of the triangle.
+    }
+
+    let to_origin = origin - triangle[0];
+    let u = to_origin.dot(p) / determinant;
+    if u < S::zero() || u > S::one() {
+        return None;
+    }
+
+    let q = to_origin.cross(edge1);
+    let v = direction.dot(q) / determinant;
+    if v < S::zero() || u + v > S::one() {
+        return None;
+    }
+
+    let distance = edge2.dot(q) / determinant;
+    if distance > S::zero() { Some(distance) } else { None }
+}
 
 #[cfg(test)]
 mod test {
+    use std::collections::HashSet;
+
     use super::*;
+    use crate::polyhedron::{ConwayDescription, VertexAndFaceOps};
 
     #[test]
     fn normal_makes_sense() {
@@ -218,6 +321,229 @@ mod test {
         assert!(Ordering::Less == clockwise(&relative, &c_anti, &center, &normal));
     }
 
+    #[test]
+    fn plane_deviation_of_quads() {
+        let square = [
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(1.0, 1.0, 0.0),
+            Point3::new(0.0, 1.0, 0.0),
+        ];
+        assert!(plane_deviation(&square) < 1e-12);
+
+        let mut skewed = square;
+        skewed[2].z = 0.4;
+        let deviation = plane_deviation(&skewed);
+        assert!(deviation > 0.05 && deviation <= 0.1);
+    }
+
+    #[test]
+    fn ray_hits_triangle() {
+        let triangle = [
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(2.0, 0.0, 0.0),
+            Point3::new(0.0, 2.0, 0.0),
+        ];
+        let down = Vector3::new(0.0, 0.0, -2.0);
+
+        let inside = Point3::new(0.5, 0.5, 4.0);
+        let outside = Point3::new(1.5, 1.5, 4.0);
+
+        assert!(ray_triangle_intersection(inside, down, triangle) == Some(2.0));
+        assert!(ray_triangle_intersection(inside, -down, triangle).is_none());
+        assert!(ray_triangle_intersection(outside, down, triangle).is_none());
+    }
+
+    #[test]
+    fn hull_of_cube() {
+        let mut points: Vec<Point3<f64>> = Vec::new();
+        for x in -1..=1 {
+            for y in -1..=1 {
+                for z in -1..=1 {
+                    points.push(Point3::new(x as f64 + 2.0, y as f64, z as f64));
+                }
+            }
+        }
+
+        let hull = convex_hull(&points).unwrap();
+        let (vertices, faces) = hull.vertices_and_faces();
+        assert!(vertices.len() == 8);
+        assert!(faces.len() == 6);
+        assert!(faces.iter().all(|face| face.len() == 4));
+        assert!(vertices.iter().all(|v| (v.to_vec().magnitude() - 3f64.sqrt()).abs() < 1e-9));
+        assert!(hull.non_planar_faces(1e-9).is_empty());
+
+        // Wound outwards.
+        for face in faces {
+            let (a, b, c) = (vertices[face[0]], vertices[face[1]], vertices[face[2]]);
+            let normal = triangle_normal(a, b, c);
+            assert!(normal.dot(vertices[face[0]].to_vec()) > 0.0);
+        }
+    }
+
+    #[test]
+    fn hull_of_spiral() {
+        let n = 200;
+        let points: Vec<Point3<f64>> = (0..n)
+            .map(|i| {
+                let z = 1.0 - 2.0 * (i as f64 + 0.5) / n as f64;
+                let theta = i as f64 * 2.399963;
+                let r = (1.0 - z * z).sqrt();
+                Point3::new(r * theta.cos(), r * theta.sin(), z)
+            })
+            .chain((0..20).map(|i| Point3::new(0.01 * i as f64, 0.0, 0.0)))
+            .collect();
+
+        let hull = convex_hull(&points).unwrap();
+        let (vertices, faces) = hull.vertices_and_faces();
+        assert!(vertices.len() == n);
+        assert!(vertices.len() + faces.len() == hull.edge_count() + 2);
+
+        let dual = ConwayDescription::new()
+            .seed(&hull).unwrap()
+            .dual().unwrap()
+            .emit().unwrap();
+        assert!(dual.notation() == "dX");
+        assert!(dual.produce().vertices_and_faces().0.len() == faces.len());
+
+        let flat = [
+            Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 0.0, 0.0), Point3::new(0.0, 1.0, 0.0),
+        ];
+        assert!(convex_hull(&flat).is_none());
+    }
+
+    #[test]
+    fn voronoi_of_spiral() {
+        let n = 100;
+        let points: Vec<Point3<f64>> = (0..n)
+            .map(|i| {
+                let z = 1.0 - 2.0 * (i as f64 + 0.5) / n as f64;
+                let theta = i as f64 * 2.399963;
+                let r = (1.0 - z * z).sqrt() * (1.0 + i as f64);
+                Point3::new(r * theta.cos(), r * theta.sin(), z * (1.0 + i as f64))
+            })
+            .collect();
+
+        let (voronoi, cells) = spherical_voronoi(&points, 2.0).unwrap();
+        let (vertices, faces) = voronoi.vertices_and_faces();
+        assert!(faces.len() == n);
+        assert!(cells.iter().all(|cell| cell.is_some()));
+        assert!(vertices.len() + faces.len() == voronoi.edge_count() + 2);
+        assert!(vertices.iter().all(|v| (v.to_vec().magnitude() - 2.0).abs() < 1e-9));
+
+        // Every corner of a cell is at least as close to the cell's point as to any other
+        // and the cell is wound outwards around it.
+        let directions: Vec<Vector3<f64>> = points
+            .iter()
+            .map(|p| p.to_vec().normalize())
+            .collect();
+        for (cell, direction) in cells.iter().zip(directions.iter()) {
+            let face = &faces[cell.unwrap()];
+            for v in face {
+                let corner = vertices[*v].to_vec().normalize();
+                let own = corner.dot(*direction);
+                assert!(directions.iter().all(|other| corner.dot(*other) <= own + 1e-9));
+            }
+            let (a, b) = (vertices[face[0]].to_vec(), vertices[face[1]].to_vec());
+            assert!(a.cross(b).dot(*direction) > 0.0);
+        }
+
+        // Around the six directions along the axes are the faces of a cube.
+        let axes = [
+            Point3::new(3.0, 0.0, 0.0), Point3::new(-1.0, 0.0, 0.0),
+            Point3::new(0.0, 1.0, 0.0), Point3::new(0.0, -1.0, 0.0),
+            Point3::new(0.0, 0.0, 1.0), Point3::new(0.0, 0.0, -2.0),
+            Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 0.0, 5.0),
+        ];
+        let (cube, cells) = spherical_voronoi(&axes, 1.0).unwrap();
+        let (vertices, faces) = cube.vertices_and_faces();
+        assert!(vertices.len() == 8);
+        assert!(faces.len() == 6);
+        assert!(faces.iter().all(|face| face.len() == 4));
+
+        // The point at the origin and one of the two up the z axis are left without a face.
+        assert!(cells[6].is_none());
+        assert!(cells[4].is_some() != cells[7].is_some());
+        let faced: Vec<usize> = cells.iter().filter_map(|cell| *cell).collect();
+        assert!(faced.iter().collect::<HashSet<_>>().len() == 6);
+        for (cell, point) in cells.iter().zip(axes.iter()) {
+            if let Some(face) = cell {
+                let corner = vertices[faces[*face][0]].to_vec();
+                assert!(corner.dot(point.to_vec()) > 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn spherical_round_trip() {
+        let point = Point3::new(1.0, -2.0, 0.5);
+        let spherical = Spherical::from_cartesian(&point);
+        assert!(point.distance(spherical.to_cartesian()) < 1e-12);
+
+        let north = Spherical::from_cartesian(&Point3::new(0.0, 0.0, 3.0));
+        assert!(north.latitude().0 == std::f64::consts::FRAC_PI_2);
+        assert!(north.radius() == &3.0);
+    }
+
+    #[test]
+    fn great_circle() {
+        use std::f64::consts::PI;
+        use cgmath::Deg;
+
+        let x = Point3::new(2.0, 0.0, 0.0);
+        let y = Point3::new(0.0, 5.0, 0.0);
+        assert!((great_circle_distance(&x, &y, 1.0) - PI / 2.0).abs() < 1e-12);
+        let opposite = Point3::new(-1.0, 0.0, 0.0);
+        assert!((great_circle_distance(&x, &opposite, 2.0) - 2.0 * PI).abs() < 1e-12);
+        assert!(great_circle_distance(&x, &x, 1.0) == 0.0);
+
+        // London to New York is about 5570km.
+        let london = Spherical::new(Deg(51.5074).into(), Deg(-0.1278).into(), 1.0);
+        let new_york = Spherical::new(Deg(40.7128).into(), Deg(-74.0060).into(), 1.0);
+        assert!((london.great_circle_distance(&new_york, 6371.0) - 5570.0).abs() < 10.0);
+    }
+
+    #[test]
+    fn weld() {
+        let points = [
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(1e-10, -1e-10, 0.0),
+            Point3::new(1.0, 1e-10, 0.0),
+            Point3::new(0.0, 1.0, 0.0),
+        ];
+        let (welded, mapping) = weld_vertices(&points, 1e-9);
+
+        assert!(welded.len() == 3);
+        assert!(mapping == vec![0, 1, 0, 1, 2]);
+        assert!(welded[0] == points[0]);
+    }
+
+    #[test]
+    fn half_edge_tetrahedron() {
+        let vertices = vec![
+            Point3::new(1.0, 1.0, 1.0),
+            Point3::new(1.0, -1.0, -1.0),
+            Point3::new(-1.0, 1.0, -1.0),
+            Point3::new(-1.0, -1.0, 1.0),
+        ];
+        let faces = vec![vec![0, 1, 2], vec![0, 3, 1], vec![0, 2, 3], vec![1, 3, 2]];
+        let mesh = HalfEdgeMesh::new(vertices, &faces);
+
+        assert!(mesh.edge_count() == 6);
+        for v in 0..4 {
+            assert!(mesh.vertex_degree(v) == 3);
+            assert!(mesh.vertex_half_edges(v).all(|h| mesh.origin(h) == v));
+        }
+        for h in 0..12 {
+            assert!(mesh.twin(mesh.twin(h)) == h);
+            assert!(mesh.destination(h) == mesh.origin(mesh.twin(h)));
+        }
+
+        let (_, round_trip) = mesh.into_parts();
+        assert!(round_trip == faces);
+    }
+
     /*
     #[test]
     fn travel_line() {
@@ -0,0 +1,191 @@
+//! Half-edge mesh. Each edge is split into two half-edges going opposite ways, one for each
+//! face it borders. Every half-edge knows the one going the other way (its twin) and the
+//! next and previous half-edges around its face. This makes walking around a face or
+//! around a vertex a matter of following links instead of searching through the faces.
+//!
+//! Only closed meshes with consistently wound faces can be represented.
+use std::collections::HashMap;
+
+use cgmath::{Point3, BaseFloat};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct HalfEdge {
+    origin: usize,
+    twin: usize,
+    next: usize,
+    prev: usize,
+    face: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct HalfEdgeMesh<S: BaseFloat = f64> {
+    vertices: Vec<Point3<S>>,
+    half_edges: Vec<HalfEdge>,
+
+    /// A half-edge leaving each vertex.
+    vertex_edges: Vec<usize>,
+
+    /// A half-edge on each face.
+    face_edges: Vec<usize>,
+}
+
+impl<S: BaseFloat> HalfEdgeMesh<S> {
+    /// Link up the faces. Panics if the faces don't form a closed mesh with every face
+    /// wound the same way or if a vertex isn't part of any face.
+    pub fn new<I>(vertices: Vec<Point3<S>>, faces: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[usize]>,
+    {
+        let mut half_edges: Vec<HalfEdge> = Vec::new();
+        let mut face_edges: Vec<usize> = Vec::new();
+        let mut directed: HashMap<(usize, usize), usize> = HashMap::new();
+
+        for (f_index, face) in faces.into_iter().enumerate() {
+            let face = face.as_ref();
+            let first = half_edges.len();
+            let len = face.len();
+            face_edges.push(first);
+
+            for i in 0..len {
+                let (v1, v2) = (face[i], face[(i + 1) % len]);
+                let previous = directed.insert((v1, v2), first + i);
+                assert!(
+                    previous.is_none(),
+                    "Edge {:?} is part of two faces wound the same way.", (v1, v2),
+                );
+
+                half_edges.push(HalfEdge {
+                    origin: v1,
+                    twin: 0,
+                    next: first + (i + 1) % len,
+                    prev: first + (i + len - 1) % len,
+                    face: f_index,
+                });
+            }
+        }
+
+        let twins: Vec<usize> = half_edges
+            .iter()
+            .map(|half_edge| {
+                let destination = half_edges[half_edge.next].origin;
+                *directed
+                    .get(&(destination, half_edge.origin))
+                    .expect("Mesh isn't closed.")
+            })
+            .collect();
+
+        let mut vertex_edges: Vec<Option<usize>> = vec![None; vertices.len()];
+        for (h, (half_edge, twin)) in half_edges.iter_mut().zip(twins).enumerate() {
+            half_edge.twin = twin;
+            vertex_edges[half_edge.origin].get_or_insert(h);
+        }
+
+        HalfEdgeMesh {
+            vertices,
+            half_edges,
+            vertex_edges: vertex_edges
+                .into_iter()
+                .map(|h| h.expect("Vertex isn't part of any face."))
+                .collect(),
+            face_edges,
+        }
+    }
+
+    pub fn vertices(&self) -> &[Point3<S>] {
+        &self.vertices
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    pub fn face_count(&self) -> usize {
+        self.face_edges.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.half_edges.len() / 2
+    }
+
+    /// The vertex a half-edge leaves from.
+    pub fn origin(&self, h: usize) -> usize {
+        self.half_edges[h].origin
+    }
+
+    /// The vertex a half-edge goes to.
+    pub fn destination(&self, h: usize) -> usize {
+        self.half_edges[self.half_edges[h].next].origin
+    }
+
+    /// The half-edge going the other way along the same edge.
+    pub fn twin(&self, h: usize) -> usize {
+        self.half_edges[h].twin
+    }
+
+    pub fn next(&self, h: usize) -> usize {
+        self.half_edges[h].next
+    }
+
+    pub fn prev(&self, h: usize) -> usize {
+        self.half_edges[h].prev
+    }
+
+    /// The face a half-edge goes around.
+    pub fn face(&self, h: usize) -> usize {
+        self.half_edges[h].face
+    }
+
+    /// The half-edges around a face in winding order.
+    pub fn face_half_edges(&self, f: usize) -> impl Iterator<Item = usize> + '_ {
+        let start = self.face_edges[f];
+        let mut current = Some(start);
+
+        std::iter::from_fn(move || {
+            let h = current?;
+            let next = self.half_edges[h].next;
+            current = if next == start { None } else { Some(next) };
+            Some(h)
+        })
+    }
+
+    /// The half-edges leaving a vertex. Going from one to the next moves to the face
+    /// before it in the winding of the faces so the faces come out in the order that the
+    /// dual face joining them is wound the same way.
+    pub fn vertex_half_edges(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        let start = self.vertex_edges[v];
+        let mut current = Some(start);
+
+        std::iter::from_fn(move || {
+            let h = current?;
+            let next = self.half_edges[self.half_edges[h].prev].twin;
+            current = if next == start { None } else { Some(next) };
+            Some(h)
+        })
+    }
+
+    pub fn face_vertices(&self, f: usize) -> impl Iterator<Item = usize> + '_ {
+        self.face_half_edges(f).map(move |h| self.origin(h))
+    }
+
+    pub fn vertex_faces(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        self.vertex_half_edges(v).map(move |h| self.face(h))
+    }
+
+    pub fn face_degree(&self, f: usize) -> usize {
+        self.face_half_edges(f).count()
+    }
+
+    pub fn vertex_degree(&self, v: usize) -> usize {
+        self.vertex_half_edges(v).count()
+    }
+
+    /// Back to vertices and the vertex indexes of each face.
+    pub fn into_parts(self) -> (Vec<Point3<S>>, Vec<Vec<usize>>) {
+        let faces = (0..self.face_count())
+            .map(|f| self.face_vertices(f).collect())
+            .collect();
+
+        (self.vertices, faces)
+    }
+}
@@ -0,0 +1,177 @@
+//! Convex hull of a cloud of points. Built up one point at a time. Each point outside the
+//! hull so far removes the faces it can see and is joined to the edges around the hole.
+//!
+//! The hull starts out as triangles. Neighbouring triangles on the same plane are merged
+//! back into one face at the end so that the hull of the corners of a cube has square
+//! faces. Vertices left in the middle of a straight edge are dropped.
+use std::collections::HashMap;
+
+use cgmath::{Point3, Vector3};
+use cgmath::prelude::*;
+
+use crate::polyhedron::{Polyhedron, VtFc};
+
+/// Build the smallest convex polyhedron holding all the `points`. It is moved so that the
+/// average of its vertices is on the origin as the Conway operations expect. `None` when
+/// the points all lie on one plane.
+pub fn convex_hull(points: &[Point3<f64>]) -> Option<Polyhedron<VtFc>> {
+    let faces = hull_faces(points)?;
+
+    // Renumber the vertices actually used.
+    let mut renumbered: HashMap<usize, usize> = HashMap::new();
+    let mut vertices: Vec<Point3<f64>> = Vec::new();
+    let faces: Vec<Vec<usize>> = faces
+        .into_iter()
+        .map(|face| {
+            face.into_iter()
+                .map(|v| *renumbered.entry(v).or_insert_with(|| {
+                    vertices.push(points[v]);
+                    vertices.len() - 1
+                }))
+                .collect()
+        })
+        .collect();
+
+    let center = Point3::centroid(&vertices);
+    let vertices: Vec<Point3<f64>> = vertices
+        .iter()
+        .map(|v| Point3::from_vec(v - center))
+        .collect();
+    let faces: Vec<&[usize]> = faces.iter().map(|face| face.as_slice()).collect();
+
+    Some(Polyhedron::unchecked(Point3::origin(), &vertices, &faces))
+}
+
+/// The faces of the hull listing indexes into `points`, wound outwards.
+pub (super) fn hull_faces(points: &[Point3<f64>]) -> Option<Vec<Vec<usize>>> {
+    let scale = points
+        .iter()
+        .map(|p| p.x.abs().max(p.y.abs()).max(p.z.abs()))
+        .fold(0.0, f64::max);
+    let epsilon = scale * 1e-9;
+
+    let mut triangles: Vec<[usize; 3]> = start(points, epsilon)?.to_vec();
+    let normal = |t: &[usize; 3]| -> Vector3<f64> {
+        (points[t[1]] - points[t[0]]).cross(points[t[2]] - points[t[0]]).normalize()
+    };
+
+    for (p_index, point) in points.iter().enumerate() {
+        let (visible, hidden): (Vec<[usize; 3]>, Vec<[usize; 3]>) = triangles
+            .iter()
+            .partition(|t| normal(t).dot(point - points[t[0]]) > epsilon);
+        if visible.is_empty() {
+            continue;
+        }
+
+        // The horizon is made of the edges of the visible triangles that aren't shared with
+        // another visible triangle.
+        let edges: Vec<(usize, usize)> = visible
+            .iter()
+            .flat_map(|t| (0..3).map(move |i| (t[i], t[(i + 1) % 3])))
+            .collect();
+        triangles = hidden;
+        triangles.extend(edges
+            .iter()
+            .filter(|(v1, v2)| !edges.contains(&(*v2, *v1)))
+            .map(|(v1, v2)| [*v1, *v2, p_index])
+        );
+    }
+
+    Some(merge(points, &triangles, epsilon))
+}
+
+/// A tetrahedron from four points that are as far apart as can be cheaply found, with its
+/// faces wound outwards.
+fn start(points: &[Point3<f64>], epsilon: f64) -> Option<[[usize; 3]; 4]> {
+    let furthest = |distance: &dyn Fn(&Point3<f64>) -> f64| -> Option<usize> {
+        let (index, d) = points
+            .iter()
+            .map(distance)
+            .enumerate()
+            .fold((0, 0.0), |best, (i, d)| if d > best.1 { (i, d) } else { best });
+        if d > epsilon { Some(index) } else { None }
+    };
+
+    let a = 0;
+    let b = furthest(&|p| p.distance(points[a]))?;
+    let line = (points[b] - points[a]).normalize();
+    let c = furthest(&|p| (p - points[a]).cross(line).magnitude())?;
+    let plane = (points[b] - points[a]).cross(points[c] - points[a]).normalize();
+    let d = furthest(&|p| plane.dot(p - points[a]).abs())?;
+
+    // The base is wound so that it faces away from the fourth point.
+    let (a, b, c) = if plane.dot(points[d] - points[a]) > 0.0 { (a, c, b) } else { (a, b, c) };
+
+    Some([[a, b, c], [b, a, d], [c, b, d], [a, c, d]])
+}
+
+/// Join neighbouring triangles facing the same way into polygons.
+fn merge(points: &[Point3<f64>], triangles: &[[usize; 3]], epsilon: f64) -> Vec<Vec<usize>> {
+    let normals: Vec<Vector3<f64>> = triangles
+        .iter()
+        .map(|t| (points[t[1]] - points[t[0]]).cross(points[t[2]] - points[t[0]]).normalize())
+        .collect();
+    let edge_triangles: HashMap<(usize, usize), usize> = triangles
+        .iter()
+        .enumerate()
+        .flat_map(|(t_index, t)| (0..3).map(move |i| ((t[i], t[(i + 1) % 3]), t_index)))
+        .collect();
+
+    // Group the triangles by flooding across edges between triangles on the same plane.
+    let mut group: Vec<Option<usize>> = vec![None; triangles.len()];
+    let mut groups = 0;
+    for first in 0..triangles.len() {
+        if group[first].is_some() {
+            continue;
+        }
+        group[first] = Some(groups);
+        let mut stack = vec![first];
+        while let Some(t_index) = stack.pop() {
+            let t = triangles[t_index];
+            for i in 0..3 {
+                let other = edge_triangles[&(t[(i + 1) % 3], t[i])];
+                if group[other].is_none() && normals[other].dot(normals[first]) > 1.0 - 1e-9 {
+                    group[other] = Some(groups);
+                    stack.push(other);
+                }
+            }
+        }
+        groups += 1;
+    }
+
+    // Each group's outline is made of the edges not shared with another triangle of the
+    // group. Following them from one to the next goes around the face.
+    let mut outlines: Vec<HashMap<usize, usize>> = vec![HashMap::new(); groups];
+    for (t_index, t) in triangles.iter().enumerate() {
+        let g = group[t_index].unwrap();
+        for i in 0..3 {
+            let (v1, v2) = (t[i], t[(i + 1) % 3]);
+            if group[edge_triangles[&(v2, v1)]] != Some(g) {
+                outlines[g].insert(v1, v2);
+            }
+        }
+    }
+
+    outlines
+        .into_iter()
+        .map(|outline| {
+            let start = *outline.keys().min().unwrap();
+            let mut face = vec![start];
+            let mut v = outline[&start];
+            while v != start {
+                face.push(v);
+                v = outline[&v];
+            }
+
+            let len = face.len();
+            (0..len)
+                .filter(|i| {
+                    let before = points[face[*i]] - points[face[(i + len - 1) % len]];
+                    let after = points[face[(i + 1) % len]] - points[face[*i]];
+                    before.cross(after).magnitude() > epsilon * after.magnitude()
+                })
+                .map(|i| face[i])
+                .collect()
+        })
+        .collect()
+}
@@ -0,0 +1,87 @@
+//! # Spherical Coordinates
+//!
+//! Points given as latitude, longitude and distance from the origin. The poles are on the z
+//! axis with north at positive z. Longitude is zero along the positive x axis and increases
+//! towards the positive y axis.
+use derive_getters::Getters;
+use cgmath::{Point3, Rad};
+use cgmath::prelude::*;
+
+#[derive(Debug, Copy, Clone, PartialEq, Getters)]
+pub struct Spherical {
+    /// From -π/2 at the south pole to π/2 at the north pole.
+    latitude: Rad<f64>,
+
+    /// From -π to π.
+    longitude: Rad<f64>,
+
+    radius: f64,
+}
+
+impl Spherical {
+    pub fn new(latitude: Rad<f64>, longitude: Rad<f64>, radius: f64) -> Self {
+        Spherical { latitude, longitude, radius }
+    }
+
+    /// The origin itself has a latitude and longitude of zero.
+    pub fn from_cartesian(point: &Point3<f64>) -> Self {
+        let radius = point.to_vec().magnitude();
+        let latitude = if radius > 0.0 {
+            Rad((point.z / radius).clamp(-1.0, 1.0).asin())
+        } else {
+            Rad(0.0)
+        };
+
+        Spherical {
+            latitude,
+            longitude: Rad(point.y.atan2(point.x)),
+            radius,
+        }
+    }
+
+    pub fn to_cartesian(&self) -> Point3<f64> {
+        let (lat_sin, lat_cos) = self.latitude.0.sin_cos();
+        let (long_sin, long_cos) = self.longitude.0.sin_cos();
+
+        Point3::new(
+            self.radius * lat_cos * long_cos,
+            self.radius * lat_cos * long_sin,
+            self.radius * lat_sin,
+        )
+    }
+
+    /// Angle between the two points seen from the origin. Uses the haversine formula so it
+    /// stays accurate for points close together.
+    pub fn central_angle(&self, other: &Spherical) -> Rad<f64> {
+        let half_lat = (other.latitude.0 - self.latitude.0) / 2.0;
+        let half_long = (other.longitude.0 - self.longitude.0) / 2.0;
+        let h = half_lat.sin().powi(2)
+            + self.latitude.0.cos() * other.latitude.0.cos() * half_long.sin().powi(2);
+
+        Rad(2.0 * h.sqrt().min(1.0).asin())
+    }
+
+    /// Distance along the surface of a sphere of `radius` between the two points. The
+    /// radius of the points themselves is ignored.
+    pub fn great_circle_distance(&self, other: &Spherical, radius: f64) -> f64 {
+        self.central_angle(other).0 * radius
+    }
+}
+
+impl From<Point3<f64>> for Spherical {
+    fn from(point: Point3<f64>) -> Self {
+        Spherical::from_cartesian(&point)
+    }
+}
+
+impl From<Spherical> for Point3<f64> {
+    fn from(spherical: Spherical) -> Self {
+        spherical.to_cartesian()
+    }
+}
+
+/// Distance along the surface of a sphere of `radius` centered on the origin between where
+/// the lines from the origin through `p1` and `p2` cross the sphere.
+pub fn great_circle_distance(p1: &Point3<f64>, p2: &Point3<f64>, radius: f64) -> f64 {
+    Spherical::from_cartesian(p1).great_circle_distance(&Spherical::from_cartesian(p2), radius)
+}
@@ -0,0 +1,78 @@
+//! Spherical Voronoi diagram. Split a sphere into a cell around each of a set of points
+//! holding the places nearer to that point than any other. Like a Goldberg polyhedron but
+//! with cells of any size and shape, such as the countries of a made up planet.
+//!
+//! The points are first pushed out onto the sphere. The faces of their convex hull are then
+//! the Delaunay triangulation of the sphere. Each face of the hull cuts off a cap of the
+//! sphere with no points in it and the middle of the cap is a corner of the cells around
+//! the points of that face.
+use std::collections::HashMap;
+
+use cgmath::{Point3, Vector3};
+use cgmath::prelude::*;
+
+use super::HalfEdgeMesh;
+use super::hull::hull_faces;
+use crate::polyhedron::{Polyhedron, VtFc};
+
+/// A face around each of the `points` with its vertices on a sphere of `radius` centered on
+/// the origin. Also gives the index of the face around each point. A point at the origin
+/// or in the same direction as another has no face of its own so gets `None`. `None`
+/// altogether when the rest of the points all lie on one circle around the sphere.
+///
+/// The corners of the cells are all on the sphere so the faces usually aren't quite flat.
+pub fn spherical_voronoi(
+    points: &[Point3<f64>], radius: f64,
+) -> Option<(Polyhedron<VtFc>, Vec<Option<usize>>)> {
+    // Which of the `points` each direction came from.
+    let (origins, directions): (Vec<usize>, Vec<Point3<f64>>) = points
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| p.to_vec().magnitude2() > 0.0)
+        .map(|(index, p)| (index, Point3::from_vec(p.to_vec().normalize())))
+        .unzip();
+    let triangulation = hull_faces(&directions)?;
+
+    // A corner in the middle of the empty cap off each face of the hull.
+    let vertices: Vec<Point3<f64>> = triangulation
+        .iter()
+        .map(|face| {
+            let normal = (0..face.len())
+                .map(|i| {
+                    let next = directions[face[(i + 1) % face.len()]].to_vec();
+                    directions[face[i]].to_vec().cross(next)
+                })
+                .fold(Vector3::zero(), |sum, n| sum + n);
+            Point3::from_vec(normal.normalize() * radius)
+        })
+        .collect();
+
+    // The half-edge mesh needs every vertex to be on a face.
+    let mut used: Vec<usize> = triangulation.iter().flatten().cloned().collect();
+    used.sort();
+    used.dedup();
+    let renumbered: HashMap<usize, usize> = used
+        .iter()
+        .enumerate()
+        .map(|(index, point)| (*point, index))
+        .collect();
+    let triangulation: Vec<Vec<usize>> = triangulation
+        .into_iter()
+        .map(|face| face.into_iter().map(|p| renumbered[&p]).collect())
+        .collect();
+    let used_directions = used.iter().map(|p| directions[*p]).collect();
+    let mesh = HalfEdgeMesh::new(used_directions, &triangulation);
+
+    // The cell around a point goes through the faces around it in the same direction.
+    let faces: Vec<Vec<usize>> = (0..used.len())
+        .map(|point| mesh.vertex_faces(point).collect())
+        .collect();
+    let faces: Vec<&[usize]> = faces.iter().map(|face| face.as_slice()).collect();
+
+    let mut cells = vec![None; points.len()];
+    for (face, direction) in used.iter().enumerate() {
+        cells[origins[*direction]] = Some(face);
+    }
+
+    Some((Polyhedron::unchecked(Point3::origin(), &vertices, &faces), cells))
+}
@@ -2,37 +2,62 @@
 //! return a transform to be applied.
 use std::collections::HashMap;
 use std::ops::Neg;
+use std::{error, fmt, fs, io, path};
 
-use wgpu::winit::{KeyboardInput, VirtualKeyCode, ElementState};
+use winit::dpi::PhysicalPosition;
+use winit::event::{ElementState, KeyEvent, MouseButton};
+use winit::keyboard::{KeyCode, PhysicalKey};
 use cgmath::{Vector3, Zero, Rad, Deg};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::presentation::Preset;
+
+mod recording;
+
+pub use self::recording::{Event, Playback, Recording, RecordingError};
 
 pub type Camera = Vector3<f32>;
 pub type RotY = Rad<f32>;
 pub type RotX = Rad<f32>;
+pub type Yaw = Rad<f32>;
+pub type Pitch = Rad<f32>;
 
 static SET_CMPX: u16 = 0b0000_0000_0000_0001;
 static SET_CMPY: u16 = 0b0000_0000_0000_0010;
 static SET_CMPZ: u16 = 0b0000_0000_0000_0100;
+static SET_HOME: u16 = 0b0000_0000_0000_1000;
 static SET_CMNX: u16 = 0b0000_0000_0001_0000;
 static SET_CMNY: u16 = 0b0000_0000_0010_0000;
 static SET_CMNZ: u16 = 0b0000_0000_0100_0000;
+static SET_FRNT: u16 = 0b0000_0000_1000_0000;
 static SET_RSPX: u16 = 0b0000_0001_0000_0000;
 static SET_RSPY: u16 = 0b0000_0010_0000_0000;
 static SET_RSNX: u16 = 0b0000_0100_0000_0000;
 static SET_RSNY: u16 = 0b0000_1000_0000_0000;
+static SET_SCRN: u16 = 0b0001_0000_0000_0000;
+static SET_SPIN: u16 = 0b0010_0000_0000_0000;
+static SET_TOP:  u16 = 0b0100_0000_0000_0000;
+static SET_ISO:  u16 = 0b1000_0000_0000_0000;
 
 static MSK_CMPX: u16 = 0b1111_1111_1111_1110;
 static MSK_CMPY: u16 = 0b1111_1111_1111_1101;
 static MSK_CMPZ: u16 = 0b1111_1111_1111_1011;
+static MSK_HOME: u16 = 0b1111_1111_1111_0111;
 static MSK_CMNX: u16 = 0b1111_1111_1110_1111;
 static MSK_CMNY: u16 = 0b1111_1111_1101_1111;
 static MSK_CMNZ: u16 = 0b1111_1111_1011_1111;
+static MSK_FRNT: u16 = 0b1111_1111_0111_1111;
 static MSK_RSPX: u16 = 0b1111_1110_1111_1111;
 static MSK_RSPY: u16 = 0b1111_1101_1111_1111;
 static MSK_RSNX: u16 = 0b1111_1011_1111_1111;
 static MSK_RSNY: u16 = 0b1111_0111_1111_1111;
+static MSK_SCRN: u16 = 0b1110_1111_1111_1111;
+static MSK_SPIN: u16 = 0b1101_1111_1111_1111;
+static MSK_TOP:  u16 = 0b1011_1111_1111_1111;
+static MSK_ISO:  u16 = 0b0111_1111_1111_1111;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Action {
     CameraMovePX,
     CameraMovePY,
@@ -44,6 +69,12 @@ pub enum Action {
     RotateShapePY,
     RotateShapeNX,
     RotateShapeNY,
+    Screenshot,
+    ToggleSpin,
+    ResetView,
+    ViewFront,
+    ViewTop,
+    ViewIsometric,
 }
 
 impl Action {
@@ -59,6 +90,12 @@ impl Action {
             Action::RotateShapePY => SET_RSPY,
             Action::RotateShapeNX => SET_RSNX,
             Action::RotateShapeNY => SET_RSNY,
+            Action::Screenshot =>    SET_SCRN,
+            Action::ToggleSpin =>    SET_SPIN,
+            Action::ResetView =>     SET_HOME,
+            Action::ViewFront =>     SET_FRNT,
+            Action::ViewTop =>       SET_TOP,
+            Action::ViewIsometric => SET_ISO,
         }
     }
 
@@ -74,6 +111,12 @@ impl Action {
             Action::RotateShapePY => MSK_RSPY,
             Action::RotateShapeNX => MSK_RSNX,
             Action::RotateShapeNY => MSK_RSNY,
+            Action::Screenshot =>    MSK_SCRN,
+            Action::ToggleSpin =>    MSK_SPIN,
+            Action::ResetView =>     MSK_HOME,
+            Action::ViewFront =>     MSK_FRNT,
+            Action::ViewTop =>       MSK_TOP,
+            Action::ViewIsometric => MSK_ISO,
         }
     }
 }
@@ -84,6 +127,16 @@ pub trait ActionState {
     fn camera_increment(&self, increment: f32) -> Camera;
     fn x_rotation_increment(&self, increment: f32) -> Rad<f32>;
     fn y_rotation_increment(&self, increment: f32) -> Rad<f32>;
+
+    /// Whether a screenshot was asked for. Clears the request so that only one is taken per
+    /// key press.
+    fn screenshot(&mut self) -> bool;
+
+    /// Whether the idle spin was switched on or off. Clears the request like `screenshot`.
+    fn toggle_spin(&mut self) -> bool;
+
+    /// Which camera preset was asked for, if any. Clears the request like `screenshot`.
+    fn preset(&mut self) -> Option<Preset>;
 }
 
 impl ActionState for u16 {
@@ -121,14 +174,57 @@ impl ActionState for u16 {
 
         Rad(0f32)
     }
+
+    fn screenshot(&mut self) -> bool {
+        let requested = *self & SET_SCRN > 0;
+        self.off(Action::Screenshot);
+
+        requested
+    }
+
+    fn toggle_spin(&mut self) -> bool {
+        let requested = *self & SET_SPIN > 0;
+        self.off(Action::ToggleSpin);
+
+        requested
+    }
+
+    fn preset(&mut self) -> Option<Preset> {
+        let preset = [
+            (Action::ResetView, SET_HOME, Preset::Home),
+            (Action::ViewFront, SET_FRNT, Preset::Front),
+            (Action::ViewTop, SET_TOP, Preset::Top),
+            (Action::ViewIsometric, SET_ISO, Preset::Isometric),
+        ]
+            .iter()
+            .find(|(_, set, _)| *self & set > 0)
+            .map(|(action, _, preset)| {
+                self.off(*action);
+                *preset
+            });
+
+        preset
+    }
 }
 
 /// Which keypresses carry out which which actions and by how much.
+///
+/// Can be saved to and loaded from a TOML file. Anything left out of the file keeps its
+/// default. The keys are all or nothing, a `[keys]` table replaces every default key.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Bindings {
-    bindings: HashMap<VirtualKeyCode, Action>,
+    pick: MouseButton,
+    orbit: MouseButton,
+    orbit_sensitivity: f32,
+    rotate: MouseButton,
+    rotate_sensitivity: f32,
     camera_increment: f32,
     x_rotation_increment: f32,
     y_rotation_increment: f32,
+    // Last as TOML wants tables after the plain values.
+    #[serde(rename = "keys", with = "key_names")]
+    bindings: HashMap<KeyCode, Action>,
 }
 
 impl Bindings {
@@ -137,58 +233,361 @@ impl Bindings {
     ) -> Self {
         Bindings {
             bindings: HashMap::new(),
+            pick: MouseButton::Left,
+            orbit: MouseButton::Right,
+            orbit_sensitivity: 0.01,
+            rotate: MouseButton::Left,
+            rotate_sensitivity: 0.01,
             camera_increment,
             x_rotation_increment,
             y_rotation_increment,
         }
     }
 
-    pub fn bind(&mut self, vkc: VirtualKeyCode, action: Action) -> Option<Action> {
-        self.bindings.insert(vkc, action)
+    pub fn bind(&mut self, key: KeyCode, action: Action) -> Option<Action> {
+        self.bindings.insert(key, action)
+    }
+
+    pub fn unbind(&mut self, key: &KeyCode) -> Option<Action> {
+        self.bindings.remove(key)
+    }
+
+    pub fn action(&self, key: &KeyCode) -> Option<Action> {
+        self.bindings.get(key).cloned()
+    }
+
+    /// The mouse button that picks the face under the cursor when clicked. Defaults to the
+    /// left button.
+    pub fn bind_pick(&mut self, button: MouseButton) {
+        self.pick = button;
+    }
+
+    /// The mouse button held down while dragging to orbit the camera around the shape.
+    /// Defaults to the right button.
+    pub fn bind_orbit(&mut self, button: MouseButton) {
+        self.orbit = button;
+    }
+
+    /// Radians the camera orbits per pixel dragged. Make it negative to drag the other way.
+    pub fn orbit_sensitivity(&mut self, sensitivity: f32) {
+        self.orbit_sensitivity = sensitivity;
+    }
+
+    /// The mouse button held down while dragging to rotate the shape. Defaults to the left
+    /// button. Sharing it with picking is fine as a drag doesn't count as a click.
+    pub fn bind_rotate(&mut self, button: MouseButton) {
+        self.rotate = button;
     }
 
-    pub fn unbind(&mut self, vkc: &VirtualKeyCode) -> Option<Action> {
-        self.bindings.remove(vkc)
+    /// Radians the shape rotates per pixel dragged. Make it negative to drag the other way.
+    pub fn rotate_sensitivity(&mut self, sensitivity: f32) {
+        self.rotate_sensitivity = sensitivity;
+    }
+
+    pub fn from_toml(toml: &str) -> Result<Self, BindingsError> {
+        toml::from_str(toml).map_err(BindingsError::Parse)
+    }
+
+    pub fn to_toml(&self) -> Result<String, BindingsError> {
+        toml::to_string(self).map_err(BindingsError::Write)
+    }
+
+    pub fn load<P: AsRef<path::Path>>(filepath: P) -> Result<Self, BindingsError> {
+        let toml = fs::read_to_string(filepath).map_err(BindingsError::Io)?;
+        Bindings::from_toml(&toml)
+    }
+
+    pub fn save<P: AsRef<path::Path>>(&self, filepath: P) -> Result<(), BindingsError> {
+        fs::write(filepath, self.to_toml()?).map_err(BindingsError::Io)
+    }
+
+    /// Load the bindings from `filepath` if it's there. Falls back on the defaults when the
+    /// file is missing or won't load.
+    pub fn load_or_default<P: AsRef<path::Path>>(filepath: P) -> Self {
+        let filepath = filepath.as_ref();
+        match Bindings::load(filepath) {
+            Ok(bindings) => {
+                info!("Loaded the key bindings from {}.", filepath.display());
+                bindings
+            },
+            Err(BindingsError::Io(ref e)) if e.kind() == io::ErrorKind::NotFound => {
+                Bindings::default()
+            },
+            Err(e) => {
+                warn!("Using the default key bindings. {}: {}", filepath.display(), e);
+                Bindings::default()
+            },
+        }
     }
 }
 
 impl Default for Bindings {
     fn default() -> Self {
         let mut bindings = Bindings::new(0.1f32, 0.5f32, 0.5f32);
-        bindings.bind(VirtualKeyCode::W, Action::CameraMoveNY);
-        bindings.bind(VirtualKeyCode::S, Action::CameraMovePY);
-        bindings.bind(VirtualKeyCode::A, Action::CameraMovePX);
-        bindings.bind(VirtualKeyCode::D, Action::CameraMoveNX);
-        bindings.bind(VirtualKeyCode::Left, Action::RotateShapePY);
-        bindings.bind(VirtualKeyCode::Right, Action::RotateShapeNY);
-        bindings.bind(VirtualKeyCode::Up, Action::RotateShapePX);
-        bindings.bind(VirtualKeyCode::Down, Action::RotateShapeNX);
+        bindings.bind(KeyCode::KeyW, Action::CameraMoveNY);
+        bindings.bind(KeyCode::KeyS, Action::CameraMovePY);
+        bindings.bind(KeyCode::KeyA, Action::CameraMovePX);
+        bindings.bind(KeyCode::KeyD, Action::CameraMoveNX);
+        bindings.bind(KeyCode::ArrowLeft, Action::RotateShapePY);
+        bindings.bind(KeyCode::ArrowRight, Action::RotateShapeNY);
+        bindings.bind(KeyCode::ArrowUp, Action::RotateShapePX);
+        bindings.bind(KeyCode::ArrowDown, Action::RotateShapeNX);
+        bindings.bind(KeyCode::F12, Action::Screenshot);
+        bindings.bind(KeyCode::Space, Action::ToggleSpin);
+        bindings.bind(KeyCode::Home, Action::ResetView);
+        bindings.bind(KeyCode::Digit1, Action::ViewFront);
+        bindings.bind(KeyCode::Digit2, Action::ViewTop);
+        bindings.bind(KeyCode::Digit3, Action::ViewIsometric);
 
         bindings
     }
 }
 
+/// TOML keys can only be strings so the key codes go by name.
+mod key_names {
+    use std::collections::HashMap;
+
+    use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+    use winit::keyboard::KeyCode;
+
+    use super::Action;
+
+    pub fn serialize<S: Serializer>(
+        bindings: &HashMap<KeyCode, Action>, serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut named: HashMap<String, Action> = HashMap::new();
+        for (key, action) in bindings {
+            let name = toml::Value::try_from(key).map_err(ser::Error::custom)?;
+            let name = name.as_str().ok_or_else(|| ser::Error::custom("Unnamed key."))?;
+            named.insert(name.to_owned(), *action);
+        }
+        named.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<KeyCode, Action>, D::Error> {
+        HashMap::<String, Action>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(name, action)| {
+                KeyCode::deserialize(toml::Value::String(name))
+                    .map(|key| (key, action))
+                    .map_err(de::Error::custom)
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+pub enum BindingsError {
+    Io(io::Error),
+    Parse(toml::de::Error),
+    Write(toml::ser::Error),
+}
+
+impl fmt::Display for BindingsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BindingsError::Io(e) => write!(f, "Can't access the bindings file: {}", e),
+            BindingsError::Parse(e) => write!(f, "Bad bindings: {}", e),
+            BindingsError::Write(e) => write!(f, "Can't write out the bindings: {}", e),
+        }
+    }
+}
+
+impl error::Error for BindingsError {
+    fn description(&self) -> &str {
+        "Error loading or saving key bindings."
+    }
+}
+
 pub fn handle_keyboard<T: ActionState>(
-    event: &KeyboardInput, bindings: &Bindings, state: &mut T,
+    event: &KeyEvent, bindings: &Bindings, state: &mut T,
 ) -> Option<(Camera, RotX, RotY)> {
-    let ci = bindings.camera_increment;
-    let xri = bindings.x_rotation_increment;
-    let yri = bindings.y_rotation_increment;
-    let vkc = event.virtual_keycode
-        .unwrap_or(VirtualKeyCode::Escape); // Escape is already caught beforehand.
+    let key = match event.physical_key {
+        PhysicalKey::Code(key) => key,
+        PhysicalKey::Unidentified(_) => return None,
+    };
 
     bindings.bindings
-        .get(&vkc)
-        .map(|action| {
-            match event.state {
-                ElementState::Pressed => state.on(*action),
-                ElementState::Released => state.off(*action),
-            }
-            (
-                state.camera_increment(ci),
-                state.x_rotation_increment(xri),
-                state.y_rotation_increment(yri),
-            )
-        })
+        .get(&key)
+        .map(|action| handle_action(*action, event.state, bindings, state))
+}
+
+/// Same as `handle_keyboard` once the key has been looked up. For actions that didn't come
+/// straight from the keyboard, such as those played back from a `Recording`.
+pub fn handle_action<T: ActionState>(
+    action: Action, element_state: ElementState, bindings: &Bindings, state: &mut T,
+) -> (Camera, RotX, RotY) {
+    match element_state {
+        ElementState::Pressed => state.on(action),
+        ElementState::Released => state.off(action),
+    }
+    (
+        state.camera_increment(bindings.camera_increment),
+        state.x_rotation_increment(bindings.x_rotation_increment),
+        state.y_rotation_increment(bindings.y_rotation_increment),
+    )
+}
+
+
+/// Pixels the cursor can wander between pressing and releasing the pick button and still
+/// count as a click rather than a drag.
+const CLICK_SLOP: f64 = 4.0;
+
+/// Keeps track of where the cursor is so a click knows what it's clicking on and a drag
+/// knows how far it went.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Mouse {
+    cursor: Option<PhysicalPosition<f64>>,
+    previous: Option<PhysicalPosition<f64>>,
+    dragging: bool,
+    rotating: bool,
+    pressed: Option<PhysicalPosition<f64>>,
+}
+
+impl Mouse {
+    pub fn moved(&mut self, position: PhysicalPosition<f64>) {
+        self.previous = self.cursor;
+        self.cursor = Some(position);
+    }
+
+    /// The cursor left the window. Ends any drag as the button release won't be seen.
+    pub fn left(&mut self) {
+        *self = Mouse::default();
+    }
+
+    pub fn cursor(&self) -> Option<PhysicalPosition<f64>> {
+        self.cursor
+    }
+
+    /// How far the cursor went in its last move.
+    fn delta(&self) -> Option<(f32, f32)> {
+        match (self.previous, self.cursor) {
+            (Some(previous), Some(cursor)) => {
+                Some(((cursor.x - previous.x) as f32, (cursor.y - previous.y) as f32))
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Returns how far to orbit the camera when the cursor is dragged with the orbit button.
+pub fn handle_cursor_moved(
+    position: PhysicalPosition<f64>, bindings: &Bindings, mouse: &mut Mouse,
+) -> Option<(Yaw, Pitch)> {
+    mouse.moved(position);
+
+    match mouse.delta() {
+        Some((dx, dy)) if mouse.dragging => {
+            let sensitivity = bindings.orbit_sensitivity;
+            Some((Rad(dx * sensitivity), Rad(dy * sensitivity)))
+        },
+        _ => None,
+    }
+}
+
+/// Returns how far to rotate the shape for the last cursor move when it's dragged with the
+/// rotate button. Dragging sideways turns it like the left and right keys and dragging up
+/// and down like the up and down keys. Goes after `handle_cursor_moved`.
+pub fn handle_mouse(bindings: &Bindings, mouse: &Mouse) -> Option<(RotX, RotY)> {
+    match mouse.delta() {
+        Some((dx, dy)) if mouse.rotating => {
+            let sensitivity = bindings.rotate_sensitivity;
+            Some((Rad(-dy * sensitivity), Rad(-dx * sensitivity)))
+        },
+        _ => None,
+    }
 }
 
+/// Starts and ends drags. Returns where to pick when the pick button is clicked with the
+/// cursor in the window. Letting go after dragging doesn't pick.
+pub fn handle_mouse_button(
+    button: MouseButton, state: ElementState, bindings: &Bindings, mouse: &mut Mouse,
+) -> Option<PhysicalPosition<f64>> {
+    let pressed = state == ElementState::Pressed;
+    if button == bindings.orbit {
+        mouse.dragging = pressed;
+    }
+    if button == bindings.rotate {
+        mouse.rotating = pressed;
+    }
+
+    if button != bindings.pick {
+        return None;
+    }
+    if pressed {
+        mouse.pressed = mouse.cursor;
+        return None;
+    }
+    match (mouse.pressed.take(), mouse.cursor) {
+        (Some(down), Some(up)) => {
+            let (dx, dy) = (up.x - down.x, up.y - down.y);
+            if dx * dx + dy * dy <= CLICK_SLOP * CLICK_SLOP { Some(up) } else { None }
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bindings_round_trip() {
+        let mut bindings = Bindings::default();
+        bindings.bind(KeyCode::KeyZ, Action::CameraMoveNY);
+        bindings.bind_orbit(MouseButton::Middle);
+
+        let toml = bindings.to_toml().unwrap();
+        assert!(Bindings::from_toml(&toml).unwrap() == bindings);
+    }
+
+    #[test]
+    fn missing_bindings_are_defaults() {
+        let toml = "
+            camera_increment = 0.5
+
+            [keys]
+            KeyZ = \"CameraMoveNY\"
+        ";
+        let bindings = Bindings::from_toml(toml).unwrap();
+
+        assert!(bindings.camera_increment == 0.5);
+        assert!(bindings.pick == Bindings::default().pick);
+        assert!(bindings.bindings.len() == 1);
+        assert!(bindings.bindings[&KeyCode::KeyZ] == Action::CameraMoveNY);
+    }
+
+    #[test]
+    fn bad_bindings() {
+        assert!(Bindings::from_toml("[keys]\nKeyZ = \"Jump\"").is_err());
+        assert!(Bindings::from_toml("[keys]\nJump = \"Screenshot\"").is_err());
+        assert!(Bindings::load_or_default("no/such/bindings.toml") == Bindings::default());
+    }
+
+    #[test]
+    fn drag_rotates_and_click_picks() {
+        let bindings = Bindings::default();
+        let mut mouse = Mouse::default();
+        let left = MouseButton::Left;
+
+        handle_cursor_moved(PhysicalPosition::new(10.0, 10.0), &bindings, &mut mouse);
+        let press = handle_mouse_button(left, ElementState::Pressed, &bindings, &mut mouse);
+        assert!(press.is_none());
+
+        handle_cursor_moved(PhysicalPosition::new(30.0, 5.0), &bindings, &mut mouse);
+        let (rot_x, rot_y) = handle_mouse(&bindings, &mouse).unwrap();
+        assert!((rot_x.0 - 0.05).abs() < 1e-6 && (rot_y.0 + 0.2).abs() < 1e-6);
+
+        // Dragged too far to be a click.
+        let drag = handle_mouse_button(left, ElementState::Released, &bindings, &mut mouse);
+        assert!(drag.is_none());
+
+        handle_mouse_button(left, ElementState::Pressed, &bindings, &mut mouse);
+        handle_cursor_moved(PhysicalPosition::new(31.0, 6.0), &bindings, &mut mouse);
+        let click = handle_mouse_button(left, ElementState::Released, &bindings, &mut mouse);
+        assert!(click == Some(PhysicalPosition::new(31.0, 6.0)));
+        assert!(handle_mouse(&bindings, &mouse).is_none());
+    }
+}
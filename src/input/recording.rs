@@ -0,0 +1,147 @@
+//! Record the actions taken while the window is open and play them back later.
+//!
+//! Only keyboard actions are kept. Each one moves the camera or shape by a fixed increment
+//! so playing the same actions back in the same order always ends up in the same place no
+//! matter how fast the frames come. The mouse and the idle spin go by how far the cursor
+//! moved and how long frames took, so they're left out.
+use std::{error, fmt, fs, io, path};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use winit::event::ElementState;
+
+use super::Action;
+
+/// An action pressed or released some milliseconds after the recording started.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+    at: u64,
+    action: Action,
+    state: ElementState,
+}
+
+impl Event {
+    pub fn at(&self) -> Duration {
+        Duration::from_millis(self.at)
+    }
+
+    pub fn action(&self) -> Action {
+        self.action
+    }
+
+    pub fn state(&self) -> ElementState {
+        self.state
+    }
+}
+
+/// Saved as a TOML file with an `[[event]]` table per action.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Recording {
+    #[serde(rename = "event")]
+    events: Vec<Event>,
+}
+
+impl Recording {
+    pub fn new() -> Self {
+        Recording::default()
+    }
+
+    /// Keep `action` taken `at` since the recording started. Events are expected in order.
+    pub fn record(&mut self, at: Duration, action: Action, state: ElementState) {
+        let at = at.as_secs() * 1000 + u64::from(at.subsec_millis());
+        self.events.push(Event { at, action, state });
+    }
+
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    pub fn from_toml(toml: &str) -> Result<Self, RecordingError> {
+        toml::from_str(toml).map_err(RecordingError::Parse)
+    }
+
+    pub fn to_toml(&self) -> Result<String, RecordingError> {
+        toml::to_string(self).map_err(RecordingError::Write)
+    }
+
+    pub fn load<P: AsRef<path::Path>>(filepath: P) -> Result<Self, RecordingError> {
+        let toml = fs::read_to_string(filepath).map_err(RecordingError::Io)?;
+        Recording::from_toml(&toml)
+    }
+
+    pub fn save<P: AsRef<path::Path>>(&self, filepath: P) -> Result<(), RecordingError> {
+        fs::write(filepath, self.to_toml()?).map_err(RecordingError::Io)
+    }
+
+    pub fn play(self) -> Playback {
+        Playback { events: self.events, next: 0 }
+    }
+}
+
+/// Hands back the recorded events as their time comes round.
+#[derive(Debug, Clone)]
+pub struct Playback {
+    events: Vec<Event>,
+    next: usize,
+}
+
+impl Playback {
+    /// All the events not yet handed back that were recorded up to `elapsed`.
+    pub fn due(&mut self, elapsed: Duration) -> &[Event] {
+        let start = self.next;
+        while self.next < self.events.len() && self.events[self.next].at() <= elapsed {
+            self.next += 1;
+        }
+        &self.events[start..self.next]
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next == self.events.len()
+    }
+}
+
+#[derive(Debug)]
+pub enum RecordingError {
+    Io(io::Error),
+    Parse(toml::de::Error),
+    Write(toml::ser::Error),
+}
+
+impl fmt::Display for RecordingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RecordingError::Io(e) => write!(f, "Can't access the recording: {}", e),
+            RecordingError::Parse(e) => write!(f, "Bad recording: {}", e),
+            RecordingError::Write(e) => write!(f, "Can't write out the recording: {}", e),
+        }
+    }
+}
+
+impl error::Error for RecordingError {
+    fn description(&self) -> &str {
+        "Error loading or saving a recording."
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn plays_back_in_time() {
+        let ms = Duration::from_millis;
+        let mut recording = Recording::new();
+        recording.record(ms(100), Action::CameraMovePX, ElementState::Pressed);
+        recording.record(ms(250), Action::CameraMovePX, ElementState::Released);
+        recording.record(ms(250), Action::Screenshot, ElementState::Pressed);
+
+        let toml = recording.to_toml().unwrap();
+        let recording = Recording::from_toml(&toml).unwrap();
+        let mut playback = recording.clone().play();
+
+        assert!(playback.due(ms(50)).is_empty());
+        assert!(playback.due(ms(100)) == &recording.events()[..1]);
+        assert!(playback.due(ms(300)) == &recording.events()[1..]);
+        assert!(playback.is_finished());
+    }
+}
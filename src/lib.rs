@@ -1,15 +1,33 @@
 //! # Polyorb
 //!
 //! Render various Goldberg polyhedrons.
+//!
+//! The rendering modules are behind the default `render` feature. Without it only the
+//! geometry is built, `polyhedron`, `geop`, `planar` and the named solids. The shaders are
+//! built into the crate. Recompiling them as they're edited needs the `shaderc` feature.
+
+mod error;
+
+pub use crate::error::{Error, Result};
 
 pub mod geop;
 pub mod shape;
+#[cfg(feature = "render")]
 pub mod input;
+#[cfg(feature = "render")]
 pub mod scene;
+#[cfg(feature = "render")]
 pub mod light;
+#[cfg(feature = "render")]
+pub mod material;
+#[cfg(feature = "render")]
 pub mod shader;
 pub mod planar;
+#[cfg(feature = "render")]
 pub mod presenter;
+#[cfg(feature = "render")]
 pub mod presentation;
 pub mod platonic_solid;
+pub mod archimedean;
+pub mod catalan;
 pub mod polyhedron;
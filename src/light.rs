@@ -1,12 +1,39 @@
 //! Light struct
 use std::{ops, mem};
 
+use bytemuck::{Pod, Zeroable};
 use derive_getters::Getters;
-use cgmath::{Deg, EuclideanSpace, Matrix4, PerspectiveFov, Point3, Vector3};
+use cgmath::{Deg, EuclideanSpace, InnerSpace, Matrix4, PerspectiveFov, Point3, Rad, Vector3};
+use cgmath::SquareMatrix;
+
+/// Tags telling the fragment shader what kind of light it has. Must match `flat.frag`.
+const POINT: u32 = 0;
+const DIRECTIONAL: u32 = 1;
+const SPOT: u32 = 2;
+
+/// How the light spreads out.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Kind {
+    /// Shines out in every direction from the light position.
+    Point,
+
+    /// Shines the same way everywhere as if from infinitely far off like sunlight. The
+    /// vector is the direction the light travels in.
+    Directional(Vector3<f32>),
+
+    /// Shines from the light position in a cone around `direction`. Full strength within
+    /// `inner` of the direction fading out to nothing at `outer`.
+    Spot {
+        direction: Vector3<f32>,
+        inner: Rad<f32>,
+        outer: Rad<f32>,
+    },
+}
 
 /// Lighting for use within a `Scene`. Must be passed in as part of scene construction.
 #[derive(Debug, Clone, Getters)]
 pub struct Light {
+    kind: Kind,
     pos: Point3<f32>,
     colour: wgpu::Color,
     fov: f32,
@@ -14,19 +41,56 @@ pub struct Light {
 }
 
 impl Light {
+    /// A point light.
     pub fn new(
         pos: Point3<f32>, colour: wgpu::Color, fov: f32, depth: ops::Range<f32>
     ) -> Self {
-        Light { pos, colour, fov, depth }
+        Light { kind: Kind::Point, pos, colour, fov, depth }
+    }
+
+    /// A light shining in `direction` from infinitely far away. It has no position, field of
+    /// view or depth.
+    pub fn directional(direction: Vector3<f32>, colour: wgpu::Color) -> Self {
+        Light {
+            kind: Kind::Directional(direction.normalize()),
+            pos: Point3::origin(),
+            colour,
+            fov: 0.0,
+            depth: 0.0..0.0,
+        }
+    }
+
+    /// A light at `pos` shining in a cone around `direction`. The cone is `inner` wide from
+    /// its middle at full strength fading out until `outer`. Its field of view is the whole
+    /// width of the cone and it has no depth.
+    pub fn spot<T: Into<Rad<f32>>>(
+        pos: Point3<f32>, direction: Vector3<f32>, inner: T, outer: T, colour: wgpu::Color,
+    ) -> Self {
+        let (inner, outer) = (inner.into(), outer.into());
+        let inner = if inner.0 < outer.0 { inner } else { outer };
+
+        Light {
+            kind: Kind::Spot { direction: direction.normalize(), inner, outer },
+            pos,
+            colour,
+            fov: Deg::from(outer).0 * 2.0,
+            depth: 0.0..0.0,
+        }
     }
 }
 
 /// Used only for final transfer to the video device.
-#[derive(Clone, Copy)]
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
 pub struct LightRaw {
     pub proj: [[f32; 4]; 4],
     pub pos: [f32; 4],
     pub colour: [f32; 4],
+    pub direction: [f32; 4],
+    /// Cosines of the inner and outer spot light cone angles. The rest is padding.
+    pub cone: [f32; 4],
+    /// Only the first is used. The rest pad it out to the 16 bytes the shader expects.
+    pub kind: [u32; 4],
 }
 
 impl LightRaw {
@@ -37,21 +101,47 @@ impl LightRaw {
 
 impl Light {
     pub fn to_raw(&self) -> LightRaw {
-        let mx_view = Matrix4::look_at(self.pos, Point3::origin(), -Vector3::unit_z());
-        
-        let projection = PerspectiveFov {
-            fovy: Deg(self.fov).into(),
-            aspect: 1.0,
-            near: self.depth.start,
-            far: self.depth.end,
-        };
-        
-        let mx_view_proj = Matrix4::from(projection.to_perspective()) * mx_view;
-        
-        LightRaw {
-            proj: *mx_view_proj.as_ref(),
-            pos: [self.pos.x, self.pos.y, self.pos.z, 1.0],
-            colour: [self.colour.r, self.colour.g, self.colour.b, 1.0],
+        let (r, g, b) = (self.colour.r as f32, self.colour.g as f32, self.colour.b as f32);
+        let colour = [r, g, b, 1.0];
+
+        match self.kind {
+            Kind::Point => {
+                let mx_view = Matrix4::look_at(self.pos, Point3::origin(), -Vector3::unit_z());
+
+                let projection = PerspectiveFov {
+                    fovy: Deg(self.fov).into(),
+                    aspect: 1.0,
+                    near: self.depth.start,
+                    far: self.depth.end,
+                };
+
+                let mx_view_proj = Matrix4::from(projection.to_perspective()) * mx_view;
+
+                LightRaw {
+                    proj: *mx_view_proj.as_ref(),
+                    pos: [self.pos.x, self.pos.y, self.pos.z, 1.0],
+                    colour,
+                    direction: [0.0; 4],
+                    cone: [0.0; 4],
+                    kind: [POINT, 0, 0, 0],
+                }
+            },
+            Kind::Directional(direction) => LightRaw {
+                proj: *Matrix4::identity().as_ref(),
+                pos: [0.0, 0.0, 0.0, 1.0],
+                colour,
+                direction: [direction.x, direction.y, direction.z, 0.0],
+                cone: [0.0; 4],
+                kind: [DIRECTIONAL, 0, 0, 0],
+            },
+            Kind::Spot { direction, inner, outer } => LightRaw {
+                proj: *Matrix4::identity().as_ref(),
+                pos: [self.pos.x, self.pos.y, self.pos.z, 1.0],
+                colour,
+                direction: [direction.x, direction.y, direction.z, 0.0],
+                cone: [inner.0.cos(), outer.0.cos(), 0.0, 0.0],
+                kind: [SPOT, 0, 0, 0],
+            },
         }
     }
 }
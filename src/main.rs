@@ -0,0 +1,424 @@
+//! Build a polyhedron from Conway notation then write it out, show it or both.
+//!
+//! ```text
+//! polyorb --seed I --ops dk --radius 2 --colour 00ff00 --export out.obj --no-window
+//! ```
+//!
+//! The operations are read right to left like the notation, so `--ops dk` runs kis first.
+//! The export format goes by the file extension.
+//!
+//! `batch` writes a file per notation instead, given as arguments or a line each in a file.
+//!
+//! ```text
+//! polyorb batch --format png --out renders tI dtI cD --from more.txt
+//! ```
+//!
+//! `repl` reads commands such as `kis 5` or `undo` from the terminal to change the shape
+//! while it's shown. Type `help` for the lot.
+use std::{error, fs, io, process, thread};
+use std::io::{BufRead, Write};
+use std::path::Path;
+use std::sync::mpsc;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use polyorb::polyhedron::{self, export, Polyhedron, VertexAndFaceOps, VtFc};
+use polyorb::polyhedron::repl::{self, Command, Session};
+use polyorb::light::Light;
+use polyorb::scene::{Cached, Prepare, Scene};
+use polyorb::{presentation, presenter, shader};
+
+/// Millimetres per unit of the polyhedron in SVG nets.
+const SVG_SCALE: f64 = 20.0;
+
+/// What `batch` can write.
+const FORMATS: &[&str] = &["obj", "stl", "ply", "gltf", "glb", "svg", "json", "dot", "png"];
+
+fn main() -> Result<(), Box<dyn error::Error>> {
+    let matches = App::new("polyorb")
+        .about("Build polyhedra from Conway notation.")
+        .args(&looks())
+        .arg(Arg::with_name("seed")
+             .long("seed")
+             .takes_value(true)
+             .default_value("D")
+             .help("Seed solid. One of T, C, O, D or I."))
+        .arg(Arg::with_name("ops")
+             .long("ops")
+             .takes_value(true)
+             .help("Conway operations to run on the seed such as dk or t5."))
+        .arg(Arg::with_name("export")
+             .long("export")
+             .takes_value(true)
+             .help("Write to a .obj, .stl, .ply, .gltf, .glb, .svg, .json or .dot file."))
+        .arg(Arg::with_name("no-window")
+             .long("no-window")
+             .help("Don't open the viewer."))
+        .subcommand(SubCommand::with_name("batch")
+            .about("Write a file per notation.")
+            .args(&looks())
+            .arg(Arg::with_name("notations")
+                 .multiple(true)
+                 .help("Full notations such as dkI."))
+            .arg(Arg::with_name("from")
+                 .long("from")
+                 .takes_value(true)
+                 .help("File with a notation per line. Skips blank lines and # comments."))
+            .arg(Arg::with_name("format")
+                 .long("format")
+                 .takes_value(true)
+                 .possible_values(FORMATS)
+                 .default_value("obj"))
+            .arg(Arg::with_name("out")
+                 .long("out")
+                 .takes_value(true)
+                 .default_value(".")
+                 .help("Directory to write into. Files are named after the notation."))
+            .arg(Arg::with_name("size")
+                 .long("size")
+                 .takes_value(true)
+                 .default_value("512")
+                 .help("Width and height of PNG renders.")))
+        .subcommand(SubCommand::with_name("repl")
+            .about("Change the shape by typing commands. Type help to list them.")
+            .args(&looks())
+            .arg(Arg::with_name("seed")
+                 .long("seed")
+                 .takes_value(true)
+                 .default_value("D")
+                 .help("Notation to start from, usually just a seed."))
+            .arg(Arg::with_name("no-window")
+                 .long("no-window")
+                 .help("Only read commands. Export to see the results.")))
+        .get_matches();
+
+    if let Some(matches) = matches.subcommand_matches("batch") {
+        return batch(matches);
+    }
+    if let Some(matches) = matches.subcommand_matches("repl") {
+        return interact(matches);
+    }
+
+    let notation = format!(
+        "{}{}", matches.value_of("ops").unwrap_or(""), matches.value_of("seed").unwrap(),
+    );
+    let specification = polyhedron::Specification::parse(&notation)?;
+    let (radius, colour) = parse_looks(&matches)?;
+
+    let polyhedron = specification.produce().scale_to_radius(radius);
+
+    if let Some(filepath) = matches.value_of("export") {
+        write(&polyhedron, colour, Path::new(filepath))?;
+        println!("Wrote {} to {}.", notation, filepath);
+    }
+
+    if !matches.is_present("no-window") {
+        show(&notation, polyhedron, colour)?;
+    }
+
+    Ok(())
+}
+
+/// How big and what colour the polyhedra are.
+fn looks<'a, 'b>() -> [Arg<'a, 'b>; 2] {
+    [
+        Arg::with_name("radius")
+            .long("radius")
+            .takes_value(true)
+            .default_value("1")
+            .help("Distance from the center to the furthest vertex."),
+        Arg::with_name("colour")
+            .long("colour")
+            .takes_value(true)
+            .default_value("0099ff")
+            .help("Face colour as six hex digits."),
+    ]
+}
+
+fn parse_looks(matches: &ArgMatches) -> Result<(f64, [f32; 3]), Box<dyn error::Error>> {
+    let radius: f64 = matches.value_of("radius")
+        .unwrap()
+        .parse()
+        .map_err(|e| format!("Bad radius: {}", e))?;
+    let colour = parse_colour(matches.value_of("colour").unwrap())
+        .ok_or("Colour must be six hex digits such as 00ff00.")?;
+
+    Ok((radius, colour))
+}
+
+/// `rrggbb` into channels from zero to one.
+fn parse_colour(hex: &str) -> Option<[f32; 3]> {
+    let hex = hex.trim_start_matches('#');
+    if hex.len() != 6 {
+        return None;
+    }
+
+    let channel = |i: usize| {
+        hex.get(i..i + 2)
+            .and_then(|c| u8::from_str_radix(c, 16).ok())
+            .map(|c| f32::from(c) / 255.0)
+    };
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+fn write(
+    polyhedron: &Polyhedron<VtFc>, colour: [f32; 3], filepath: &Path,
+) -> Result<(), Box<dyn error::Error>> {
+    let extension = filepath.extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extension.to_lowercase())
+        .unwrap_or_default();
+    let cached = || presenter::SingleColour::new(colour, polyhedron.clone()).to_cached();
+
+    let mut writer = io::BufWriter::new(fs::File::create(filepath)?);
+    match extension.as_str() {
+        "obj" => export::obj::write(&polyhedron.clone().normalize(), &mut writer)?,
+        "stl" => export::stl::write(
+            &polyhedron.clone().normalize(), export::stl::Format::Binary, &mut writer,
+        )?,
+        "ply" => export::ply::write(polyhedron, |_, _| colour, &mut writer)?,
+        "gltf" => export::gltf::write(&cached(), export::gltf::Format::Gltf, &mut writer)?,
+        "glb" => export::gltf::write(&cached(), export::gltf::Format::Glb, &mut writer)?,
+        "svg" => export::svg::write(polyhedron, SVG_SCALE, &mut writer)?,
+        "json" => export::json::write(polyhedron, &mut writer)?,
+        "dot" => {
+            let (graph, format) = (export::graph::Graph::Vertices, export::graph::Format::Dot);
+            export::graph::write(polyhedron, graph, format, &mut writer)?
+        },
+        _ => return Err(format!("Can't export to {}.", filepath.display()).into()),
+    }
+
+    Ok(())
+}
+
+fn batch(matches: &ArgMatches) -> Result<(), Box<dyn error::Error>> {
+    let (radius, colour) = parse_looks(matches)?;
+    let format = matches.value_of("format").unwrap();
+    let directory = Path::new(matches.value_of("out").unwrap());
+
+    let mut notations: Vec<String> = matches.values_of("notations")
+        .map(|notations| notations.map(|notation| notation.to_owned()).collect())
+        .unwrap_or_default();
+    if let Some(filepath) = matches.value_of("from") {
+        let listed = fs::read_to_string(filepath)?;
+        notations.extend(listed
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| line.to_owned()));
+    }
+    if notations.is_empty() {
+        return Err("Nothing to write. Give some notations or a file of them.".into());
+    }
+
+    // Catch any mistakes before spending time on the others.
+    let specifications = notations
+        .iter()
+        .map(|notation| {
+            polyhedron::Specification::parse(notation)
+                .map_err(|e| format!("Bad notation {}: {}", notation, e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    fs::create_dir_all(directory)?;
+    let filepath = |notation: &str| directory.join(format!("{}.{}", notation, format));
+
+    if format == "png" {
+        let size: u32 = matches.value_of("size")
+            .unwrap()
+            .parse()
+            .map_err(|e| format!("Bad size: {}", e))?;
+        let cached: Vec<Cached> = specifications
+            .iter()
+            .map(|specification| {
+                let polyhedron = specification.produce().scale_to_radius(radius);
+                presenter::SingleColour::new(colour, polyhedron).to_cached()
+            })
+            .collect();
+
+        let first = cached[0].clone();
+        let mut offscreen = presentation::Offscreen::new(scene(first)?, size, size)?;
+        for (notation, geometry) in notations.iter().zip(cached.iter()) {
+            offscreen.update(|scene, device, queue| {
+                scene.replace_geometry(geometry, device, queue)
+            })?;
+            offscreen.capture(filepath(notation));
+            println!("Rendered {}.", notation);
+        }
+        offscreen.finish()?;
+    } else {
+        for (notation, specification) in notations.iter().zip(specifications.iter()) {
+            let polyhedron = specification.produce().scale_to_radius(radius);
+            write(&polyhedron, colour, &filepath(notation))?;
+            println!("Wrote {}.", notation);
+        }
+    }
+
+    Ok(())
+}
+
+/// What a command did to the session.
+enum Outcome {
+    Changed,
+    Same,
+    Quit,
+}
+
+fn interact(matches: &ArgMatches) -> Result<(), Box<dyn error::Error>> {
+    let (radius, colour) = parse_looks(matches)?;
+    let mut session = Session::new(matches.value_of("seed").unwrap())?;
+    println!("Starting from {}. Type help to list the commands.", session.notation());
+
+    if matches.is_present("no-window") {
+        prompt();
+        for line in io::stdin().lock().lines() {
+            if let Outcome::Quit = command(&mut session, &line?, radius, colour) {
+                break;
+            }
+            prompt();
+        }
+        return Ok(());
+    }
+
+    // Reading the terminal blocks so it's done off to the side of the window.
+    let (sender, receiver) = mpsc::channel::<String>();
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let sent = line.map(|line| sender.send(line).is_ok()).unwrap_or(false);
+            if !sent {
+                break;
+            }
+        }
+    });
+
+    let present = move |polyhedron: &Polyhedron<VtFc>| {
+        let polyhedron = polyhedron.clone().scale_to_radius(radius);
+        presenter::SingleColour::new(colour, polyhedron).to_cached()
+    };
+    let scene = scene(present(session.polyhedron()))?;
+    prompt();
+    presentation::run_animated("polyorb", scene, move |scene, _, device, queue| {
+        for line in receiver.try_iter() {
+            match command(&mut session, &line, radius, colour) {
+                Outcome::Changed => {
+                    let geometry = present(session.polyhedron());
+                    if let Err(e) = scene.replace_geometry(&geometry, device, queue) {
+                        println!("Can't show {}: {}", session.notation(), e);
+                    }
+                },
+                Outcome::Same => (),
+                Outcome::Quit => process::exit(0),
+            }
+            prompt();
+        }
+    })?;
+
+    Ok(())
+}
+
+fn prompt() {
+    print!("> ");
+    io::stdout().flush().ok();
+}
+
+/// Carry out the command on `line` telling the user how it went.
+fn command(session: &mut Session, line: &str, radius: f64, colour: [f32; 3]) -> Outcome {
+    let command = match Command::parse(line) {
+        Ok(Some(command)) => command,
+        Ok(None) => return Outcome::Same,
+        Err(e) => {
+            println!("{}", e);
+            return Outcome::Same;
+        },
+    };
+
+    match command {
+        Command::Operation(operations) => match session.apply(&operations) {
+            Ok(_) => {
+                println!("{}", session.notation());
+                Outcome::Changed
+            },
+            Err(e) => {
+                println!("{}", e);
+                Outcome::Same
+            },
+        },
+        Command::Undo => {
+            if session.undo() {
+                println!("{}", session.notation());
+                Outcome::Changed
+            } else {
+                println!("Nothing to undo.");
+                Outcome::Same
+            }
+        },
+        Command::Export(filepath) => {
+            let polyhedron = session.polyhedron().clone().scale_to_radius(radius);
+            match write(&polyhedron, colour, &filepath) {
+                Ok(()) => println!("Wrote {} to {}.", session.notation(), filepath.display()),
+                Err(e) => println!("{}", e),
+            }
+            Outcome::Same
+        },
+        Command::Show => {
+            let polyhedron = session.polyhedron();
+            let (vertices, faces) = polyhedron.vertices_and_faces();
+            println!(
+                "{}: {} vertices, {} edges, {} faces",
+                session.notation(), vertices.len(), polyhedron.edge_count(), faces.len(),
+            );
+            Outcome::Same
+        },
+        Command::Help => {
+            println!("{}", repl::HELP);
+            Outcome::Same
+        },
+        Command::Quit => Outcome::Quit,
+    }
+}
+
+fn show(
+    title: &str, polyhedron: Polyhedron<VtFc>, colour: [f32; 3],
+) -> Result<(), Box<dyn error::Error>> {
+    let scene = scene(presenter::SingleColour::new(colour, polyhedron).to_cached())?;
+    presentation::run_spinning(title, scene, presentation::Spin::default())?;
+
+    Ok(())
+}
+
+/// Lit from above on either side.
+fn scene(geometry: Cached) -> Result<Scene<Prepare<Cached>>, Box<dyn error::Error>> {
+    let light1 = Light::new(
+        cgmath::Point3::new(7f32, -5f32, 10f32),
+        wgpu::Color { r: 1.0, g: 1.0, b: 1.0, a: 1.0 },
+        60.0,
+        1.0..20.0,
+    );
+    let light2 = Light::new(
+        cgmath::Point3::new(-5f32, -7f32, 10f32),
+        wgpu::Color { r: 0.5, g: 0.5, b: 0.5, a: 1.0 },
+        45.0,
+        1.0..20.0,
+    );
+
+    let flat_shaders = shader::load_flat_shaders()?;
+    Ok(Scene::new()
+        .shaders(&flat_shaders)
+        .add_light(light1)
+        .add_light(light2)
+        .multisample(4)
+        .geometry(geometry))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn colours() {
+        assert!(parse_colour("00ff00") == Some([0.0, 1.0, 0.0]));
+        assert!(parse_colour("#ff0000") == Some([1.0, 0.0, 0.0]));
+        assert!(parse_colour("0f0").is_none());
+        assert!(parse_colour("gg0000").is_none());
+    }
+}
@@ -0,0 +1,81 @@
+//! Material struct
+use std::mem;
+
+use bytemuck::{Pod, Zeroable};
+use derive_getters::Getters;
+
+/// How the surface of the geometry in a `Scene` reacts to light. Lit with the Blinn-Phong
+/// model by the flat shaders and the metallic-roughness model by the PBR shaders.
+#[derive(Debug, Copy, Clone, PartialEq, Getters)]
+pub struct Material {
+    /// Multiplies the vertex colours.
+    diffuse: [f32; 3],
+
+    /// Colour of the highlights.
+    specular: [f32; 3],
+
+    /// The higher the smaller and sharper the highlights.
+    shininess: f32,
+
+    /// Light given off by the surface itself whether lit or not.
+    emissive: [f32; 3],
+
+    /// From 0 for a mirror finish to 1 for completely matte. Only for the PBR shaders.
+    roughness: f32,
+
+    /// From 0 for a dielectric like plastic to 1 for bare metal. Only for the PBR shaders.
+    metallic: f32,
+}
+
+impl Material {
+    pub fn new(
+        diffuse: [f32; 3], specular: [f32; 3], shininess: f32, emissive: [f32; 3],
+    ) -> Self {
+        Material { diffuse, specular, shininess, emissive, roughness: 0.5, metallic: 0.0 }
+    }
+
+    /// Set the surface for the PBR shaders. Defaults to half rough and not metallic.
+    pub fn pbr(mut self, roughness: f32, metallic: f32) -> Self {
+        self.roughness = roughness;
+        self.metallic = metallic;
+        self
+    }
+
+    pub fn to_raw(&self) -> MaterialRaw {
+        let [dr, dg, db] = self.diffuse;
+        let [sr, sg, sb] = self.specular;
+        let [er, eg, eb] = self.emissive;
+
+        MaterialRaw {
+            diffuse: [dr, dg, db, 1.0],
+            specular: [sr, sg, sb, self.shininess],
+            emissive: [er, eg, eb, 1.0],
+            pbr: [self.roughness, self.metallic, 0.0, 0.0],
+        }
+    }
+}
+
+/// Leaves the vertex colours as they are with faint highlights.
+impl Default for Material {
+    fn default() -> Self {
+        Material::new([1.0; 3], [0.25; 3], 32.0, [0.0; 3])
+    }
+}
+
+/// Used only for final transfer to the video device.
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct MaterialRaw {
+    pub diffuse: [f32; 4],
+    /// The shininess goes in the last.
+    pub specular: [f32; 4],
+    pub emissive: [f32; 4],
+    /// Roughness then metallic. The rest is padding.
+    pub pbr: [f32; 4],
+}
+
+impl MaterialRaw {
+    pub const fn sizeof() -> usize {
+        mem::size_of::<MaterialRaw>()
+    }
+}
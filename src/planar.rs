@@ -10,53 +10,102 @@
 //! triangles expressed as all the vertices of the face in addition to an index delineating
 //! the order to traverse the vertices tracing out triangles that cover the entire face.
 
-use cgmath::{Point3, Vector3};
+use cgmath::{Point3, Vector3, BaseFloat};
+use cgmath::prelude::*;
 
+#[cfg(feature = "render")]
 use crate::scene;
 
 /// A planar polygon. It is a logic error for all the vertices to not be on the same plane
 /// when there are more than three vertices. Notwithstanding small roudning errors from the
 /// use of floating point numbers because that can't really be avoided unless we use
 /// fractional numbers or rework the definition to be a 2D polygon with a 3D normal and a
-/// 3D translation.
+/// 3D translation. `VertexAndFaceOps::non_planar_faces` finds the faces that would break
+/// this.
 #[derive(Debug, Clone)]
-pub struct Polygon<F64> {
-    vertices: Vec<Point3<F64>>,
-    normal: Vector3<F64>,
+pub struct Polygon<S> {
+    vertices: Vec<Point3<S>>,
+    normal: Vector3<S>,
 }
 
-impl Polygon<f64> {
+impl<S: BaseFloat> Polygon<S> {
     /// Don't expose it publicly outside the crate otherwise an incorrect planar `Polygon`
     /// could be constructed. This method will not check for a minimum of 3 vertices nor
     /// planarity if there are more than 3 vertices.
-    pub (in crate) fn new(vertices: &[Point3<f64>], normal: Vector3<f64>) -> Self {
+    pub (in crate) fn new(vertices: &[Point3<S>], normal: Vector3<S>) -> Self {
         Polygon {
             vertices: vertices.to_owned(),
             normal,
         }
     }
 
+    pub fn normal(&self) -> Vector3<S> {
+        self.normal
+    }
+
+    /// Number of sides which is also the number of vertices.
+    pub fn sides(&self) -> usize {
+        self.vertices.len()
+    }
+
+    /// Texture coordinates for each vertex from laying the polygon flat on its own plane. The
+    /// first edge runs along u. The polygon is scaled to fit from 0 to 1 both ways keeping
+    /// its proportions and centred along the shorter way.
+    pub fn texture_coordinates(&self) -> Vec<[f32; 2]> {
+        let origin = self.vertices[0];
+        let u_axis = (self.vertices[1] - origin).normalize();
+        let v_axis = self.normal.cross(u_axis).normalize();
+        let flat: Vec<(S, S)> = self.vertices
+            .iter()
+            .map(|p| ((p - origin).dot(u_axis), (p - origin).dot(v_axis)))
+            .collect();
+
+        let (min_u, max_u, min_v, max_v) = flat
+            .iter()
+            .fold(
+                (S::infinity(), S::neg_infinity(), S::infinity(), S::neg_infinity()),
+                |(min_u, max_u, min_v, max_v), (u, v)| {
+                    (min_u.min(*u), max_u.max(*u), min_v.min(*v), max_v.max(*v))
+                },
+            );
+        let extent = (max_u - min_u).max(max_v - min_v);
+        let two = S::one() + S::one();
+        let centre_u = (extent - (max_u - min_u)) / two;
+        let centre_v = (extent - (max_v - min_v)) / two;
+
+        flat.into_iter()
+            .map(|(u, v)| [(u - min_u + centre_u) / extent, (v - min_v + centre_v) / extent])
+            .map(|[u, v]| [u.to_f32().unwrap(), v.to_f32().unwrap()])
+            .collect()
+    }
+
+    /// Fan the polygon out into triangles from the first vertex. Each triangle keeps the
+    /// winding of the polygon.
+    pub fn triangles(&self) -> impl Iterator<Item = [Point3<S>; 3]> + '_ {
+        (1..(self.vertices.len() - 1))
+            .map(move |i| [self.vertices[0], self.vertices[i], self.vertices[i + 1]])
+    }
+
+    #[cfg(feature = "render")]
     pub fn as_scene_consumable<T: Into<Option<usize>>>(
         &self, colour: [f32; 3], index_offset: T,
-    ) -> (Vec<scene::Vertex>, Vec<u16>) {
+    ) -> (Vec<scene::Vertex>, Vec<u32>) {
         let maybie_offset: Option<usize> = index_offset.into();
         let offset: usize = maybie_offset.unwrap_or(0);
-        let mut indexes: Vec<u16> = Vec::new();
+        let mut indexes: Vec<u32> = Vec::new();
         
         for index in 1..(self.vertices.len() - 1) {
-            indexes.push((0 + offset) as u16);
-            indexes.push((index + offset) as u16);
-            indexes.push((index + 1 + offset) as u16);
+            indexes.push(offset as u32);
+            indexes.push((index + offset) as u32);
+            indexes.push((index + 1 + offset) as u32);
         }
         
+        let normal = self.normal.cast::<f32>().unwrap();
         let vertices = self.vertices
             .iter()
-            .map(|v| (v.clone(), self.normal.clone()))
-            .map(|(v, n)| scene::Vertex::new(
-                [v.x as f32, v.y as f32, v.z as f32],
-                [n.x as f32, n.y as f32, n.z as f32],
-                colour,
-            ))
+            .zip(self.texture_coordinates())
+            .map(|(v, uv)| (v.cast::<f32>().unwrap(), uv))
+            .map(|(v, uv)| scene::Vertex::textured(v.into(), normal.into(), colour, uv))
             .collect();
 
         (vertices, indexes)
@@ -81,3 +130,36 @@ mod test {
         
     }
 */
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn square_texture_coordinates() {
+        let square = Polygon::new(
+            &[
+                Point3::new(1.0, 1.0, 2.0),
+                Point3::new(3.0, 1.0, 2.0),
+                Point3::new(3.0, 3.0, 2.0),
+                Point3::new(1.0, 3.0, 2.0),
+            ],
+            Vector3::new(0.0, 0.0, 1.0),
+        );
+
+        let uvs = square.texture_coordinates();
+        assert!(uvs == vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]);
+    }
+
+    #[test]
+    fn triangle_centred() {
+        let triangle = Polygon::new(
+            &[Point3::new(0.0, 0.0, 0.0), Point3::new(4.0, 0.0, 0.0), Point3::new(2.0, 1.0, 0.0)],
+            Vector3::new(0.0, 0.0, 1.0),
+        );
+
+        // Wider than tall so it's centred up the middle.
+        let uvs = triangle.texture_coordinates();
+        assert!(uvs == vec![[0.0, 0.375], [1.0, 0.375], [0.5, 0.625]]);
+    }
+}
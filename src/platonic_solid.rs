@@ -1,8 +1,10 @@
 //! The five platonic solids.
 
+#[cfg(feature = "render")]
 use cgmath::{Point3, Vector3, BaseFloat};
 
 use crate::polyhedron::{Polyhedron, VtFc, Seed, SeedSolid};
+#[cfg(feature = "render")]
 use crate::scene;
 
 mod tetrahedron;
@@ -12,6 +14,7 @@ mod dodecahedron;
 mod icosahedron;
 
 /// Made private so as not to clash with `scene::Vertex`.
+#[cfg(feature = "render")]
 #[derive(Debug, Clone)]
 struct Vertex<S: BaseFloat> {
     position: Point3<S>,
@@ -19,12 +22,14 @@ struct Vertex<S: BaseFloat> {
     colour: [f32; 3],
 }
 
+#[cfg(feature = "render")]
 impl<S: BaseFloat> Vertex<S> {
     fn new(position: Point3<S>, normal: Vector3<S>, colour: [f32; 3]) -> Self {
         Vertex { position, normal, colour }
     }
 }
 
+#[cfg(feature = "render")]
 macro_rules! platonic {
     ($name:ident, $function:expr) => {
         #[derive(Debug, Copy, Clone)]
@@ -49,12 +54,12 @@ macro_rules! platonic {
                     ))
                     .collect::<Vec<scene::Vertex>>();
                 
-                scene::Cached::new(&vertices, &index)
+                scene::Cached::new(&vertices, scene::Index::U16(index))
             }
         }
 
         impl scene::Geometry for $name {
-            fn geometry(&self) -> (Vec<scene::Vertex>, Vec<u16>) {
+            fn geometry(&self) -> (Vec<scene::Vertex>, scene::Index) {
                 self.generate()
                     .geometry()
             }
@@ -62,10 +67,15 @@ macro_rules! platonic {
     };
 }
 
+#[cfg(feature = "render")]
 platonic!(Tetrahedron, tetrahedron::tetrahedron);
+#[cfg(feature = "render")]
 platonic!(Cube, cube::cube);
+#[cfg(feature = "render")]
 platonic!(Octahedron, octahedron::octahedron);
+#[cfg(feature = "render")]
 platonic!(Dodecahedron, dodecahedron::dodecahedron);
+#[cfg(feature = "render")]
 platonic!(Icosahedron, icosahedron::icosahedron);
 
 macro_rules! platonic2 {
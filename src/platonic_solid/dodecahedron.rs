@@ -2,12 +2,15 @@
 use std::ops::Neg;
 
 use cgmath::Point3;
-use cgmath::prelude::*;
 
 use crate::polyhedron::{Polyhedron, VtFc};
-use crate::geop::{triangle_normal, golden_ratio};
+use crate::geop::golden_ratio;
+#[cfg(feature = "render")]
+use crate::geop::triangle_normal;
+#[cfg(feature = "render")]
 use super::Vertex;
 
+#[cfg(feature = "render")]
 pub (in crate::platonic_solid) fn dodecahedron(
     len: f32, colour: [f32; 3]
 ) -> (Vec<Vertex<f32>>, Vec<u16>) {    
@@ -246,14 +249,6 @@ pub (in crate::platonic_solid) fn dodecahedron2(len: f64) -> Polyhedron<VtFc> {
     let r_yz_nn = Point3::new(0f64, l.neg(), s.neg());
     let r_yz_np = Point3::new(0f64, l.neg(), s);
 
-    // Get one of the points and as a vector, get the magnitude. This becomes the
-    // radius of the circumscribing sphere.
-    let radius = r_xy_pp
-        .clone()
-        .to_homogeneous()
-        .truncate()
-        .magnitude();
-
     let vertices: [Point3<f64>; 20] = [
         c_ppp,    //  0
         c_npp,    //  1
@@ -290,9 +285,8 @@ pub (in crate::platonic_solid) fn dodecahedron2(len: f64) -> Polyhedron<VtFc> {
     let p11 = [16, 0, 8, 4, 17];
     let p12 = [17, 5, 11, 1, 16];
 
-    Polyhedron::new(
+    Polyhedron::unchecked(
         cc,
-        radius,
         &vertices,
         &[&p1, &p2, &p3, &p4, &p5, &p6, &p7, &p8, &p9, &p10, &p11, &p12],
     )
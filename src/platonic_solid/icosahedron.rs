@@ -2,15 +2,18 @@
 use std::ops::Neg;
 
 use cgmath::Point3;
-use cgmath::prelude::*;
 
 use crate::polyhedron::{Polyhedron, VtFc};
-use crate::geop::{triangle_normal, golden_ratio};
+use crate::geop::golden_ratio;
+#[cfg(feature = "render")]
+use crate::geop::triangle_normal;
+#[cfg(feature = "render")]
 use super::Vertex;
 
 /// Possibly broken if the len is anything other that 1.0.
 ///
 /// TODO: Use the golden ratio!
+#[cfg(feature = "render")]
 pub (in crate::platonic_solid) fn icosahedron(
     len: f32, colour: [f32; 3]
 ) -> (Vec<Vertex<f32>>, Vec<u16>) {
@@ -220,14 +223,6 @@ pub (in crate::platonic_solid) fn icosahedron2(len: f64) -> Polyhedron<VtFc> {
     let r_yz_br = Point3::new(0f64, g_mid, h_len.neg());
     let r_yz_bl = Point3::new(0f64, g_mid.neg(), h_len.neg());
 
-    // Get one of the points and as a vector, get the magnitude. This becomes the
-    // radius of the circumscribing sphere.
-    let radius = r_xy_tl
-        .clone()
-        .to_homogeneous()
-        .truncate()
-        .magnitude();
-
     let vertices: [Point3<f64>; 12] = [
         r_xy_tl, //  0
         r_xy_tr, //  1
@@ -264,9 +259,8 @@ pub (in crate::platonic_solid) fn icosahedron2(len: f64) -> Polyhedron<VtFc> {
     let t19 = [10, 1, 4];
     let t20 = [6, 8, 5];
 
-    Polyhedron::new(
+    Polyhedron::unchecked(
         cc,
-        radius,
         &vertices,
         &[
             &t1,
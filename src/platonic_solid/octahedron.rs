@@ -4,9 +4,12 @@ use std::ops::Neg;
 use cgmath::Point3;
 
 use crate::polyhedron::{Polyhedron, VtFc};
+#[cfg(feature = "render")]
 use crate::geop::triangle_normal;
+#[cfg(feature = "render")]
 use super::Vertex;
 
+#[cfg(feature = "render")]
 pub (in crate::platonic_solid) fn octahedron(
     len: f32, colour: [f32; 3]
 ) -> (Vec<Vertex<f32>>, Vec<u16>) {
@@ -123,5 +126,5 @@ pub (in crate::platonic_solid) fn octahedron2(len: f64) -> Polyhedron<VtFc> {
     let t7 = [2, 3, 5];
     let t8 = [3, 1, 5];
 
-    Polyhedron::new(cc, radius, &vertices, &[&t1, &t2, &t3, &t4, &t5, &t6, &t7, &t8])
+    Polyhedron::unchecked(cc, &vertices, &[&t1, &t2, &t3, &t4, &t5, &t6, &t7, &t8])
 }
@@ -4,10 +4,13 @@ use std::ops::Neg;
 use cgmath::Point3;
 
 use crate::polyhedron::{Polyhedron, VtFc};
+#[cfg(feature = "render")]
 use crate::geop::triangle_normal;
+#[cfg(feature = "render")]
 use super::Vertex;
 
 /// Raw tetrahedron generation.
+#[cfg(feature = "render")]
 pub (in crate::platonic_solid) fn tetrahedron(
     len: f32, colour: [f32; 3]
 ) -> (Vec<Vertex<f32>>, Vec<u16>) {
@@ -97,6 +100,6 @@ pub (in crate::platonic_solid) fn tetrahedron2(len: f64) -> Polyhedron<VtFc> {
     let t3 = [2, 3, 1];
     let t4 = [0, 1, 3];
 
-    Polyhedron::new(cc, radius, &vertices, &[&t1, &t2, &t3, &t4])
+    Polyhedron::unchecked(cc, &vertices, &[&t1, &t2, &t3, &t4])
 }
 
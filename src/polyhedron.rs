@@ -4,17 +4,52 @@
 //! the polydron with various modifiers being chained on. A seed shape is usually a
 //! [platonic solid](https://en.wikipedia.org/wiki/Platonic_solid).
 //!
-//! Since all polyhedron are assumed to be regular, a circumscribing sphere is given by the
-//! radius. 
+//! Nothing keeps the vertices on a sphere so a polyhedron can be any shape, such as a torus
+//! read in as a custom seed. See `Polyhedron::bounding_sphere`, `Lift` and `DualCenter`.
 use std::{fmt, error};
 use std::iter::Extend;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashSet};
 
-use cgmath::{Point3, Vector3};
-use cgmath::prelude::*;
+use cgmath::{Point3, Vector3, BaseFloat, MetricSpace};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
 use crate::geop;
 use crate::planar;
+use crate::platonic_solid::{
+    Tetrahedron2, Cube2, Octahedron2, Dodecahedron2, Icosahedron2,
+};
+
+mod adjacency;
+pub mod analysis;
+mod cache;
+mod canonical;
+mod count;
+mod custom;
+pub mod diagnostics;
+pub mod export;
+mod faces;
+mod flag;
+mod geodesic;
+mod grid;
+pub mod import;
+mod isomorphism;
+mod iter;
+mod morph;
+mod operation;
+mod parallel;
+mod quad_sphere;
+mod relax;
+pub mod repl;
+
+pub use self::adjacency::{Adjacency, IncidenceTable};
+pub use self::cache::SpecificationCache;
+pub use self::custom::{CustomSeed, SeedError};
+pub use self::faces::{Faces, FacesIter};
+pub use self::grid::GoldbergGrid;
+pub use self::iter::{EdgeIter, EdgeRef, FaceIter, FaceRef, VertexIter, VertexRef};
+pub use self::morph::Morph;
+pub use self::quad_sphere::QuadSphere;
 
 #[derive(Debug, Copy, Clone)]
 pub enum SeedSolid {
@@ -23,6 +58,9 @@ pub enum SeedSolid {
     Octahedron,
     Dodecahedron,
     Icosahedron,
+
+    /// Any other closed polyhedron such as one read in from a file.
+    Custom,
 }
 
 impl SeedSolid {
@@ -33,6 +71,7 @@ impl SeedSolid {
             SeedSolid::Octahedron   => "O",
             SeedSolid::Dodecahedron => "D",
             SeedSolid::Icosahedron  => "I",
+            SeedSolid::Custom       => "X",
         }
     }
 }
@@ -46,6 +85,76 @@ pub trait Seed: objekt::Clone + fmt::Debug {
 
 objekt::clone_trait_object!(Seed);
 
+/// Any polyhedron already built, such as a `geop::convex_hull`, can seed more operations.
+impl Seed for Polyhedron<VtFc> {
+    fn solid(&self) -> SeedSolid {
+        SeedSolid::Custom
+    }
+
+    fn polyhedron(&self) -> Polyhedron<VtFc> {
+        self.clone()
+    }
+}
+
+/// Build a [geodesic polyhedron](https://en.wikipedia.org/wiki/Geodesic_polyhedron) by
+/// subdividing each face of a triangle faced `seed` such as the icosahedron, octahedron or
+/// tetrahedron. The `frequency` is the `(m, n)` class. `(f, 0)` is class I, `(f, f)` is
+/// class II and anything else is class III. New vertices are projected out onto the
+/// circumscribing sphere of the seed.
+pub fn geodesic<S: Seed>(
+    frequency: (usize, usize), seed: &S,
+) -> Result<Polyhedron<VtFc>, OpError> {
+    let (m, n) = frequency;
+    if m == 0 && n == 0 {
+        return Err(OpError::ZeroFrequency);
+    }
+
+    let seed = seed.polyhedron();
+    if seed.data.faces.iter().any(|face| face.len() != 3) {
+        return Err(OpError::NotTriangulated);
+    }
+
+    Ok(geodesic::subdivide(seed, m, n, Lift::Sphere))
+}
+
+/// Build a [Goldberg Polyhedron](https://en.wikipedia.org/wiki/Goldberg_polyhedron) of
+/// class GP(m, n) directly. This is the dual of the geodesic icosahedron of the same class.
+/// The result has `10(m² + mn + n²) + 2` faces, twelve of them pentagons and the rest
+/// hexagons. Swapping `m` and `n` gives the mirror image.
+pub fn goldberg(m: usize, n: usize, radius: f64) -> Result<Polyhedron<VtFc>, OpError> {
+    let side_len = radius / (2.0 * std::f64::consts::PI / 5.0).sin();
+    let geodesic = geodesic((m, n), &Icosahedron2::new(side_len))?;
+
+    Ok(operation::dual(geodesic, DualCenter::Circumsphere))
+}
+
+/// Where Kis and Subdivide put the vertices they add.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Lift {
+    /// Out onto the bounding sphere so round polyhedra stay round.
+    Sphere,
+
+    /// Left on the faces they're made in, at the centroid for Kis. Keeps the shape of
+    /// polyhedra that aren't round such as a torus. Kis then leaves flat pyramids.
+    Flat,
+}
+
+/// Where Dual puts the vertex it makes for each face.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DualCenter {
+    /// The face centroid pushed out onto the bounding sphere so round polyhedra stay round.
+    Circumsphere,
+
+    /// The point on the plane of the face closest to the center, where the plane would
+    /// touch a sphere it's tangent to. Same as the centroid on a regular face but keeps a
+    /// lopsided face's tilt.
+    FacePlaneTangent,
+
+    /// The face centroid as it is. Keeps the shape of polyhedra that aren't round such as
+    /// a torus.
+    Centroid,
+}
+
 /// Conway operations which change the topology of a polyhedron. For more information see
 /// [here](https://en.wikipedia.org/wiki/Conway_polyhedron_notation). Only few of the
 /// operators are implmented. The ones necessary for constructing a [Goldberg Polyhedron](https://en.wikipedia.org/wiki/Goldberg_polyhedron)
@@ -59,14 +168,119 @@ enum ConwayOperation {
     Seed(SeedSolid, Polyhedron<VtFc>),
 
     /// Replace each face with a vertex and each vertex is a face.
-    Dual,
+    Dual(DualCenter),
 
     /// Raise a pyramid on each face. When doing this on a tetrahedron, it will make it
-    /// look like a cube. It is not. The topology is different.
-    Kis,
+    /// look like a cube. It is not. The topology is different. When a degree is given
+    /// only the faces with that many vertices have a pyramid raised.
+    Kis(Option<usize>, Lift),
+
+    /// Specifically, uniform truncation. When a degree is given only the vertices where
+    /// that many faces meet are cut off. When a ratio is given each edge is cut that far
+    /// along from each end instead of where it would leave regular faces.
+    Truncate(Option<usize>, Option<f64>),
+
+    /// Rectify. Each edge is replaced by a vertex at its midpoint. The original faces
+    /// shrink and each original vertex becomes a face.
+    Ambo,
+
+    /// Split each face into pentagons spiralling around the face centroid. Used for
+    /// constructing class III Goldberg polyhedra.
+    Gyro,
+
+    /// Dual of gyro. Each vertex is surrounded by triangles twisted the same way as the
+    /// gyro pentagons.
+    Snub,
+
+    /// Cantellation. Separate the faces and fill the gaps with squares along the edges and
+    /// vertex figures at the vertices.
+    Expand,
+
+    /// Truncated rectification. Faces and vertex figures with double the edges separated
+    /// by squares.
+    Bevel,
+
+    /// Dual of expand. Split each face into quadrilaterals meeting at the face centroid.
+    Ortho,
+
+    /// Dual of ambo. Each edge becomes a quadrilateral.
+    Join,
 
-    /// Specifically, uniform truncation.
-    Truncate,
+    /// Replace each edge with a hexagon while shrinking the faces. Used for constructing
+    /// class II Goldberg polyhedra.
+    Chamfer,
+
+    /// Rotate a smaller copy of each face and surround it with hexagons. Used for
+    /// constructing class III Goldberg polyhedra.
+    Whirl,
+
+    /// Rotate a smaller copy of each face and surround it with quadrilaterals.
+    Propeller,
+
+    /// Kis of join. Each n sided face is split into 2n triangles.
+    Meta,
+
+    /// Kis of dual. Each vertex is surrounded by triangles, two per edge.
+    Needle,
+
+    /// Dual of kis. Each face and each vertex becomes a face with twice the edges.
+    Zip,
+
+    /// Raise a prism on each face.
+    Loft,
+
+    /// Raise an antiprism on each face.
+    Lace,
+
+    /// Split each triangle into the square of the frequency smaller triangles. Faces that
+    /// aren't triangles have a pyramid raised on them first. The dual of a subdivided
+    /// icosahedron is the Goldberg polyhedron GP(n, 0).
+    Subdivide(usize, Lift),
+
+    /// Move each vertex the given fraction of the way out to the bounding sphere. Only the
+    /// shape changes, not the topology.
+    Spherize(f64),
+
+    /// Push each face out along its normal by the given height and stand it on a wall of
+    /// quadrilaterals. Like loft but the faces keep their size and nothing is lifted.
+    Extrude(f64),
+
+    /// Shrink each face the given ratio of the way to its centroid and join it to the
+    /// original edges by a ring of quadrilaterals. Like loft without lifting.
+    Inset(f64),
+}
+
+impl ConwayOperation {
+    /// Parameterized operations have the parameter appended such as `k5`.
+    fn notation(&self) -> String {
+        match self {
+            ConwayOperation::Seed(ss, _) => ss.conway_notation().to_owned(),
+            ConwayOperation::Dual(_) => "d".to_owned(),
+            ConwayOperation::Kis(None, _) => "k".to_owned(),
+            ConwayOperation::Kis(Some(degree), _) => format!("k{}", degree),
+            ConwayOperation::Truncate(None, _) => "t".to_owned(),
+            ConwayOperation::Truncate(Some(degree), _) => format!("t{}", degree),
+            ConwayOperation::Ambo => "a".to_owned(),
+            ConwayOperation::Gyro => "g".to_owned(),
+            ConwayOperation::Snub => "s".to_owned(),
+            ConwayOperation::Expand => "e".to_owned(),
+            ConwayOperation::Bevel => "b".to_owned(),
+            ConwayOperation::Ortho => "o".to_owned(),
+            ConwayOperation::Join => "j".to_owned(),
+            ConwayOperation::Chamfer => "c".to_owned(),
+            ConwayOperation::Whirl => "w".to_owned(),
+            ConwayOperation::Propeller => "p".to_owned(),
+            ConwayOperation::Meta => "m".to_owned(),
+            ConwayOperation::Needle => "n".to_owned(),
+            ConwayOperation::Zip => "z".to_owned(),
+            ConwayOperation::Loft => "l".to_owned(),
+            ConwayOperation::Lace => "L".to_owned(),
+            ConwayOperation::Subdivide(frequency, _) => format!("u{}", frequency),
+            ConwayOperation::Spherize(_) => "S".to_owned(),
+            ConwayOperation::Extrude(_) => "x".to_owned(),
+            ConwayOperation::Inset(_) => "i".to_owned(),
+        }
+    }
 }
 
 /// A polyhedron ready to be built. This struct is not to be modified.
@@ -85,12 +299,7 @@ impl Specification {
         let notation: String = operations
             .iter()
             .rfold(String::new(), |mut ops, op| -> String {
-                ops.push_str(match op {
-                    ConwayOperation::Seed(ss, _) => ss.conway_notation(),
-                    ConwayOperation::Dual => "d",
-                    ConwayOperation::Kis =>  "k",
-                    ConwayOperation::Truncate => "t",
-                });
+                ops.push_str(&op.notation());
                 
                 ops
             });
@@ -101,227 +310,115 @@ impl Specification {
         }
     }
 
+    /// Parse Conway notation such as `dkD` straight into a `Specification`. See
+    /// `ConwayDescription::from_notation`.
+    pub fn parse(notation: &str) -> Result<Self, NotationError> {
+        ConwayDescription::from_notation(notation)?
+            .emit()
+            .map_err(NotationError::Op)
+    }
+
+    /// Same as `parse` but `X` stands for `custom`. Reads back the notation of a
+    /// `Specification` seeded with a `CustomSeed` or any other polyhedron.
+    pub fn parse_with_seed<S: Seed>(
+        notation: &str, custom: &S,
+    ) -> Result<Self, NotationError> {
+        ConwayDescription::from_notation_with_seed(notation, custom)?
+            .emit()
+            .map_err(NotationError::Op)
+    }
+
     pub fn notation(&self) -> &str {
         &self.notation
     }
 
+    /// The vertex, edge and face counts `produce` will give worked out from the operations
+    /// alone. Handy for spotting a huge mesh before building it. `None` when a degree
+    /// restricted kis or truncate can't be predicted because an earlier degree restricted
+    /// operation left the number of faces or vertices of that degree unknown.
+    pub fn predicted_counts(&self) -> Option<(usize, usize, usize)> {
+        let seed = match &self.operations[0] {
+            ConwayOperation::Seed(_, p) => count::Counts::new(p),
+            _ => panic!("Specification must start with a seed."),
+        };
+
+        self.operations
+            .iter()
+            .skip(1)
+            .try_fold(seed, |counts, op| counts.apply(op))
+            .map(|counts| counts.totals())
+    }
+
     pub fn produce(&self) -> Polyhedron<VtFc> {
         let seed = match &self.operations[0] {
             ConwayOperation::Seed(_, p) => p.clone(),
             _ => panic!("Specification must start with a seed."),
         };        
         
-        self.operations
+        Specification::apply(&self.operations[1..], seed)
+    }
+
+    /// Produce starting from a polyhedron already produced from `base` rather than from the
+    /// seed. Only the operations after those of `base` are run. `None` if this
+    /// specification doesn't start with the operations of `base`. Operations are compared
+    /// as the cache compares them so any ratio, lift or amount has to match too.
+    pub fn produce_from(
+        &self, base: &Specification, produced: Polyhedron<VtFc>,
+    ) -> Option<Polyhedron<VtFc>> {
+        let count = base.operations.len();
+        if count > self.operations.len() {
+            return None;
+        }
+
+        let same = base.operations
             .iter()
-            .skip(1)
-            .fold(seed, |p, op| match op {
-                ConwayOperation::Dual => {
-                    let p = p.centroidize();
-                    let vertex_face_members = p.faces_per_vertex();
-
-                    let np_faces: Vec<Vec<usize>> = vertex_face_members
-                        .into_iter()
-                        .fold(Vec::new(), |mut faces, (v_index, f_indices)| {
-                            // The normal of our new face plane is the vertex.
-                            let vertex = p.data.vertices[v_index].clone();
-                            let vector = vertex
-                                .clone()
-                                .to_homogeneous()
-                                .truncate();
-                            let normal = vector
-                                .clone()
-                                .normalize();
-
-                            // To finish our plane definition, we use one of the calculated
-                            // centroids as the point on the plane
-                            let point = p.data.centroids[f_indices[0]].clone();
-                            
-                            // We use the `point` and `normal` to define the plane for the
-                            // new face defined from the centroids.
-                            let plane = geop::Plane::new(normal, point);
-                            
-                            // Get the intersection of the vertex as a line from origin with
-                            // the plane. Intersection point is centroid of the new face.
-                            let centroid = plane
-                                .line_intersection(vector, vertex)
-                                .expect("Polyhedron is internally inconsistent");
-
-                            // Sort the vertices of the new face clockwize using
-                            // the new normal and the new centroid.
-                            let mut ordered: Vec<usize> = f_indices.clone();
-                            ordered.sort_by(|fi1, fi2| geop::clockwise(
-                                &p.data.centroids[*fi1],
-                                &p.data.centroids[*fi2],
-                                &centroid,
-                                plane.normal(),
-                            ).reverse() // flip the ordering around. Somethings up...
-                            );
-
-                            faces.push(ordered);
-                            faces
-                        });
-
-                    // We lengthen the lines from origin to each centroid so that the
-                    // vertex is touching the circumscribing sphere. We do this by just
-                    // adjusting the magnitude to equal the radius.
-                    let vertices = p.data.centroids
-                        .iter()
-                        .map(|point| geop::point_line_lengthen(point, p.data.radius))
-                        .collect();
-
-                    Polyhedron {
-                        data: VtFc {
-                            center: p.data.center,
-                            radius: p.data.radius,
-                            vertices,
-                            faces: np_faces,
-                        },
-                    }
-                },
-                ConwayOperation::Kis => {
-                    let mut k = p.centroidize();
-                    let offset = k.data.vertices.len();
-
-                    // The centroids form the tips of pyramids rising from each face. Thus
-                    // each face is subdivided into multiple triangle faces. To rise the
-                    // centroids we increase the magnitude to equal the radius of the
-                    // circumscribing sphere.
-                    let radius = k.data.radius;
-                    let pyramid_tips_iter = k.data.centroids
-                        .iter()
-                        .map(|point| geop::point_line_lengthen(point, radius));
-
-                    // We attach the pyramid_tips (centroids) to the vertices.
-                    //
-                    // TODO: Sort the vertices afterwards to put the pyramid_tips within
-                    //       their face locality as an extra step to prevent jumping
-                    //       through memory tempting cache misses.
-                    k.data.vertices.extend(pyramid_tips_iter);
-
-                    // Now we go through each face and split into triangles using the
-                    // centroid vertex at index(face_num + offset) in the vertices.
-                    let faces: Vec<Vec<usize>> = k.data.faces
-                        .into_iter()
-                        .enumerate()
-                        .fold(Vec::new(), |mut faces, (f_index, face)| {
-                            let pyramid_tip_index = f_index + offset;
-
-                            // Start the first face from the first and last indexes.
-                            faces.push(
-                                vec![*face.last().unwrap(), face[0], pyramid_tip_index]
-                            );
-
-                            // Get the rest of the new faces.
-                            face.windows(2)
-                                .for_each(|w| {
-                                    faces.push(vec![w[0], w[1], pyramid_tip_index])
-                                });
-                            
-                            faces
-                        });
-
-                    Polyhedron {
-                        data: VtFc {
-                            center: k.data.center,
-                            radius,
-                            vertices: k.data.vertices,
-                            faces,
-                        }
-                    }
-                },
-                ConwayOperation::Truncate => {                    
-                    let vertex_face_members = p.faces_per_vertex();
-                    //                      v1         v2     f1     f2
-                    let mut lines: HashMap<usize, Vec<(usize, usize, usize)>> =
-                                           HashMap::new();
-
-                    for (v_i, faces) in vertex_face_members {
-                        // find shared lines
-                        for face in faces.iter() {
-                            // Scan through all the other faces. We test if they both
-                            // share another vertex apart from the current vertex.
-                            p.data.faces[*face]
-                                .iter()
-                                .filter(|i| **i != v_i) // skip the current vertex
-                                .for_each(|i| {
-                                    faces
-                                        .iter()
-                                        .filter(|f| *f != face) // skip the current face
-                                        .for_each(|f| {
-                                            p.data.faces[*f]
-                                                .iter()
-                                                .enumerate()
-                                                .filter(|(fi, _)| *fi != v_i)
-                                                .for_each(|(fi, _)| {
-                                                    if fi == *i {
-                                                        let edges = lines
-                                                            .entry(v_i)
-                                                            .or_insert(Vec::new());
-                                                        
-                                                        edges.push((*i, *face, fi));
-                                                    }
-                                                })
-                                        })
-                                });
-                        }
-                    }
+            .zip(self.operations.iter())
+            .all(|(op1, op2)| cache::op_key(op1) == cache::op_key(op2));
+        if !same {
+            return None;
+        }
 
-                    dbg!(&lines);
-                    
-                    let mut vertices = p.data.vertices.clone();
-                    let mut faces = p.data.faces.clone();
-                    p.data.vertices
-                        .iter()
-                        .enumerate()
-                        .for_each(|(i, vertex)| {
-                            //                      fi     nvi
-                            let mut update: HashMap<usize, Vec<usize>> = HashMap::new();
-                            let chop = 0.75f64;
-                            let edges = lines.get(&i).unwrap();
-                            for edge in edges {
-                                let v_2 = vertices[edge.0];
-                                let vector = vertex - v_2;                                
-                                let n_x = v_2.x + vector.x * chop;
-                                let n_y = v_2.y + vector.y * chop;
-                                let n_z = v_2.z + vector.z * chop;
-                                let new_point = Point3::new(n_x, n_y, n_z);
-
-                                let index = vertices.len();
-                                vertices.push(new_point);
-
-                                {
-                                    let fe = update
-                                        .entry(edge.1)
-                                        .or_insert(Vec::new());
-
-                                    fe.push(index);
-                                }
-
-                                {
-                                    let fe = update
-                                        .entry(edge.2)
-                                        .or_insert(Vec::new());
-
-                                    fe.push(index);
-                                }
-                            }
-
-                            for (f_i, nvi) in update {
-                                let fvis = &mut faces[f_i];
-                                fvis.retain(|vi| *vi != i);
-                                fvis.extend(nvi);
-                            }
-                        });
-
-                    Polyhedron {
-                        data: VtFc {
-                            center: p.data.center,
-                            radius: p.data.radius,
-                            vertices,
-                            faces,
-                        }
-                    }
+        Some(Specification::apply(&self.operations[count..], produced))
+    }
+
+    /// Append the operations of `more` to produce a new `Specification`. See
+    /// `ConwayDescription::compose`.
+    pub fn extend(self, more: &ConwayDescription) -> Result<Specification, OpError> {
+        ConwayDescription::from(self)
+            .compose(more)?
+            .emit()
+    }
+
+    fn apply(operations: &[ConwayOperation], p: Polyhedron<VtFc>) -> Polyhedron<VtFc> {
+        operations
+            .iter()
+            .fold(p, |p, op| match op {
+                ConwayOperation::Dual(placement) => operation::dual(p, *placement),
+                ConwayOperation::Kis(degree, lift) => operation::kis(p, *degree, *lift),
+                ConwayOperation::Truncate(degree, ratio) => {
+                    operation::truncate(p, *degree, *ratio)
+                },
+                ConwayOperation::Ambo => operation::ambo(p),
+                ConwayOperation::Gyro => operation::gyro(p),
+                ConwayOperation::Snub => operation::snub(p),
+                ConwayOperation::Expand => operation::expand(p),
+                ConwayOperation::Bevel => operation::bevel(p),
+                ConwayOperation::Ortho => operation::ortho(p),
+                ConwayOperation::Join => operation::join(p),
+                ConwayOperation::Chamfer => operation::chamfer(p),
+                ConwayOperation::Whirl => operation::whirl(p),
+                ConwayOperation::Propeller => operation::propeller(p),
+                ConwayOperation::Meta => operation::meta(p),
+                ConwayOperation::Needle => operation::needle(p),
+                ConwayOperation::Zip => operation::zip(p),
+                ConwayOperation::Loft => operation::loft(p),
+                ConwayOperation::Lace => operation::lace(p),
+                ConwayOperation::Subdivide(frequency, lift) => {
+                    operation::subdivide(p, *frequency, *lift)
                 },
+                ConwayOperation::Spherize(strength) => operation::spherize(p, *strength),
+                ConwayOperation::Extrude(height) => operation::extrude(p, *height),
+                ConwayOperation::Inset(ratio) => operation::inset(p, *ratio),
                 ConwayOperation::Seed(_, _) => panic!("Second seed somehow snuck in."),
             })
     }
@@ -333,6 +430,15 @@ pub struct ConwayDescription {
     operations: Vec<ConwayOperation>,
 }
 
+impl From<Specification> for ConwayDescription {
+    /// Carry on adding operations to an already emitted `Specification`.
+    fn from(specification: Specification) -> Self {
+        ConwayDescription {
+            operations: specification.operations,
+        }
+    }
+}
+
 impl ConwayDescription {
     pub fn new() -> Self {
         ConwayDescription {
@@ -340,7 +446,108 @@ impl ConwayDescription {
         }
     }
 
-    pub fn seed<S: Seed>(mut self, seed: &S) -> Result<Self, OpError> {
+    /// Apply the operations of `other` after the operations already added. The seed of
+    /// `other` is dropped so that `kdT` composed with `aC` is `akdT`.
+    pub fn compose(mut self, other: &ConwayDescription) -> Result<Self, OpError> {
+        if self.operations.is_empty() || other.operations.is_empty() {
+            Err(OpError::NoSeedSet)
+        } else {
+            self.operations.extend(other.operations[1..].iter().cloned());
+            Ok(self)
+        }
+    }
+
+    /// Build up the operations from Conway notation. The notation is read right to left
+    /// starting with the seed which must be the last character. Seeds are made with a side
+    /// length of one. The kis and truncate operators take an optional degree such as `k5`
+    /// and subdivide an optional frequency such as `u3`, two when left out. `S` spherizes
+    /// at full strength, `x` extrudes by 0.1 and `i` insets by 0.25. The `X` of a custom
+    /// seed can only be read by `from_notation_with_seed`.
+    pub fn from_notation(notation: &str) -> Result<Self, NotationError> {
+        ConwayDescription::read_notation(notation, None)
+    }
+
+    /// Same as `from_notation` but `X` stands for `custom`.
+    pub fn from_notation_with_seed<S: Seed>(
+        notation: &str, custom: &S,
+    ) -> Result<Self, NotationError> {
+        ConwayDescription::read_notation(notation, Some(custom))
+    }
+
+    fn read_notation(
+        notation: &str, custom: Option<&dyn Seed>,
+    ) -> Result<Self, NotationError> {
+        let chars: Vec<(usize, char)> = notation.char_indices().collect();
+        let (seed_position, seed) = *chars.last().ok_or(NotationError::Empty)?;
+
+        let description = match seed {
+            'T' => ConwayDescription::new().seed(&Tetrahedron2::new(1.0)),
+            'C' => ConwayDescription::new().seed(&Cube2::new(1.0)),
+            'O' => ConwayDescription::new().seed(&Octahedron2::new(1.0)),
+            'D' => ConwayDescription::new().seed(&Dodecahedron2::new(1.0)),
+            'I' => ConwayDescription::new().seed(&Icosahedron2::new(1.0)),
+            'X' => match custom {
+                Some(custom) => ConwayDescription::new().seed(custom),
+                None => return Err(NotationError::NoCustomSeed(seed_position)),
+            },
+            _ => return Err(NotationError::UnknownSeed(seed, seed_position)),
+        }.map_err(NotationError::Op)?;
+
+        // Split into operators each with their optional degree. They are read left to right
+        // here and then applied right to left.
+        let mut operators: Vec<(usize, char, Option<usize>)> = Vec::new();
+        for (position, c) in chars[..chars.len() - 1].iter().cloned() {
+            match (c.to_digit(10), operators.last_mut()) {
+                (Some(digit), Some((_, op, degree))) if "ktu".contains(*op) => {
+                    *degree = degree
+                        .unwrap_or(0)
+                        .checked_mul(10)
+                        .and_then(|d| d.checked_add(digit as usize));
+                    if degree.is_none() {
+                        return Err(NotationError::InvalidDegree(position));
+                    }
+                },
+                (Some(_), _) => return Err(NotationError::UnexpectedDigit(c, position)),
+                (None, _) => operators.push((position, c, None)),
+            }
+        }
+
+        operators
+            .into_iter()
+            .rev()
+            .try_fold(description, |description, (position, op, degree)| {
+                match (op, degree) {
+                    ('d', None) => description.dual(),
+                    ('k', None) => description.kis(),
+                    ('k', Some(degree)) => description.kis_on(degree),
+                    ('t', None) => description.truncate(),
+                    ('t', Some(degree)) => description.truncate_degree(degree),
+                    ('a', None) => description.ambo(),
+                    ('g', None) => description.gyro(),
+                    ('s', None) => description.snub(),
+                    ('e', None) => description.expand(),
+                    ('b', None) => description.bevel(),
+                    ('o', None) => description.ortho(),
+                    ('j', None) => description.join(),
+                    ('c', None) => description.chamfer(),
+                    ('w', None) => description.whirl(),
+                    ('p', None) => description.propeller(),
+                    ('m', None) => description.meta(),
+                    ('n', None) => description.needle(),
+                    ('z', None) => description.zip(),
+                    ('l', None) => description.loft(),
+                    ('L', None) => description.lace(),
+                    ('u', None) => description.subdivide(2),
+                    ('u', Some(frequency)) => description.subdivide(frequency),
+                    ('S', None) => description.spherize(1.0),
+                    ('x', None) => description.extrude(0.1),
+                    ('i', None) => description.inset(0.25),
+                    _ => return Err(NotationError::UnknownOperator(op, position)),
+                }.map_err(NotationError::Op)
+            })
+    }
+
+    pub fn seed<S: Seed + ?Sized>(mut self, seed: &S) -> Result<Self, OpError> {
         if !self.operations.is_empty() {
             Err(OpError::AlreadyHasSeed)
         } else {
@@ -349,20 +556,38 @@ impl ConwayDescription {
         }
     }
 
-    pub fn dual(mut self) -> Result<Self, OpError> {
+    /// Lifts the new vertices onto the bounding sphere. See `dual_centered`.
+    pub fn dual(self) -> Result<Self, OpError> {
+        self.dual_centered(DualCenter::Circumsphere)
+    }
+
+    /// Dual with the new vertices put where `placement` says.
+    pub fn dual_centered(mut self, placement: DualCenter) -> Result<Self, OpError> {
         if self.operations.is_empty() {
             Err(OpError::NoSeedSet)
         } else {
-            self.operations.push(ConwayOperation::Dual);
+            self.operations.push(ConwayOperation::Dual(placement));
             Ok(self)
         }
     }
 
-    pub fn kis(mut self) -> Result<Self, OpError> {
+    /// Lifts the pyramid tips onto the bounding sphere. See `kis_lifted`.
+    pub fn kis(self) -> Result<Self, OpError> {
+        self.kis_lifted(None, Lift::Sphere)
+    }
+
+    /// Kis only the faces with `degree` vertices.
+    pub fn kis_on(self, degree: usize) -> Result<Self, OpError> {
+        self.kis_lifted(Some(degree), Lift::Sphere)
+    }
+
+    /// Kis the faces with `degree` vertices or all of them when `None`, putting the pyramid
+    /// tips where `lift` says.
+    pub fn kis_lifted(mut self, degree: Option<usize>, lift: Lift) -> Result<Self, OpError> {
         if self.operations.is_empty() {
             Err(OpError::NoSeedSet)
         } else {
-            self.operations.push(ConwayOperation::Kis);
+            self.operations.push(ConwayOperation::Kis(degree, lift));
             Ok(self)
         }
     }
@@ -371,7 +596,223 @@ impl ConwayDescription {
         if self.operations.is_empty() {
             Err(OpError::NoSeedSet)
         } else {
-            self.operations.push(ConwayOperation::Truncate);
+            self.operations.push(ConwayOperation::Truncate(None, None));
+            Ok(self)
+        }
+    }
+
+    /// Truncate only the vertices where `degree` faces meet.
+    pub fn truncate_degree(mut self, degree: usize) -> Result<Self, OpError> {
+        if self.operations.is_empty() {
+            Err(OpError::NoSeedSet)
+        } else {
+            self.operations.push(ConwayOperation::Truncate(Some(degree), None));
+            Ok(self)
+        }
+    }
+
+    /// Truncate cutting each edge `ratio` of the way along from each end. The ratio must
+    /// be more than 0 and less than 0.5.
+    pub fn truncate_ratio(mut self, ratio: f64) -> Result<Self, OpError> {
+        if self.operations.is_empty() {
+            Err(OpError::NoSeedSet)
+        } else if !(ratio > 0.0 && ratio < 0.5) {
+            Err(OpError::InvalidRatio)
+        } else {
+            self.operations.push(ConwayOperation::Truncate(None, Some(ratio)));
+            Ok(self)
+        }
+    }
+
+    pub fn ambo(mut self) -> Result<Self, OpError> {
+        if self.operations.is_empty() {
+            Err(OpError::NoSeedSet)
+        } else {
+            self.operations.push(ConwayOperation::Ambo);
+            Ok(self)
+        }
+    }
+
+    pub fn gyro(mut self) -> Result<Self, OpError> {
+        if self.operations.is_empty() {
+            Err(OpError::NoSeedSet)
+        } else {
+            self.operations.push(ConwayOperation::Gyro);
+            Ok(self)
+        }
+    }
+
+    pub fn snub(mut self) -> Result<Self, OpError> {
+        if self.operations.is_empty() {
+            Err(OpError::NoSeedSet)
+        } else {
+            self.operations.push(ConwayOperation::Snub);
+            Ok(self)
+        }
+    }
+
+    pub fn expand(mut self) -> Result<Self, OpError> {
+        if self.operations.is_empty() {
+            Err(OpError::NoSeedSet)
+        } else {
+            self.operations.push(ConwayOperation::Expand);
+            Ok(self)
+        }
+    }
+
+    pub fn bevel(mut self) -> Result<Self, OpError> {
+        if self.operations.is_empty() {
+            Err(OpError::NoSeedSet)
+        } else {
+            self.operations.push(ConwayOperation::Bevel);
+            Ok(self)
+        }
+    }
+
+    pub fn ortho(mut self) -> Result<Self, OpError> {
+        if self.operations.is_empty() {
+            Err(OpError::NoSeedSet)
+        } else {
+            self.operations.push(ConwayOperation::Ortho);
+            Ok(self)
+        }
+    }
+
+    pub fn join(mut self) -> Result<Self, OpError> {
+        if self.operations.is_empty() {
+            Err(OpError::NoSeedSet)
+        } else {
+            self.operations.push(ConwayOperation::Join);
+            Ok(self)
+        }
+    }
+
+    pub fn chamfer(mut self) -> Result<Self, OpError> {
+        if self.operations.is_empty() {
+            Err(OpError::NoSeedSet)
+        } else {
+            self.operations.push(ConwayOperation::Chamfer);
+            Ok(self)
+        }
+    }
+
+    pub fn whirl(mut self) -> Result<Self, OpError> {
+        if self.operations.is_empty() {
+            Err(OpError::NoSeedSet)
+        } else {
+            self.operations.push(ConwayOperation::Whirl);
+            Ok(self)
+        }
+    }
+
+    pub fn propeller(mut self) -> Result<Self, OpError> {
+        if self.operations.is_empty() {
+            Err(OpError::NoSeedSet)
+        } else {
+            self.operations.push(ConwayOperation::Propeller);
+            Ok(self)
+        }
+    }
+
+    pub fn meta(mut self) -> Result<Self, OpError> {
+        if self.operations.is_empty() {
+            Err(OpError::NoSeedSet)
+        } else {
+            self.operations.push(ConwayOperation::Meta);
+            Ok(self)
+        }
+    }
+
+    pub fn needle(mut self) -> Result<Self, OpError> {
+        if self.operations.is_empty() {
+            Err(OpError::NoSeedSet)
+        } else {
+            self.operations.push(ConwayOperation::Needle);
+            Ok(self)
+        }
+    }
+
+    pub fn zip(mut self) -> Result<Self, OpError> {
+        if self.operations.is_empty() {
+            Err(OpError::NoSeedSet)
+        } else {
+            self.operations.push(ConwayOperation::Zip);
+            Ok(self)
+        }
+    }
+
+    pub fn loft(mut self) -> Result<Self, OpError> {
+        if self.operations.is_empty() {
+            Err(OpError::NoSeedSet)
+        } else {
+            self.operations.push(ConwayOperation::Loft);
+            Ok(self)
+        }
+    }
+
+    pub fn lace(mut self) -> Result<Self, OpError> {
+        if self.operations.is_empty() {
+            Err(OpError::NoSeedSet)
+        } else {
+            self.operations.push(ConwayOperation::Lace);
+            Ok(self)
+        }
+    }
+
+    /// Lifts the new vertices onto the bounding sphere. See `subdivide_lifted`.
+    pub fn subdivide(self, frequency: usize) -> Result<Self, OpError> {
+        self.subdivide_lifted(frequency, Lift::Sphere)
+    }
+
+    /// Split each triangle into `frequency²` triangles, putting the new vertices where
+    /// `lift` says. A frequency of one leaves triangles as they are.
+    pub fn subdivide_lifted(mut self, frequency: usize, lift: Lift) -> Result<Self, OpError> {
+        if self.operations.is_empty() {
+            Err(OpError::NoSeedSet)
+        } else if frequency == 0 {
+            Err(OpError::ZeroFrequency)
+        } else {
+            self.operations.push(ConwayOperation::Subdivide(frequency, lift));
+            Ok(self)
+        }
+    }
+
+    /// Move each vertex `strength` of the way out to the bounding sphere. Zero leaves the
+    /// vertices where they are and one puts them all on the sphere. Can go anywhere in the
+    /// chain to round off what came before such as the flat pyramids of `kis_lifted`.
+    pub fn spherize(mut self, strength: f64) -> Result<Self, OpError> {
+        if self.operations.is_empty() {
+            Err(OpError::NoSeedSet)
+        } else if !(0.0..=1.0).contains(&strength) {
+            Err(OpError::InvalidStrength)
+        } else {
+            self.operations.push(ConwayOperation::Spherize(strength));
+            Ok(self)
+        }
+    }
+
+    /// Push each face `height` out along its normal, leaving a bumpy polyhedron that's no
+    /// longer convex.
+    pub fn extrude(mut self, height: f64) -> Result<Self, OpError> {
+        if self.operations.is_empty() {
+            Err(OpError::NoSeedSet)
+        } else if !(height > 0.0 && height.is_finite()) {
+            Err(OpError::InvalidHeight)
+        } else {
+            self.operations.push(ConwayOperation::Extrude(height));
+            Ok(self)
+        }
+    }
+
+    /// Shrink each face `ratio` of the way towards its centroid inside a ring of
+    /// quadrilaterals. The ratio must be more than 0 and less than 1.
+    pub fn inset(mut self, ratio: f64) -> Result<Self, OpError> {
+        if self.operations.is_empty() {
+            Err(OpError::NoSeedSet)
+        } else if !(ratio > 0.0 && ratio < 1.0) {
+            Err(OpError::InvalidInset)
+        } else {
+            self.operations.push(ConwayOperation::Inset(ratio));
             Ok(self)
         }
     }
@@ -385,96 +826,210 @@ impl ConwayDescription {
     }
 }
 
-pub trait VertexAndFaceOps {
-    fn vertices_and_faces(&self) -> (&[Point3<f64>], &[Vec<usize>]);
+pub trait VertexAndFaceOps<S: BaseFloat = f64> {
+    fn vertices_and_faces(&self) -> (&[Point3<S>], &Faces);
+
+    /// Each face with its vertices, centroid and normal worked out as they're asked for.
+    fn iter_faces(&self) -> FaceIter<'_, S> {
+        let (vertices, faces) = self.vertices_and_faces();
+        FaceIter::new(vertices, faces)
+    }
+
+    /// Each edge once, in the same order as `edges`.
+    fn iter_edges(&self) -> EdgeIter<'_, S> {
+        let (vertices, faces) = self.vertices_and_faces();
+        EdgeIter::new(vertices, faces)
+    }
+
+    fn iter_vertices(&self) -> VertexIter<'_, S> {
+        VertexIter::new(self.vertices_and_faces().0)
+    }
+
+    /// The faces each vertex is part of worked out in one pass over the faces.
+    fn incidence(&self) -> IncidenceTable {
+        let (points, faces) = self.vertices_and_faces();
+        IncidenceTable::new(points.len(), faces)
+    }
 
     /// Return the index for each vertex attached with the indexes for each face a
-    /// vertex is part of.
+    /// vertex is part of. See `incidence` to avoid copying.
     fn faces_per_vertex(&self) -> Vec<(usize, Vec<usize>)> {
-        let (points, faces) = self.vertices_and_faces();
+        self.incidence()
+            .iter()
+            .map(|(i, f_v)| (i, f_v.to_vec()))
+            .collect()
+    }
+
+    /// Each edge once as the pair of vertex indexes it joins, lowest index first. Edges
+    /// come in the order they are first reached going around the faces.
+    fn edges(&self) -> Vec<(usize, usize)> {
+        self.iter_edges().map(|edge| edge.vertex_indexes()).collect()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.edges().len()
+    }
+
+    /// The faces with a vertex further than `epsilon` from the plane best fitting the face
+    /// along with how far the furthest vertex is.
+    fn non_planar_faces(&self, epsilon: S) -> Vec<(usize, S)> {
+        let (vertices, faces) = self.vertices_and_faces();
 
-        points
+        faces
             .iter()
             .enumerate()
-            .map(|(i, _p)| {
-                let f_v: Vec<usize> = faces
-                    .iter()
-                    .enumerate()
-                    .fold(Vec::new(), |mut v, (face_index, face_indices)| -> Vec<usize> {
-                        v.extend(
-                            face_indices
-                                .iter()
-                                .filter(|x| **x == i)
-                                .map(|_| face_index)
-                        );
-
-                        v
-                    });
-                
-                (i, f_v)
+            .filter(|(_, face)| face.len() > 3)
+            .map(|(f_index, face)| {
+                let points: Vec<Point3<S>> = face.iter().map(|v| vertices[*v]).collect();
+                (f_index, geop::plane_deviation(&points))
             })
+            .filter(|(_, deviation)| *deviation > epsilon)
             .collect()
-    }    
+    }
+
+    /// How many faces there are with each number of sides.
+    fn face_census(&self) -> BTreeMap<usize, usize> {
+        let (_, faces) = self.vertices_and_faces();
+
+        faces
+            .iter()
+            .fold(BTreeMap::new(), |mut census, face| {
+                *census.entry(face.len()).or_insert(0) += 1;
+                census
+            })
+    }
+
+    /// Neighbours of every face and vertex. Use this when making many queries.
+    fn adjacency(&self) -> Adjacency {
+        let (vertices, faces) = self.vertices_and_faces();
+        Adjacency::new(&geop::HalfEdgeMesh::new(vertices.to_vec(), faces))
+    }
+
+    /// See `Adjacency::face_neighbors`.
+    fn face_neighbors(&self, face_index: usize) -> Vec<usize> {
+        self.adjacency().face_neighbors(face_index).to_vec()
+    }
+
+    /// See `Adjacency::vertex_neighbors`.
+    fn vertex_neighbors(&self, vertex_index: usize) -> Vec<usize> {
+        self.adjacency().vertex_neighbors(vertex_index).to_vec()
+    }
 }
 
 /// Vertices and Faces. Inner state type for a `Polyhedron`. Not directly constructable.
 /// All faces are guaranteed to have three or more vertices.
+///
+/// The Conway operations are carried out in `f64`. Once built a polyhedron can be `cast` to
+/// `f32` for presenting.
 #[derive(Debug, Clone)]
-pub struct VtFc {
-    center: Point3<f64>,
-    radius: f64,
-    vertices: Vec<Point3<f64>>,
-    faces: Vec<Vec<usize>>,
+pub struct VtFc<S: BaseFloat = f64> {
+    center: Point3<S>,
+    vertices: Vec<Point3<S>>,
+    faces: Faces,
 }
 
 /// Add the centroid for each face.
 #[derive(Debug, Clone)]
-pub struct VtFcCt {
-    center: Point3<f64>,
-    radius: f64,
-    vertices: Vec<Point3<f64>>,
-    faces: Vec<Vec<usize>>,
-    centroids: Vec<Point3<f64>>,
+pub struct VtFcCt<S: BaseFloat = f64> {
+    center: Point3<S>,
+    vertices: Vec<Point3<S>>,
+    faces: Faces,
+    centroids: Vec<Point3<S>>,
 }
 
 /// Add the normals. Vector of normals and faces are parallel.
 #[derive(Debug, Clone)]
-pub struct VtFcNm {
-    center: Point3<f64>,
-    radius: f64,
-    vertices: Vec<Point3<f64>>,
-    faces: Vec<Vec<usize>>,
-    normals: Vec<Vector3<f64>>,
+pub struct VtFcNm<S: BaseFloat = f64> {
+    center: Point3<S>,
+    vertices: Vec<Point3<S>>,
+    faces: Faces,
+    normals: Vec<Vector3<S>>,
 }
 
+pub type VtFc32 = VtFc<f32>;
+pub type VtFcCt32 = VtFcCt<f32>;
+pub type VtFcNm32 = VtFcNm<f32>;
+
 /// The faces, vertices and edges that make up a polyhedron.
 #[derive(Debug, Clone)]
 pub struct Polyhedron<T> {
     data: T,
 }
 
-impl Polyhedron<VtFc> {
+impl<S: BaseFloat + Send + Sync> Polyhedron<VtFc<S>> {
+    /// Every face must have at least three vertices, only refer to vertices that exist and
+    /// be wound the same way as its neighbours so no two faces go along an edge in the same
+    /// direction. The faces must also close up, each edge going back the other way along
+    /// another face, and every vertex must be part of a face.
     pub fn new(
-        center: Point3<f64>, radius: f64, vertices: &[Point3<f64>], faces: &[&[usize]],
+        center: Point3<S>, vertices: &[Point3<S>], faces: &[&[usize]],
+    ) -> Result<Self, PolyhedronError> {
+        let mut edges: HashSet<(usize, usize)> = HashSet::new();
+        for (f, face) in faces.iter().enumerate() {
+            if face.len() < 3 {
+                return Err(PolyhedronError::FaceTooSmall(f));
+            }
+            if let Some(v) = face.iter().find(|v| **v >= vertices.len()) {
+                return Err(PolyhedronError::InvalidIndex(f, *v));
+            }
+            for i in 0..face.len() {
+                let edge = (face[i], face[(i + 1) % face.len()]);
+                if edge.0 == edge.1 || !edges.insert(edge) {
+                    return Err(PolyhedronError::InconsistentWinding(edge.0, edge.1));
+                }
+            }
+        }
+
+        let mut used = vec![false; vertices.len()];
+        for face in faces.iter() {
+            for i in 0..face.len() {
+                let (v1, v2) = (face[i], face[(i + 1) % face.len()]);
+                if !edges.contains(&(v2, v1)) {
+                    return Err(PolyhedronError::NotClosed(v1, v2));
+                }
+                used[v1] = true;
+            }
+        }
+        if let Some(v) = used.iter().position(|used| !used) {
+            return Err(PolyhedronError::UnusedVertex(v));
+        }
+
+        Ok(Polyhedron::unchecked(center, vertices, faces))
+    }
+
+    /// Same as `new` without checking the faces. For faces already known to be good.
+    pub fn unchecked(
+        center: Point3<S>, vertices: &[Point3<S>], faces: &[&[usize]],
     ) -> Self {
+        let count = faces.iter().map(|face| face.len()).sum();
+        let mut flat = Faces::with_capacity(faces.len(), count);
+        flat.extend(faces);
+
         Polyhedron {
             data: VtFc {
                 center,
-                radius,
                 vertices: vertices.to_owned(),
-                faces: faces
-                    .iter()
-                    .map(|f| f.to_vec())
-                    .collect(),
+                faces: flat,
             },
         }
     }
 
+    /// The sphere about the center reaching out to the furthest vertex. Worked out when
+    /// asked for since the vertices of shapes that aren't round are nowhere near it.
+    pub fn bounding_sphere(&self) -> (Point3<S>, S) {
+        let center = self.data.center;
+        let radius = self.data.vertices
+            .iter()
+            .map(|vertex| vertex.distance(center))
+            .fold(S::zero(), S::max);
+        (center, radius)
+    }
+
     /// Calculate the normal for each face and emit a `Polyhedron` with that information
     /// saved consuming self.
-    pub fn normalize(self) -> Polyhedron<VtFcNm> {
-        let normals: Vec<Vector3<f64>> = self.data.faces
-            .iter()
+    pub fn normalize(self) -> Polyhedron<VtFcNm<S>> {
+        let normals: Vec<Vector3<S>> = parallel::each_index(self.data.faces.len())
+            .map(|f_index| &self.data.faces[f_index])
             .map(|v| geop::triangle_normal(
                 self.data.vertices[v[0]],
                 self.data.vertices[v[1]],
@@ -485,7 +1040,6 @@ impl Polyhedron<VtFc> {
         Polyhedron {
             data: VtFcNm {
                 center: self.data.center,
-                radius: self.data.radius,
                 vertices: self.data.vertices,
                 faces: self.data.faces,
                 normals,
@@ -495,13 +1049,13 @@ impl Polyhedron<VtFc> {
 
     /// Calculate the centroid for each face and emit a `Polyhedron` with that information
     /// saved consuming self.
-    pub fn centroidize(self) -> Polyhedron<VtFcCt> {
-        let centroids: Vec<Point3<f64>> = self.data.faces
-            .iter()
+    pub fn centroidize(self) -> Polyhedron<VtFcCt<S>> {
+        let centroids: Vec<Point3<S>> = parallel::each_index(self.data.faces.len())
+            .map(|f_index| &self.data.faces[f_index])
             .map(|v| v
                  .iter()
                  .map(|i| self.data.vertices[*i])
-                 .collect::<Vec<Point3<f64>>>()
+                 .collect::<Vec<Point3<S>>>()
             )
             .map(|v| geop::convex_planar_polygon_centroid(&v))
             .collect();
@@ -509,49 +1063,126 @@ impl Polyhedron<VtFc> {
         Polyhedron {
             data: VtFcCt {
                 center: self.data.center,
-                radius: self.data.radius,
                 vertices: self.data.vertices,
                 faces: self.data.faces,
                 centroids: centroids,
             }
         }
     }
+
+    /// Convert to another float type such as `f32`. `None` if a coordinate doesn't fit.
+    pub fn cast<T: BaseFloat>(&self) -> Option<Polyhedron<VtFc<T>>> {
+        Some(Polyhedron {
+            data: VtFc {
+                center: self.data.center.cast()?,
+                vertices: cast_points(&self.data.vertices)?,
+                faces: self.data.faces.clone(),
+            }
+        })
+    }
+}
+
+fn cast_points<S: BaseFloat, T: BaseFloat>(points: &[Point3<S>]) -> Option<Vec<Point3<T>>> {
+    points.iter().map(|point| point.cast()).collect()
+}
+
+impl Polyhedron<VtFc> {
+    /// Merge vertices closer together than `epsilon` and point the faces at the merged
+    /// vertices. A face left with the same vertex twice in a row has the repeat removed.
+    /// Faces left with less than three vertices are dropped.
+    pub fn dedup_vertices(self, epsilon: f64) -> Self {
+        let (vertices, mapping) = geop::weld_vertices(&self.data.vertices, epsilon);
+        let faces = self.data.faces
+            .iter()
+            .map(|face| {
+                let mut face: Vec<usize> = face.iter().map(|v| mapping[*v]).collect();
+                face.dedup();
+                if face.len() > 1 && face.first() == face.last() {
+                    face.pop();
+                }
+                face
+            })
+            .filter(|face| face.len() >= 3)
+            .collect();
+
+        Polyhedron {
+            data: VtFc {
+                center: self.data.center,
+                vertices,
+                faces,
+            }
+        }
+    }
+
+    /// Scale about the center so the furthest vertex is `radius` away from it.
+    pub fn scale_to_radius(mut self, radius: f64) -> Self {
+        let (center, furthest) = self.bounding_sphere();
+        if furthest > 0.0 {
+            let scale = radius / furthest;
+            for vertex in self.data.vertices.iter_mut() {
+                *vertex = center + (*vertex - center) * scale;
+            }
+        }
+        self
+    }
+}
+
+impl<S: BaseFloat> From<&Polyhedron<VtFc<S>>> for geop::HalfEdgeMesh<S> {
+    fn from(polyhedron: &Polyhedron<VtFc<S>>) -> Self {
+        geop::HalfEdgeMesh::new(polyhedron.data.vertices.clone(), &polyhedron.data.faces)
+    }
 }
 
-impl VertexAndFaceOps for Polyhedron<VtFc> {
-    fn vertices_and_faces(&self) -> (&[Point3<f64>], &[Vec<usize>]) {
+impl<S: BaseFloat> VertexAndFaceOps<S> for Polyhedron<VtFc<S>> {
+    fn vertices_and_faces(&self) -> (&[Point3<S>], &Faces) {
         (&self.data.vertices, &self.data.faces)
     }
 }
 
-impl Polyhedron<VtFcNm> {
-    pub fn faces(&self) -> impl Iterator<Item = planar::Polygon<f64>> + '_ {
+impl<S: BaseFloat> Polyhedron<VtFcNm<S>> {
+    /// Copy each face out as a polygon with its saved normal. See `iter_faces` to go over
+    /// the faces without copying.
+    pub fn faces(&self) -> impl Iterator<Item = planar::Polygon<S>> + '_ {
         self.data.faces
             .iter()
             .map(move |vertex_indexes| {
                 vertex_indexes
                     .iter()
                     .map(move |i| self.data.vertices[*i].clone())
-                    .collect::<Vec<Point3<f64>>>()
+                    .collect::<Vec<Point3<S>>>()
             })
             .enumerate()
             .map(move |(i, v)| planar::Polygon::new(&v, self.data.normals[i].clone()))
     }
+
+    /// Convert to another float type such as `f32`. `None` if a coordinate doesn't fit.
+    pub fn cast<T: BaseFloat>(&self) -> Option<Polyhedron<VtFcNm<T>>> {
+        Some(Polyhedron {
+            data: VtFcNm {
+                center: self.data.center.cast()?,
+                vertices: cast_points(&self.data.vertices)?,
+                faces: self.data.faces.clone(),
+                normals: self.data.normals
+                    .iter()
+                    .map(|normal| normal.cast())
+                    .collect::<Option<Vec<Vector3<T>>>>()?,
+            }
+        })
+    }
 }
 
-impl VertexAndFaceOps for Polyhedron<VtFcNm> {
-    fn vertices_and_faces(&self) -> (&[Point3<f64>], &[Vec<usize>]) {
+impl<S: BaseFloat> VertexAndFaceOps<S> for Polyhedron<VtFcNm<S>> {
+    fn vertices_and_faces(&self) -> (&[Point3<S>], &Faces) {
         (&self.data.vertices, &self.data.faces)
     }
 }
 
-impl Polyhedron<VtFcCt> {
+impl<S: BaseFloat> Polyhedron<VtFcCt<S>> {
     /// Strip out the centroid information.
-    pub fn downgrade(self) -> Polyhedron<VtFc> {
+    pub fn downgrade(self) -> Polyhedron<VtFc<S>> {
         Polyhedron {
             data: VtFc {
                 center: self.data.center,
-                radius: self.data.radius,
                 vertices: self.data.vertices,
                 faces: self.data.faces,
             }
@@ -559,8 +1190,8 @@ impl Polyhedron<VtFcCt> {
     }
 }
 
-impl VertexAndFaceOps for Polyhedron<VtFcCt> {
-    fn vertices_and_faces(&self) -> (&[Point3<f64>], &[Vec<usize>]) {
+impl<S: BaseFloat> VertexAndFaceOps<S> for Polyhedron<VtFcCt<S>> {
+    fn vertices_and_faces(&self) -> (&[Point3<S>], &Faces) {
         (&self.data.vertices, &self.data.faces)
     }
 }
@@ -570,6 +1201,12 @@ pub enum OpError {
     NoOperations,
     AlreadyHasSeed,
     NoSeedSet,
+    InvalidRatio,
+    ZeroFrequency,
+    NotTriangulated,
+    InvalidStrength,
+    InvalidHeight,
+    InvalidInset,
 }
 
 impl fmt::Display for OpError {
@@ -578,6 +1215,12 @@ impl fmt::Display for OpError {
             OpError::NoOperations => "No Conway operations set.",
             OpError::AlreadyHasSeed => "Seed already present.",
             OpError::NoSeedSet => "No seed has been set to run Conway operations on.",
+            OpError::InvalidRatio => "Ratio must be more than 0 and less than 0.5.",
+            OpError::ZeroFrequency => "Subdivision frequency must be more than 0.",
+            OpError::NotTriangulated => "Seed must only have triangle faces.",
+            OpError::InvalidStrength => "Strength must be from 0 to 1.",
+            OpError::InvalidHeight => "Height must be more than 0.",
+            OpError::InvalidInset => "Inset must be more than 0 and less than 1.",
         })
     }
 }
@@ -587,3 +1230,639 @@ impl error::Error for OpError {
         "Error adding Conway operation."
     }
 }
+
+/// Faces that don't make a polyhedron given to `Polyhedron::new`. Faces and vertices are
+/// numbered by their position starting at zero.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PolyhedronError {
+    FaceTooSmall(usize),
+    InvalidIndex(usize, usize),
+    InconsistentWinding(usize, usize),
+    NotClosed(usize, usize),
+    UnusedVertex(usize),
+}
+
+impl fmt::Display for PolyhedronError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PolyhedronError::FaceTooSmall(face) => {
+                write!(f, "Face {} has less than three vertices.", face)
+            },
+            PolyhedronError::InvalidIndex(face, v) => {
+                write!(f, "Face {} refers to missing vertex {}.", face, v)
+            },
+            PolyhedronError::InconsistentWinding(v1, v2) => write!(
+                f, "Edge from vertex {} to {} is shared by faces wound the same way.", v1, v2,
+            ),
+            PolyhedronError::NotClosed(v1, v2) => {
+                write!(f, "Edge from vertex {} to {} is only part of one face.", v1, v2)
+            },
+            PolyhedronError::UnusedVertex(v) => {
+                write!(f, "Vertex {} isn't part of any face.", v)
+            },
+        }
+    }
+}
+
+impl error::Error for PolyhedronError {
+    fn description(&self) -> &str {
+        "Error building a polyhedron."
+    }
+}
+
+/// Problems reading Conway notation. Positions are byte offsets into the notation.
+#[derive(Debug, Copy, Clone)]
+pub enum NotationError {
+    Empty,
+    UnknownSeed(char, usize),
+    NoCustomSeed(usize),
+    UnknownOperator(char, usize),
+    UnexpectedDigit(char, usize),
+    InvalidDegree(usize),
+    Op(OpError),
+}
+
+impl fmt::Display for NotationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NotationError::Empty => write!(f, "Notation is empty."),
+            NotationError::UnknownSeed(c, p) => {
+                write!(f, "Unknown seed '{}' at {}. Expected one of T, C, O, D or I.", c, p)
+            },
+            NotationError::NoCustomSeed(p) => {
+                write!(f, "The custom seed 'X' at {} has to be given with the notation.", p)
+            },
+            NotationError::UnknownOperator(c, p) => {
+                write!(f, "Unknown Conway operator '{}' at {}.", c, p)
+            },
+            NotationError::UnexpectedDigit(c, p) => {
+                write!(f, "Unexpected digit '{}' at {}. Only k and t take a degree.", c, p)
+            },
+            NotationError::InvalidDegree(p) => write!(f, "Degree at {} is too large.", p),
+            NotationError::Op(e) => e.fmt(f),
+        }
+    }
+}
+
+impl error::Error for NotationError {
+    fn description(&self) -> &str {
+        "Error reading Conway notation."
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cgmath::prelude::*;
+
+    use super::*;
+
+    /// Vertex, edge and face counts. Also checks that every edge is shared by exactly two
+    /// faces which traverse it in opposite directions.
+    fn counts(p: &Polyhedron<VtFc>) -> (usize, usize, usize) {
+        let (vertices, faces) = p.vertices_and_faces();
+        let mut edges = HashSet::new();
+
+        for face in faces {
+            for i in 0..face.len() {
+                let edge = (face[i], face[(i + 1) % face.len()]);
+                assert!(edges.insert(edge), "Edge {:?} traversed twice.", edge);
+            }
+        }
+        for (v1, v2) in edges.iter() {
+            assert!(edges.contains(&(*v2, *v1)), "Edge {:?} is open.", (v1, v2));
+        }
+
+        (vertices.len(), edges.len() / 2, faces.len())
+    }
+
+    fn edge_lengths(p: &Polyhedron<VtFc>) -> Vec<f64> {
+        let (vertices, _) = p.vertices_and_faces();
+
+        p.edges()
+            .into_iter()
+            .map(|(v1, v2)| vertices[v1].distance(vertices[v2]))
+            .collect()
+    }
+
+    #[test]
+    fn edges_deduplicated() {
+        let cube = Specification::parse("C").unwrap().produce();
+        let edges = cube.edges();
+
+        assert!(cube.edge_count() == 12);
+        assert!(edges.iter().all(|(v1, v2)| v1 < v2));
+        assert!(edges.iter().collect::<HashSet<_>>().len() == 12);
+        assert!(Specification::parse("tI").unwrap().produce().edge_count() == 90);
+    }
+
+    #[test]
+    fn dedup_split_vertices() {
+        // Give every face its own copy of its vertices, nudged slightly.
+        let cube = Specification::parse("C").unwrap().produce();
+        let (vertices, faces) = cube.vertices_and_faces();
+        let mut split_vertices: Vec<Point3<f64>> = Vec::new();
+        let mut split_faces: Vec<Vec<usize>> = Vec::new();
+        for face in faces {
+            split_faces.push(face.iter().map(|v| {
+                split_vertices.push(vertices[*v] + Vector3::new(1e-12, 0.0, 0.0));
+                split_vertices.len() - 1
+            }).collect());
+        }
+        let split_faces: Vec<&[usize]> = split_faces.iter().map(|f| f.as_slice()).collect();
+        // The split faces don't share edges so aren't closed until welded.
+        let split = Polyhedron::unchecked(Point3::origin(), &split_vertices, &split_faces);
+        assert!(split.vertices_and_faces().0.len() == 24);
+
+        let welded = split.dedup_vertices(1e-9);
+        assert!(counts(&welded) == (8, 12, 6));
+    }
+
+    #[test]
+    fn rejects_bad_faces() {
+        let vertices = [
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(0.0, 1.0, 0.0),
+            Point3::new(0.0, 0.0, 1.0),
+        ];
+        let new = |faces: &[&[usize]]| Polyhedron::new(Point3::origin(), &vertices, faces);
+
+        assert!(new(&[&[0, 2, 1], &[0, 1, 3], &[1, 2, 3], &[0, 3, 2]]).is_ok());
+        assert!(new(&[&[0, 2, 1], &[0, 1]]).unwrap_err() == PolyhedronError::FaceTooSmall(1));
+        assert!(new(&[&[0, 2, 4]]).unwrap_err() == PolyhedronError::InvalidIndex(0, 4));
+        assert!(new(&[&[0, 2, 1], &[0, 2, 3]]).unwrap_err()
+            == PolyhedronError::InconsistentWinding(0, 2));
+        assert!(new(&[&[0, 2, 1], &[0, 1, 3], &[1, 2, 3]]).unwrap_err()
+            == PolyhedronError::NotClosed(0, 2));
+    }
+
+    #[test]
+    fn open_mesh_cant_seed() {
+        // A lone triangle would otherwise panic linking up its half-edges in `produce`.
+        let vertices = [
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(0.0, 1.0, 0.0),
+            Point3::new(0.0, 0.0, 1.0),
+            Point3::new(1.0, 1.0, 1.0),
+        ];
+        let triangle = Polyhedron::new(Point3::origin(), &vertices[..3], &[&[0, 1, 2]]);
+        assert!(triangle.unwrap_err() == PolyhedronError::NotClosed(0, 1));
+
+        // A tetrahedron with a vertex left over.
+        let spare = [
+            Point3::new(1.0, 1.0, 1.0),
+            Point3::new(1.0, -1.0, -1.0),
+            Point3::new(-1.0, 1.0, -1.0),
+            Point3::new(-1.0, -1.0, 1.0),
+            Point3::new(2.0, 2.0, 2.0),
+        ];
+        let faces: [&[usize]; 4] = [&[0, 3, 1], &[0, 2, 3], &[0, 1, 2], &[1, 3, 2]];
+        let tetrahedron = Polyhedron::new(Point3::origin(), &spare, &faces);
+        assert!(tetrahedron.unwrap_err() == PolyhedronError::UnusedVertex(4));
+
+        let seed = Polyhedron::new(Point3::origin(), &spare[..4], &faces).unwrap();
+        let kis = ConwayDescription::new()
+            .seed(&seed).unwrap()
+            .kis().unwrap()
+            .emit().unwrap()
+            .produce();
+        assert!(counts(&kis) == (8, 18, 12));
+    }
+
+    #[test]
+    fn scaled_to_radius() {
+        let p = Specification::parse("kD").unwrap().produce().scale_to_radius(2.0);
+        let (vertices, _) = p.vertices_and_faces();
+        let furthest = vertices
+            .iter()
+            .map(|v| v.distance(p.data.center))
+            .fold(0.0, f64::max);
+
+        assert!((furthest - 2.0).abs() < 1e-9);
+        assert!((p.bounding_sphere().1 - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn torus_keeps_its_hole() {
+        // Eight quads around the ring by four around the tube.
+        let (ring, tube) = (8, 4);
+        let mut vertices: Vec<Point3<f64>> = Vec::new();
+        let mut faces: Vec<Vec<usize>> = Vec::new();
+        for i in 0..ring {
+            let a = i as f64 * 2.0 * std::f64::consts::PI / ring as f64;
+            for j in 0..tube {
+                let b = j as f64 * 2.0 * std::f64::consts::PI / tube as f64;
+                let r = 2.0 + 0.5 * b.cos();
+                vertices.push(Point3::new(r * a.cos(), r * a.sin(), 0.5 * b.sin()));
+                let at = |i: usize, j: usize| (i % ring) * tube + j % tube;
+                faces.push(vec![at(i, j), at(i + 1, j), at(i + 1, j + 1), at(i, j + 1)]);
+            }
+        }
+        let torus = CustomSeed::new(vertices, faces).unwrap();
+        let (center, radius) = torus.polyhedron().bounding_sphere();
+        assert!(center == Point3::origin());
+        assert!((radius - 2.5).abs() < 1e-9);
+
+        let dual = |placement| ConwayDescription::new()
+            .seed(&torus).unwrap()
+            .dual_centered(placement).unwrap()
+            .emit().unwrap()
+            .produce();
+        let from_axis = |p: &Polyhedron<VtFc>| -> Vec<f64> {
+            p.vertices_and_faces().0.iter().map(|v| (v.x * v.x + v.y * v.y).sqrt()).collect()
+        };
+
+        // Pushed out onto the sphere the inside of the ring is thrown to the outside.
+        let lifted = dual(DualCenter::Circumsphere);
+        assert!(from_axis(&lifted).iter().all(|d| *d > 2.0));
+        assert!(lifted.vertices_and_faces().0.iter().all(|v| {
+            (v.distance(Point3::origin()) - 2.5).abs() < 1e-9
+        }));
+
+        // Left in the faces they stay around the tube.
+        let centroids = dual(DualCenter::Centroid);
+        assert!(from_axis(&centroids).iter().all(|d| *d > 1.5 && *d < 2.5));
+        assert!(counts(&centroids) == (32, 64, 32));
+
+        let kis = ConwayDescription::new()
+            .seed(&torus).unwrap()
+            .kis_lifted(None, Lift::Flat).unwrap()
+            .emit().unwrap()
+            .produce();
+        assert!(kis.non_planar_faces(1e-9).is_empty());
+        assert!(from_axis(&kis).iter().all(|d| *d > 1.4));
+    }
+
+    #[test]
+    fn dual_centers() {
+        let dual = |placement| ConwayDescription::from_notation("kC").unwrap()
+            .dual_centered(placement).unwrap()
+            .emit().unwrap()
+            .produce();
+        let kis = Specification::parse("kC").unwrap().produce();
+        let (kis_vertices, kis_faces) = kis.vertices_and_faces();
+
+        // Each tangent point is on its face and straight out from the center.
+        let tangent = dual(DualCenter::FacePlaneTangent);
+        for (point, face) in tangent.vertices_and_faces().0.iter().zip(kis_faces.iter()) {
+            let along = kis_vertices[face[0]] - point;
+            assert!(along.dot(point.to_vec()).abs() < 1e-9);
+        }
+
+        // The kis triangles lean so the tangent points aren't their centroids.
+        let centroid = dual(DualCenter::Centroid);
+        let moved = tangent.vertices_and_faces().0
+            .iter()
+            .zip(centroid.vertices_and_faces().0.iter())
+            .all(|(t, c)| t.distance(*c) > 1e-3);
+        assert!(moved);
+        assert!(counts(&tangent) == counts(&centroid));
+    }
+
+    #[test]
+    fn single_precision() {
+        let p = Specification::parse("tI").unwrap().produce();
+        let single: Polyhedron<VtFc32> = p.cast().unwrap();
+
+        assert!(single.face_census() == p.face_census());
+        assert!(single.edges() == p.edges());
+        for (v64, v32) in p.vertices_and_faces().0.iter().zip(single.vertices_and_faces().0) {
+            assert!((v64.distance(v32.cast().unwrap())) < 1e-6);
+        }
+
+        let normalized = single.normalize();
+        assert!(normalized.faces().all(|face| (face.normal().magnitude() - 1.0).abs() < 1e-6));
+    }
+
+    #[test]
+    fn planar_faces() {
+        for notation in ["tC", "aD", "C"].iter() {
+            let p = Specification::parse(notation).unwrap().produce();
+            assert!(p.non_planar_faces(1e-9).is_empty());
+        }
+
+        // Lifting the new vertices onto the sphere bends the faces of a gyro.
+        let bent = Specification::parse("gC").unwrap().produce().non_planar_faces(1e-9);
+        assert!(!bent.is_empty());
+        assert!(bent.iter().all(|(_, deviation)| *deviation > 1e-9));
+    }
+
+    #[test]
+    fn truncated_icosahedron_census() {
+        let census = Specification::parse("tI").unwrap().produce().face_census();
+        assert!(census.into_iter().collect::<Vec<_>>() == vec![(5, 12), (6, 20)]);
+    }
+
+    #[test]
+    fn incidence_table() {
+        let p = Specification::parse("tT").unwrap().produce();
+        let incidence = p.incidence();
+        let (vertices, faces) = p.vertices_and_faces();
+
+        for v_index in 0..vertices.len() {
+            assert!(incidence.degree(v_index) == 3);
+            assert!(incidence.vertex_faces(v_index).iter().all(|f| faces[*f].contains(&v_index)));
+        }
+        assert!(p.faces_per_vertex()[0] == (0, incidence.vertex_faces(0).to_vec()));
+    }
+
+    #[test]
+    fn goldberg_neighbors() {
+        let gp = goldberg(2, 1, 1.0).unwrap();
+        let adjacency = gp.adjacency();
+        let (_, faces) = gp.vertices_and_faces();
+
+        for (f_index, face) in faces.iter().enumerate() {
+            let neighbors = adjacency.face_neighbors(f_index);
+            assert!(neighbors.len() == face.len());
+            assert!(neighbors.iter().all(|g| adjacency.face_neighbors(*g).contains(&f_index)));
+        }
+        for v_index in 0..gp.vertices_and_faces().0.len() {
+            assert!(adjacency.vertex_neighbors(v_index).len() == 3);
+        }
+        assert!(gp.face_neighbors(0) == adjacency.face_neighbors(0));
+    }
+
+    #[test]
+    fn parse_notation() {
+        let spec = Specification::parse("dk5zD").unwrap();
+        assert!(spec.notation() == "dk5zD");
+        assert!(counts(&spec.produce()) == (80, 150, 72));
+
+        assert!(Specification::parse("t4jC").unwrap().notation() == "t4jC");
+        assert!(Specification::parse("").is_err());
+        assert!(Specification::parse("kX").is_err());
+        assert!(Specification::parse("qD").is_err());
+        assert!(Specification::parse("d5D").is_err());
+    }
+
+    #[test]
+    fn truncate_counts() {
+        let tt = ConwayDescription::new()
+            .seed(&Tetrahedron2::new(1.0)).unwrap()
+            .truncate().unwrap()
+            .emit().unwrap()
+            .produce();
+        let tc = ConwayDescription::new()
+            .seed(&Cube2::new(1.0)).unwrap()
+            .truncate().unwrap()
+            .emit().unwrap()
+            .produce();
+        let td = ConwayDescription::new()
+            .seed(&Dodecahedron2::new(1.0)).unwrap()
+            .truncate().unwrap()
+            .emit().unwrap()
+            .produce();
+
+        assert!(counts(&tt) == (12, 18, 8));
+        assert!(counts(&tc) == (24, 36, 14));
+        assert!(counts(&td) == (60, 90, 32));
+    }
+
+    #[test]
+    fn truncate_is_uniform() {
+        let tc = ConwayDescription::new()
+            .seed(&Cube2::new(1.0)).unwrap()
+            .truncate().unwrap()
+            .emit().unwrap()
+            .produce();
+
+        let lengths = edge_lengths(&tc);
+        let first = lengths[0];
+        assert!(lengths.iter().all(|l| (l - first).abs() < 1e-9));
+    }
+
+    #[test]
+    fn truncate_ratio() {
+        let tc = ConwayDescription::new()
+            .seed(&Cube2::new(1.0)).unwrap()
+            .truncate_ratio(0.1).unwrap()
+            .emit().unwrap()
+            .produce();
+
+        assert!(counts(&tc) == (24, 36, 14));
+        assert!(ConwayDescription::new()
+                .seed(&Cube2::new(1.0)).unwrap()
+                .truncate_ratio(0.5)
+                .is_err());
+    }
+
+    #[test]
+    fn goldberg_counts() {
+        for (m, n) in [(1, 0), (2, 0), (1, 1), (2, 1), (3, 2)].iter().cloned() {
+            let t = m * m + m * n + n * n;
+            let gp = goldberg(m, n, 1.0).unwrap();
+            let census: Vec<(usize, usize)> = gp.face_census().into_iter().collect();
+
+            assert!(counts(&gp) == (20 * t, 30 * t, 10 * t + 2));
+            if t == 1 {
+                assert!(census == vec![(5, 12)]);
+            } else {
+                assert!(census == vec![(5, 12), (6, 10 * (t - 1))]);
+            }
+        }
+
+        assert!(goldberg(0, 0, 1.0).is_err());
+    }
+
+    #[test]
+    fn geodesic_counts() {
+        let octahedron = Octahedron2::new(1.0);
+        assert!(counts(&geodesic((2, 0), &octahedron).unwrap()) == (18, 48, 32));
+        assert!(counts(&geodesic((1, 1), &Tetrahedron2::new(1.0)).unwrap()) == (8, 18, 12));
+        assert!(counts(&geodesic((2, 1), &Icosahedron2::new(1.0)).unwrap()) == (72, 210, 140));
+        assert!(geodesic((1, 0), &Cube2::new(1.0)).is_err());
+    }
+
+    #[test]
+    fn subdivide_counts() {
+        let gp = Specification::parse("du3I").unwrap().produce();
+        assert!(counts(&gp) == counts(&goldberg(3, 0, 1.0).unwrap()));
+        assert!(gp.face_census().into_iter().collect::<Vec<_>>() == vec![(5, 12), (6, 80)]);
+        assert!(Specification::parse("uT").unwrap().notation() == "u2T");
+        assert!(Specification::parse("u0T").is_err());
+
+        // Left flat the cube keeps its square sides.
+        let flat = ConwayDescription::new()
+            .seed(&Cube2::new(2.0)).unwrap()
+            .subdivide_lifted(3, Lift::Flat).unwrap()
+            .emit().unwrap()
+            .produce();
+        let (vertices, faces) = flat.vertices_and_faces();
+        assert!(faces.len() == 6 * 4 * 9);
+        let side = |v: &Point3<f64>| v.x.abs().max(v.y.abs()).max(v.z.abs());
+        assert!(vertices.iter().all(|v| (side(v) - side(&vertices[0])).abs() < 1e-9));
+    }
+
+    #[test]
+    fn spherize() {
+        let flat = ConwayDescription::new()
+            .seed(&Cube2::new(2.0)).unwrap()
+            .kis_lifted(None, Lift::Flat).unwrap();
+        let distances = |strength: f64| -> Vec<f64> {
+            flat.clone()
+                .spherize(strength).unwrap()
+                .emit().unwrap()
+                .produce()
+                .vertices_and_faces().0
+                .iter()
+                .map(|v| v.distance(Point3::origin()))
+                .collect()
+        };
+
+        // The corners are already on the sphere and the pyramid tips start on the faces.
+        let radius = 3f64.sqrt();
+        let tips = |d: &[f64]| d[8..].iter().all(|d| (d - radius).abs() < 1e-9);
+        assert!(!tips(&distances(0.0)));
+        assert!(tips(&distances(1.0)));
+        assert!(distances(0.5)[8..].iter().all(|d| (d - (1.0 + radius) / 2.0).abs() < 1e-9));
+        assert!(distances(0.5)[..8].iter().all(|d| (d - radius).abs() < 1e-9));
+
+        assert!(flat.clone().spherize(1.5).is_err());
+        assert!(Specification::parse("SkC").unwrap().notation() == "SkC");
+    }
+
+    #[test]
+    fn extrude() {
+        let extruded = ConwayDescription::new()
+            .seed(&Cube2::new(2.0)).unwrap()
+            .extrude(0.5).unwrap()
+            .emit().unwrap()
+            .produce();
+        assert!(counts(&extruded) == (8 + 24, 60, 6 + 24));
+
+        // The corners stay and each side is raised half a unit off the cube.
+        let (vertices, _) = extruded.vertices_and_faces();
+        let side = |v: &Point3<f64>| v.x.abs().max(v.y.abs()).max(v.z.abs());
+        let at = |d: f64| vertices.iter().filter(|v| (side(v) - d).abs() < 1e-9).count();
+        assert!(at(1.0) == 8 && at(1.5) == 24);
+
+        assert!(ConwayDescription::from_notation("xC").unwrap().extrude(0.0).is_err());
+    }
+
+    #[test]
+    fn inset() {
+        let inset = ConwayDescription::new()
+            .seed(&Cube2::new(2.0)).unwrap()
+            .inset(0.5).unwrap()
+            .emit().unwrap()
+            .produce();
+        assert!(counts(&inset) == (8 + 24, 60, 6 + 24));
+
+        // Everything stays on the sides of the cube with the inner squares half the size.
+        let (vertices, _) = inset.vertices_and_faces();
+        let side = |v: &Point3<f64>| v.x.abs().max(v.y.abs()).max(v.z.abs());
+        assert!(vertices.iter().all(|v| (side(v) - 1.0).abs() < 1e-9));
+        let inner = vertices.iter().filter(|v| v.distance(Point3::origin()) < 1.3).count();
+        assert!(inner == 24);
+
+        let cube = ConwayDescription::from_notation("C").unwrap();
+        assert!(cube.clone().inset(0.0).is_err());
+        assert!(cube.inset(1.0).is_err());
+    }
+
+    #[test]
+    fn predicted_counts_match() {
+        let notations = [
+            "D", "dC", "kT", "k4C", "k5aD", "tI", "t3C", "t4aC", "aO", "gC", "sT", "eC", "bT",
+            "oC", "jD", "cI", "wT", "pC", "mO", "nD", "zC", "lT", "LC", "wwD", "dk5zD", "t4jC",
+            "u3I", "uC", "u1aC", "du4O", "SkC", "xC", "dxI", "iD",
+        ];
+
+        for notation in notations.iter() {
+            let spec = Specification::parse(notation).unwrap();
+            assert!(spec.predicted_counts() == Some(counts(&spec.produce())), "{}", notation);
+        }
+
+        // Which faces are left as pentagons after k5 depends on the actual topology.
+        assert!(Specification::parse("k5k3aD").unwrap().predicted_counts().is_some());
+        assert!(Specification::parse("t3k5aD").unwrap().predicted_counts().is_none());
+    }
+
+    #[test]
+    fn extend_specification() {
+        let base = Specification::parse("kD").unwrap();
+        let more = ConwayDescription::from_notation("dtC").unwrap();
+        let spec = base.clone().extend(&more).unwrap();
+        assert!(spec.notation() == "dtkD");
+
+        let produced = spec.produce_from(&base, base.produce()).unwrap();
+        assert!(counts(&produced) == counts(&spec.produce()));
+        assert!(base.produce_from(&spec, spec.produce()).is_none());
+        let other = Specification::parse("kI").unwrap();
+        assert!(spec.produce_from(&other, base.produce()).is_none());
+    }
+
+    #[test]
+    fn operations_follow_translated_seed() {
+        let offset = Vector3::new(3.0, -2.0, 5.0);
+        let cube = Specification::parse("C").unwrap().produce();
+        let moved = Polyhedron {
+            data: VtFc {
+                center: cube.data.center + offset,
+                vertices: cube.data.vertices.iter().map(|v| v + offset).collect(),
+                faces: cube.data.faces.clone(),
+            },
+        };
+
+        for notation in ["dC", "kC", "aC", "tC", "cC", "gC", "pC", "wC", "LC", "lC", "xC"] {
+            let ops = ConwayDescription::from_notation(notation).unwrap();
+            let at_origin = ConwayDescription::new()
+                .seed(&cube).unwrap()
+                .compose(&ops).unwrap()
+                .emit().unwrap()
+                .produce();
+            let translated = ConwayDescription::new()
+                .seed(&moved).unwrap()
+                .compose(&ops).unwrap()
+                .emit().unwrap()
+                .produce();
+
+            let (expected, _) = at_origin.vertices_and_faces();
+            let (vertices, _) = translated.vertices_and_faces();
+            assert!(vertices.len() == expected.len());
+            for vertex in vertices {
+                assert!(
+                    expected.iter().any(|e| e.distance(vertex - offset) < 1e-9),
+                    "{} moved {:?} away from the seed.", notation, vertex,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn custom_seed_notation() {
+        let seed = Specification::parse("tC").unwrap().produce();
+        let spec = ConwayDescription::new()
+            .seed(&seed).unwrap()
+            .kis().unwrap()
+            .emit().unwrap();
+        assert!(spec.notation() == "kX");
+
+        let read = Specification::parse_with_seed(spec.notation(), &seed).unwrap();
+        assert!(counts(&read.produce()) == counts(&spec.produce()));
+        match Specification::parse(spec.notation()) {
+            Err(NotationError::NoCustomSeed(1)) => (),
+            other => panic!("Unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn produce_from_compares_parameters() {
+        let spec = Specification::parse("dtC").unwrap();
+        let base = ConwayDescription::from(Specification::parse("C").unwrap())
+            .truncate_ratio(0.2).unwrap()
+            .emit().unwrap();
+        assert!(base.notation() == "tC");
+        assert!(spec.produce_from(&base, base.produce()).is_none());
+
+        let base = Specification::parse("tC").unwrap();
+        assert!(spec.produce_from(&base, base.produce()).is_some());
+
+        let spec = Specification::parse("kdC").unwrap();
+        let base = ConwayDescription::from(Specification::parse("C").unwrap())
+            .dual_centered(DualCenter::Centroid).unwrap()
+            .emit().unwrap();
+        assert!(spec.produce_from(&base, base.produce()).is_none());
+    }
+}
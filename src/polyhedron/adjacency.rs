@@ -0,0 +1,74 @@
+//! Which faces and vertices are next to each other. Worked out once so that walking across
+//! a polyhedron, such as over the tiles of a Goldberg polyhedron, doesn't have to search
+//! through every face at each step.
+//!
+//! `IncidenceTable` only needs the faces to list their vertices. `Adjacency` needs the
+//! faces to form a closed mesh.
+use cgmath::BaseFloat;
+
+use crate::geop::HalfEdgeMesh;
+use super::Faces;
+
+#[derive(Debug, Clone)]
+pub struct Adjacency {
+    faces: Vec<Vec<usize>>,
+    vertices: Vec<Vec<usize>>,
+}
+
+impl Adjacency {
+    pub (in crate::polyhedron) fn new<S: BaseFloat>(mesh: &HalfEdgeMesh<S>) -> Self {
+        let faces = (0..mesh.face_count())
+            .map(|f| mesh.face_half_edges(f).map(|h| mesh.face(mesh.twin(h))).collect())
+            .collect();
+        let vertices = (0..mesh.vertex_count())
+            .map(|v| mesh.vertex_half_edges(v).map(|h| mesh.destination(h)).collect())
+            .collect();
+
+        Adjacency { faces, vertices }
+    }
+
+    /// The faces across each edge of the face in the same order as the face vertices. The
+    /// first is across the edge from the first vertex to the second.
+    pub fn face_neighbors(&self, face_index: usize) -> &[usize] {
+        &self.faces[face_index]
+    }
+
+    /// The vertices joined to the vertex by an edge going anticlockwise around it seen
+    /// from outside.
+    pub fn vertex_neighbors(&self, vertex_index: usize) -> &[usize] {
+        &self.vertices[vertex_index]
+    }
+}
+
+/// The faces each vertex is part of.
+#[derive(Debug, Clone)]
+pub struct IncidenceTable {
+    faces: Vec<Vec<usize>>,
+}
+
+impl IncidenceTable {
+    /// One pass over the faces. Each vertex has its faces in ascending order.
+    pub fn new(vertex_count: usize, faces: &Faces) -> Self {
+        let mut incidence: Vec<Vec<usize>> = vec![Vec::new(); vertex_count];
+        for (f_index, face) in faces.iter().enumerate() {
+            for v in face {
+                incidence[*v].push(f_index);
+            }
+        }
+
+        IncidenceTable { faces: incidence }
+    }
+
+    pub fn vertex_faces(&self, vertex_index: usize) -> &[usize] {
+        &self.faces[vertex_index]
+    }
+
+    /// How many faces meet at the vertex.
+    pub fn degree(&self, vertex_index: usize) -> usize {
+        self.faces[vertex_index].len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &[usize])> + '_ {
+        self.faces.iter().map(|faces| faces.as_slice()).enumerate()
+    }
+}
@@ -0,0 +1,140 @@
+//! How close a polyhedron is to being made of regular faces with equal length edges. Once
+//! subdivided, geodesic and Goldberg polyhedra have edges of several lengths. Building one
+//! as a frame needs a strut made for each distinct length.
+use std::f64::consts::PI;
+
+use cgmath::{InnerSpace, MetricSpace, Rad};
+use derive_getters::Getters;
+
+use crate::polyhedron::VertexAndFaceOps;
+
+/// How far a face is from a regular polygon with the same number of sides.
+#[derive(Debug, Copy, Clone, Getters)]
+pub struct Irregularity {
+    /// Largest difference of an edge length from the mean edge length of the face as a
+    /// fraction of that mean.
+    edges: f64,
+
+    /// Largest difference of a corner angle from the corner angle of a regular polygon.
+    angles: Rad<f64>,
+}
+
+#[derive(Debug, Clone, Getters)]
+pub struct Analysis {
+    min_edge: f64,
+    max_edge: f64,
+    mean_edge: f64,
+
+    /// Each distinct edge length along with how many edges have it, shortest first.
+    edge_lengths: Vec<(f64, usize)>,
+
+    /// For each face in the same order as the faces.
+    faces: Vec<Irregularity>,
+}
+
+impl Analysis {
+    /// Edge lengths within `tolerance` of each other as a fraction of the longest edge are
+    /// counted as the same length.
+    pub fn new<P: VertexAndFaceOps>(polyhedron: &P, tolerance: f64) -> Self {
+        let (vertices, faces) = polyhedron.vertices_and_faces();
+
+        let mut lengths: Vec<f64> = polyhedron
+            .edges()
+            .into_iter()
+            .map(|(v1, v2)| vertices[v1].distance(vertices[v2]))
+            .collect();
+        lengths.sort_by(|l1, l2| l1.partial_cmp(l2).expect("Edge length is NaN"));
+
+        let min_edge = *lengths.first().expect("Polyhedron has no edges");
+        let max_edge = *lengths.last().expect("Polyhedron has no edges");
+        let mean_edge = lengths.iter().sum::<f64>() / lengths.len() as f64;
+
+        // Each length is the mean of the group it stands for.
+        let mut edge_lengths: Vec<(f64, usize)> = Vec::new();
+        let mut group_first = lengths[0];
+        for length in lengths.iter() {
+            match edge_lengths.last_mut() {
+                Some((sum, count)) if length - group_first <= tolerance * max_edge => {
+                    *sum += length;
+                    *count += 1;
+                },
+                _ => {
+                    group_first = *length;
+                    edge_lengths.push((*length, 1));
+                },
+            }
+        }
+        for (sum, count) in edge_lengths.iter_mut() {
+            *sum /= *count as f64;
+        }
+
+        let faces = faces
+            .iter()
+            .map(|face| {
+                let len = face.len();
+                let points: Vec<_> = face.iter().map(|v| vertices[*v]).collect();
+                let edges: Vec<f64> = (0..len)
+                    .map(|i| points[i].distance(points[(i + 1) % len]))
+                    .collect();
+                let mean = edges.iter().sum::<f64>() / len as f64;
+                let regular = PI * (len as f64 - 2.0) / len as f64;
+
+                let edges = edges
+                    .iter()
+                    .map(|edge| (edge - mean).abs() / mean)
+                    .fold(0.0, f64::max);
+                let angles = (0..len)
+                    .map(|i| {
+                        let corner = points[i];
+                        let before = points[(i + len - 1) % len] - corner;
+                        let after = points[(i + 1) % len] - corner;
+                        (before.angle(after).0 - regular).abs()
+                    })
+                    .fold(0.0, f64::max);
+
+                Irregularity { edges, angles: Rad(angles) }
+            })
+            .collect();
+
+        Analysis { min_edge, max_edge, mean_edge, edge_lengths, faces }
+    }
+
+    /// How many different lengths of strut a frame would need.
+    pub fn distinct_edge_lengths(&self) -> usize {
+        self.edge_lengths.len()
+    }
+
+    /// The face furthest from regular going by its edges and its angles.
+    pub fn worst_face(&self) -> Irregularity {
+        self.faces
+            .iter()
+            .fold(Irregularity { edges: 0.0, angles: Rad(0.0) }, |worst, face| Irregularity {
+                edges: worst.edges.max(face.edges),
+                angles: Rad(worst.angles.0.max(face.angles.0)),
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::polyhedron::{Specification, geodesic};
+    use crate::platonic_solid::Icosahedron2;
+
+    #[test]
+    fn regular_and_geodesic() {
+        let cube = Analysis::new(&Specification::parse("C").unwrap().produce(), 1e-6);
+        assert!(cube.distinct_edge_lengths() == 1);
+        assert!((cube.max_edge() - cube.min_edge()).abs() < 1e-9);
+        assert!(cube.worst_face().edges() < &1e-9);
+        assert!(cube.worst_face().angles().0 < 1e-9);
+
+        // The 2 frequency icosahedral geodesic sphere famously needs two strut lengths.
+        let sphere = geodesic((2, 0), &Icosahedron2::new(1.0)).unwrap();
+        let analysis = Analysis::new(&sphere, 1e-6);
+        assert!(analysis.distinct_edge_lengths() == 2);
+        assert!(analysis.edge_lengths().iter().map(|(_, c)| c).sum::<usize>() == 120);
+        assert!(analysis.faces().len() == 80);
+        assert!(analysis.min_edge() < analysis.mean_edge());
+    }
+}
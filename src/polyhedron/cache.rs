@@ -0,0 +1,162 @@
+//! Remember what specifications produced so toggling operations on and off in an
+//! interactive app doesn't rebuild the whole chain every time.
+//!
+//! A specification missing from the cache is built from the longest run of its first
+//! operations that is cached, so appending an operation only runs that one operation.
+//! Seeds are told apart by their geometry, not just their notation, so seeds of different
+//! sizes don't get mixed up.
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::collections::hash_map::DefaultHasher;
+
+use super::{ConwayOperation, DualCenter, Lift, Polyhedron, Specification, VtFc};
+
+/// One entry per operation. Unlike the notation this includes truncation ratios, where Kis
+/// and Dual lift to and the seed geometry.
+type Key = Vec<String>;
+
+fn key(operations: &[ConwayOperation]) -> Key {
+    operations.iter().map(op_key).collect()
+}
+
+/// The entry in the key for a single operation.
+pub(super) fn op_key(op: &ConwayOperation) -> String {
+    match op {
+        ConwayOperation::Seed(_, p) => {
+            let mut hasher = DefaultHasher::new();
+            for v in p.data.vertices.iter() {
+                for c in [v.x, v.y, v.z].iter() {
+                    c.to_bits().hash(&mut hasher);
+                }
+            }
+            p.data.faces.hash(&mut hasher);
+            format!("{}{:x}", op.notation(), hasher.finish())
+        },
+        ConwayOperation::Truncate(_, Some(ratio)) => {
+            format!("{}@{:x}", op.notation(), ratio.to_bits())
+        },
+        ConwayOperation::Spherize(amount)
+        | ConwayOperation::Extrude(amount)
+        | ConwayOperation::Inset(amount) => {
+            format!("{}@{:x}", op.notation(), amount.to_bits())
+        },
+        ConwayOperation::Dual(DualCenter::FacePlaneTangent) => {
+            format!("{}@tangent", op.notation())
+        },
+        ConwayOperation::Dual(DualCenter::Centroid)
+        | ConwayOperation::Kis(_, Lift::Flat)
+        | ConwayOperation::Subdivide(_, Lift::Flat) => format!("{}@flat", op.notation()),
+        _ => op.notation(),
+    }
+}
+
+/// Holds up to `capacity` polyhedra. The least recently used is forgotten first.
+#[derive(Debug, Clone)]
+pub struct SpecificationCache {
+    capacity: usize,
+    entries: HashMap<Key, Polyhedron<VtFc>>,
+    used: VecDeque<Key>,
+}
+
+impl SpecificationCache {
+    pub fn new(capacity: usize) -> Self {
+        SpecificationCache {
+            capacity,
+            entries: HashMap::new(),
+            used: VecDeque::new(),
+        }
+    }
+
+    /// Same as `Specification::produce` but reusing earlier results.
+    pub fn produce(&mut self, specification: &Specification) -> Polyhedron<VtFc> {
+        let operations = &specification.operations;
+        let key = key(operations);
+
+        let cached = (1..=operations.len())
+            .rev()
+            .find(|count| self.entries.contains_key(&key[..*count]));
+        let polyhedron = match cached {
+            Some(count) => {
+                self.touch(&key[..count]);
+                let base = self.entries[&key[..count]].clone();
+                Specification::apply(&operations[count..], base)
+            },
+            None => specification.produce(),
+        };
+
+        if !self.entries.contains_key(&key) {
+            self.insert(key, polyhedron.clone());
+        }
+
+        polyhedron
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.used.clear();
+    }
+
+    fn touch(&mut self, key: &[String]) {
+        if let Some(position) = self.used.iter().position(|used| used.as_slice() == key) {
+            let key = self.used.remove(position).unwrap();
+            self.used.push_back(key);
+        }
+    }
+
+    fn insert(&mut self, key: Key, polyhedron: Polyhedron<VtFc>) {
+        if self.capacity == 0 {
+            return;
+        }
+        while self.entries.len() >= self.capacity {
+            let oldest = self.used.pop_front().unwrap();
+            self.entries.remove(&oldest);
+        }
+
+        self.used.push_back(key.clone());
+        self.entries.insert(key, polyhedron);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::polyhedron::{ConwayDescription, VertexAndFaceOps};
+    use crate::platonic_solid::Cube2;
+
+    #[test]
+    fn reuse_and_evict() {
+        let mut cache = SpecificationCache::new(2);
+        let kc = Specification::parse("kC").unwrap();
+        let dkc = Specification::parse("dkC").unwrap();
+
+        let produced = cache.produce(&kc);
+        assert!(cache.len() == 1);
+        assert!(produced.vertices_and_faces().1.len() == 24);
+
+        // Built on top of the cached kis.
+        let produced = cache.produce(&dkc);
+        assert!(cache.len() == 2);
+        assert!(produced.vertices_and_faces().0.len() == 24);
+        assert!(produced.edges() == dkc.produce().edges());
+
+        cache.produce(&Specification::parse("tC").unwrap());
+        assert!(cache.len() == 2);
+
+        // Same notation, different seed size.
+        let big = ConwayDescription::new()
+            .seed(&Cube2::new(10.0)).unwrap()
+            .kis().unwrap()
+            .emit().unwrap();
+        let big = cache.produce(&big);
+        let small = kc.produce();
+        assert!(big.vertices_and_faces().0[0] != small.vertices_and_faces().0[0]);
+    }
+}
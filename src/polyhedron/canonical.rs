@@ -0,0 +1,135 @@
+//! Canonical form. Every edge touches the unit sphere, the faces are flat and the points
+//! where the edges touch the sphere are centered on the origin. Every convex polyhedron has
+//! one and it's unique up to rotation. For the Archimedean solids it's the uniform solid.
+//!
+//! Found the way George Hart does it by nudging the vertices over and over. Each round pulls
+//! every edge towards being tangent to the sphere, moves the tangent points back to being
+//! centered and then pushes each face towards its own plane.
+use cgmath::prelude::*;
+use cgmath::{Point3, Vector3};
+
+use super::{Faces, Polyhedron, VertexAndFaceOps, VtFc};
+
+/// How far of the way to each correction to go each round. Going much further overshoots
+/// and some never settle.
+const STABILITY: f64 = 0.3;
+
+/// Stop once no vertex moves further than this in a round.
+const TOLERANCE: f64 = 1e-10;
+
+/// Give up on settling after this many rounds. The truncated icosidodecahedron takes close
+/// to twenty thousand.
+const MAX_ROUNDS: usize = 50_000;
+
+impl Polyhedron<VtFc> {
+    /// Move the vertices into canonical form keeping the faces. The center ends up on the
+    /// origin.
+    ///
+    /// Shapes a long way from their canonical form, such as after several chamfers, may not
+    /// settle. They're left as they were after the last round.
+    pub fn canonicalize(mut self) -> Self {
+        let edges = self.edges();
+        let center = self.data.center.to_vec();
+        let mut vertices: Vec<Point3<f64>> = self.data.vertices
+            .iter()
+            .map(|vertex| vertex - center)
+            .collect();
+
+        for _ in 0..MAX_ROUNDS {
+            let before = vertices.clone();
+            tangentify(&mut vertices, &edges);
+            recenter(&mut vertices, &edges);
+            planarize(&mut vertices, &self.data.faces);
+
+            let moved = vertices
+                .iter()
+                .zip(before.iter())
+                .map(|(after, before)| after.distance2(*before))
+                .fold(0.0, f64::max);
+            if moved < TOLERANCE * TOLERANCE {
+                break;
+            }
+        }
+
+        self.data.center = Point3::origin();
+        self.data.vertices = vertices;
+        self
+    }
+}
+
+/// The point on the line through `v1` and `v2` closest to the origin.
+fn tangent_point(v1: Point3<f64>, v2: Point3<f64>) -> Vector3<f64> {
+    let d = v2 - v1;
+    let v1 = v1.to_vec();
+    v1 - d * (d.dot(v1) / d.magnitude2())
+}
+
+fn tangentify(vertices: &mut [Point3<f64>], edges: &[(usize, usize)]) {
+    for &(v1, v2) in edges {
+        let t = tangent_point(vertices[v1], vertices[v2]);
+        let c = t * (STABILITY / 2.0 * (1.0 - t.magnitude()));
+        vertices[v1] += c;
+        vertices[v2] += c;
+    }
+}
+
+fn recenter(vertices: &mut [Point3<f64>], edges: &[(usize, usize)]) {
+    let sum = edges
+        .iter()
+        .map(|&(v1, v2)| tangent_point(vertices[v1], vertices[v2]))
+        .fold(Vector3::zero(), |sum, t| sum + t);
+    let center = sum / edges.len() as f64;
+
+    for vertex in vertices.iter_mut() {
+        *vertex -= center;
+    }
+}
+
+fn planarize(vertices: &mut [Point3<f64>], faces: &Faces) {
+    let before = vertices.to_vec();
+    for face in faces {
+        let centroid = face
+            .iter()
+            .fold(Vector3::zero(), |sum, v| sum + before[*v].to_vec()) / face.len() as f64;
+
+        // Newell's method copes with faces that aren't flat yet.
+        let mut normal = Vector3::zero();
+        for i in 0..face.len() {
+            let (p, q) = (before[face[i]], before[face[(i + 1) % face.len()]]);
+            normal += p.to_vec().cross(q.to_vec());
+        }
+        if normal.magnitude2() == 0.0 {
+            continue;
+        }
+        let mut normal = normal.normalize();
+        if normal.dot(centroid) < 0.0 {
+            normal = -normal;
+        }
+
+        for v in face {
+            let offset = normal.dot(centroid - before[*v].to_vec());
+            vertices[*v] += normal * (STABILITY * offset);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::polyhedron::Specification;
+
+    #[test]
+    fn edges_touch_the_unit_sphere() {
+        let p = Specification::parse("tI").unwrap().produce().canonicalize();
+        let (vertices, faces) = p.vertices_and_faces();
+
+        for (v1, v2) in p.edges() {
+            let distance = tangent_point(vertices[v1], vertices[v2]).magnitude();
+            assert!((distance - 1.0).abs() < 1e-6);
+        }
+        for face in faces {
+            let points: Vec<Point3<f64>> = face.iter().map(|v| vertices[*v]).collect();
+            assert!(crate::geop::plane_deviation(&points) < 1e-6);
+        }
+    }
+}
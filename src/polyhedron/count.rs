@@ -0,0 +1,306 @@
+//! Predict the size of a polyhedron from its Conway operations without building it.
+//!
+//! Each operation changes the vertex, edge and face counts in a fixed way. The operations
+//! restricted to a degree also need to know how many faces or vertices have that degree so
+//! the number of faces and vertices of each degree is tracked as well. Some operations
+//! leave the degrees unknowable without the actual topology. A later degree restricted
+//! operation then can't be predicted.
+use std::collections::BTreeMap;
+
+use super::{ConwayOperation, Polyhedron, VtFc, VertexAndFaceOps};
+
+/// How many faces or vertices there are of each degree.
+type Degrees = BTreeMap<usize, usize>;
+
+fn degrees(pairs: &[(usize, usize)]) -> Degrees {
+    pairs
+        .iter()
+        .fold(Degrees::new(), |mut d, (degree, count)| {
+            if *count > 0 {
+                *d.entry(*degree).or_insert(0) += count;
+            }
+            d
+        })
+}
+
+/// Merge several degree counts together. Unknown if any of them are unknown.
+fn merge(all: &[Option<&Degrees>]) -> Option<Degrees> {
+    all.iter().try_fold(Degrees::new(), |mut merged, d| {
+        for (degree, count) in (*d)?.iter() {
+            *merged.entry(*degree).or_insert(0) += count;
+        }
+        Some(merged)
+    })
+}
+
+/// Multiply each degree by `factor` keeping the counts.
+fn multiply(d: Option<&Degrees>, factor: usize) -> Option<Degrees> {
+    d.map(|d| d.iter().map(|(degree, count)| (degree * factor, *count)).collect())
+}
+
+#[derive(Debug, Clone)]
+pub (in crate::polyhedron) struct Counts {
+    vertices: usize,
+    edges: usize,
+    faces: usize,
+    vertex_degrees: Option<Degrees>,
+    face_degrees: Option<Degrees>,
+}
+
+impl Counts {
+    pub fn new(p: &Polyhedron<VtFc>) -> Self {
+        let incidence = p.incidence();
+        let vertex_degrees: Vec<(usize, usize)> = incidence
+            .iter()
+            .map(|(v_index, _)| (incidence.degree(v_index), 1))
+            .collect();
+
+        Counts {
+            vertices: p.data.vertices.len(),
+            edges: p.edge_count(),
+            faces: p.data.faces.len(),
+            vertex_degrees: Some(degrees(&vertex_degrees)),
+            face_degrees: Some(p.face_census()),
+        }
+    }
+
+    /// Vertices, edges and faces.
+    pub fn totals(&self) -> (usize, usize, usize) {
+        (self.vertices, self.edges, self.faces)
+    }
+
+    /// Counts after applying `op`. `None` if `op` is restricted to a degree and how many
+    /// faces or vertices have that degree isn't known.
+    pub fn apply(&self, op: &ConwayOperation) -> Option<Self> {
+        let (v, e, f) = self.totals();
+        let vd = self.vertex_degrees.as_ref();
+        let fd = self.face_degrees.as_ref();
+
+        let counts = match op {
+            ConwayOperation::Seed(_, p) => Counts::new(p),
+            ConwayOperation::Dual(_) => Counts {
+                vertices: f,
+                edges: e,
+                faces: v,
+                vertex_degrees: self.face_degrees.clone(),
+                face_degrees: self.vertex_degrees.clone(),
+            },
+            ConwayOperation::Kis(None, _) => Counts {
+                vertices: v + f,
+                edges: 3 * e,
+                faces: 2 * e,
+                vertex_degrees: merge(&[multiply(vd, 2).as_ref(), fd]),
+                face_degrees: Some(degrees(&[(3, 2 * e)])),
+            },
+            ConwayOperation::Kis(Some(degree), _) => {
+                let split = *fd?.get(degree).unwrap_or(&0);
+                let mut face_degrees = fd?.clone();
+                face_degrees.remove(degree);
+
+                // Which vertices gain edges depends on where the split faces are.
+                let vertex_degrees = if split == 0 {
+                    self.vertex_degrees.clone()
+                } else if split == f {
+                    merge(&[multiply(vd, 2).as_ref(), Some(&degrees(&[(*degree, split)]))])
+                } else {
+                    None
+                };
+
+                Counts {
+                    vertices: v + split,
+                    edges: e + degree * split,
+                    faces: f + (degree - 1) * split,
+                    vertex_degrees,
+                    face_degrees: merge(&[
+                        Some(&face_degrees), Some(&degrees(&[(3, degree * split)])),
+                    ]),
+                }
+            },
+            ConwayOperation::Truncate(None, _) => Counts {
+                vertices: 2 * e,
+                edges: 3 * e,
+                faces: f + v,
+                vertex_degrees: Some(degrees(&[(3, 2 * e)])),
+                face_degrees: merge(&[multiply(fd, 2).as_ref(), vd]),
+            },
+            ConwayOperation::Truncate(Some(degree), _) => {
+                let cut = *vd?.get(degree).unwrap_or(&0);
+                let mut vertex_degrees = vd?.clone();
+                vertex_degrees.remove(degree);
+
+                // Which faces gain edges depends on where the cut vertices are.
+                let face_degrees = if cut == 0 {
+                    self.face_degrees.clone()
+                } else if cut == v {
+                    merge(&[multiply(fd, 2).as_ref(), Some(&degrees(&[(*degree, cut)]))])
+                } else {
+                    None
+                };
+
+                Counts {
+                    vertices: v - cut + degree * cut,
+                    edges: e + degree * cut,
+                    faces: f + cut,
+                    vertex_degrees: merge(&[
+                        Some(&vertex_degrees), Some(&degrees(&[(3, degree * cut)])),
+                    ]),
+                    face_degrees,
+                }
+            },
+            ConwayOperation::Ambo => Counts {
+                vertices: e,
+                edges: 2 * e,
+                faces: f + v,
+                vertex_degrees: Some(degrees(&[(4, e)])),
+                face_degrees: merge(&[fd, vd]),
+            },
+            ConwayOperation::Gyro => Counts {
+                vertices: v + f + 2 * e,
+                edges: 5 * e,
+                faces: 2 * e,
+                vertex_degrees: merge(&[vd, fd, Some(&degrees(&[(3, 2 * e)]))]),
+                face_degrees: Some(degrees(&[(5, 2 * e)])),
+            },
+            ConwayOperation::Snub => Counts {
+                vertices: 2 * e,
+                edges: 5 * e,
+                faces: v + f + 2 * e,
+                vertex_degrees: Some(degrees(&[(5, 2 * e)])),
+                face_degrees: merge(&[vd, fd, Some(&degrees(&[(3, 2 * e)]))]),
+            },
+            ConwayOperation::Expand => Counts {
+                vertices: 2 * e,
+                edges: 4 * e,
+                faces: v + e + f,
+                vertex_degrees: Some(degrees(&[(4, 2 * e)])),
+                face_degrees: merge(&[fd, vd, Some(&degrees(&[(4, e)]))]),
+            },
+            ConwayOperation::Bevel => Counts {
+                vertices: 4 * e,
+                edges: 6 * e,
+                faces: v + e + f,
+                vertex_degrees: Some(degrees(&[(3, 4 * e)])),
+                face_degrees: merge(&[
+                    multiply(fd, 2).as_ref(),
+                    multiply(vd, 2).as_ref(),
+                    Some(&degrees(&[(4, e)])),
+                ]),
+            },
+            ConwayOperation::Ortho => Counts {
+                vertices: v + e + f,
+                edges: 4 * e,
+                faces: 2 * e,
+                vertex_degrees: merge(&[fd, vd, Some(&degrees(&[(4, e)]))]),
+                face_degrees: Some(degrees(&[(4, 2 * e)])),
+            },
+            ConwayOperation::Join => Counts {
+                vertices: v + f,
+                edges: 2 * e,
+                faces: e,
+                vertex_degrees: merge(&[fd, vd]),
+                face_degrees: Some(degrees(&[(4, e)])),
+            },
+            ConwayOperation::Chamfer => Counts {
+                vertices: v + 2 * e,
+                edges: 4 * e,
+                faces: f + e,
+                vertex_degrees: merge(&[vd, Some(&degrees(&[(3, 2 * e)]))]),
+                face_degrees: merge(&[fd, Some(&degrees(&[(6, e)]))]),
+            },
+            ConwayOperation::Whirl => Counts {
+                vertices: v + 4 * e,
+                edges: 7 * e,
+                faces: f + 2 * e,
+                vertex_degrees: merge(&[vd, Some(&degrees(&[(3, 4 * e)]))]),
+                face_degrees: merge(&[fd, Some(&degrees(&[(6, 2 * e)]))]),
+            },
+            ConwayOperation::Propeller => Counts {
+                vertices: v + 2 * e,
+                edges: 5 * e,
+                faces: f + 2 * e,
+                vertex_degrees: merge(&[vd, Some(&degrees(&[(4, 2 * e)]))]),
+                face_degrees: merge(&[fd, Some(&degrees(&[(4, 2 * e)]))]),
+            },
+            ConwayOperation::Meta => Counts {
+                vertices: v + e + f,
+                edges: 6 * e,
+                faces: 4 * e,
+                vertex_degrees: merge(&[
+                    multiply(vd, 2).as_ref(),
+                    multiply(fd, 2).as_ref(),
+                    Some(&degrees(&[(4, e)])),
+                ]),
+                face_degrees: Some(degrees(&[(3, 4 * e)])),
+            },
+            ConwayOperation::Needle => Counts {
+                vertices: v + f,
+                edges: 3 * e,
+                faces: 2 * e,
+                vertex_degrees: merge(&[multiply(fd, 2).as_ref(), vd]),
+                face_degrees: Some(degrees(&[(3, 2 * e)])),
+            },
+            ConwayOperation::Zip => Counts {
+                vertices: 2 * e,
+                edges: 3 * e,
+                faces: v + f,
+                vertex_degrees: Some(degrees(&[(3, 2 * e)])),
+                face_degrees: merge(&[multiply(vd, 2).as_ref(), fd]),
+            },
+            ConwayOperation::Loft
+            | ConwayOperation::Extrude(_)
+            | ConwayOperation::Inset(_) => Counts {
+                vertices: v + 2 * e,
+                edges: 5 * e,
+                faces: f + 2 * e,
+                vertex_degrees: merge(&[
+                    multiply(vd, 2).as_ref(), Some(&degrees(&[(3, 2 * e)])),
+                ]),
+                face_degrees: merge(&[fd, Some(&degrees(&[(4, 2 * e)]))]),
+            },
+            ConwayOperation::Lace => Counts {
+                vertices: v + 2 * e,
+                edges: 7 * e,
+                faces: f + 4 * e,
+                vertex_degrees: merge(&[
+                    multiply(vd, 3).as_ref(), Some(&degrees(&[(4, 2 * e)])),
+                ]),
+                face_degrees: merge(&[fd, Some(&degrees(&[(3, 4 * e)]))]),
+            },
+            ConwayOperation::Subdivide(frequency, _) => {
+                // The faces that aren't triangles are split around their centroids first.
+                let triangles = *fd?.get(&3).unwrap_or(&0);
+                let raised = f - triangles;
+                let sides: usize = fd?
+                    .iter()
+                    .filter(|(degree, _)| **degree != 3)
+                    .map(|(degree, count)| degree * count)
+                    .sum();
+                let (v, e, f) = (v + raised, e + sides, triangles + sides);
+
+                // Each edge gains `frequency - 1` vertices and each triangle gains a smaller
+                // triangle of them inside.
+                let k = *frequency;
+                let edge_vertices = e * (k - 1);
+                let inner_vertices = f * (k - 1) * k.saturating_sub(2) / 2;
+
+                // Which vertices gain edges depends on where the raised faces are.
+                let vertex_degrees = if raised == 0 {
+                    merge(&[vd, Some(&degrees(&[(6, edge_vertices + inner_vertices)]))])
+                } else {
+                    None
+                };
+
+                Counts {
+                    vertices: v + edge_vertices + inner_vertices,
+                    edges: k * e + 3 * f * k * (k - 1) / 2,
+                    faces: k * k * f,
+                    vertex_degrees,
+                    face_degrees: Some(degrees(&[(3, k * k * f)])),
+                }
+            },
+            ConwayOperation::Spherize(_) => self.clone(),
+        };
+
+        Some(counts)
+    }
+}
@@ -0,0 +1,214 @@
+//! Seed Conway operations with any closed polyhedron given as plain vertices and faces.
+//!
+//! The operations assume the polyhedron is centered on the origin so the vertices are moved
+//! to put their average there. Vertices that aren't part of any face are dropped. Faces
+//! wound clockwise seen from the outside are all flipped.
+use std::{error, fmt};
+use std::collections::HashMap;
+
+use cgmath::prelude::*;
+use cgmath::{Point3, Vector3};
+
+use super::{Polyhedron, VtFc, Seed, SeedSolid};
+
+/// A checked closed polyhedron to start from.
+#[derive(Debug, Clone)]
+pub struct CustomSeed {
+    polyhedron: Polyhedron<VtFc>,
+}
+
+impl CustomSeed {
+    /// Each face lists indexes into `vertices` going around it. Every edge must be shared
+    /// by two faces going along it in opposite directions.
+    pub fn new(
+        vertices: Vec<Point3<f64>>, mut faces: Vec<Vec<usize>>,
+    ) -> Result<Self, SeedError> {
+        if faces.is_empty() {
+            return Err(SeedError::NoFaces);
+        }
+        for (f, face) in faces.iter().enumerate() {
+            if face.len() < 3 {
+                return Err(SeedError::FaceTooSmall(f));
+            }
+            if let Some(v) = face.iter().find(|v| **v >= vertices.len()) {
+                return Err(SeedError::InvalidIndex(f, *v));
+            }
+        }
+
+        // Each directed edge must be used once and the reverse once to be closed and wound
+        // consistently.
+        let mut edges: HashMap<(usize, usize), usize> = HashMap::new();
+        for face in faces.iter() {
+            for i in 0..face.len() {
+                *edges.entry((face[i], face[(i + 1) % face.len()])).or_insert(0) += 1;
+            }
+        }
+        for (&(v1, v2), &count) in edges.iter() {
+            if count > 1 || v1 == v2 {
+                return Err(SeedError::NotManifold(v1, v2));
+            }
+            if !edges.contains_key(&(v2, v1)) {
+                return Err(SeedError::NotClosed(v1, v2));
+            }
+        }
+
+        // Drop the unused vertices.
+        let mut renumber: HashMap<usize, usize> = HashMap::new();
+        let mut used: Vec<Point3<f64>> = Vec::new();
+        for face in faces.iter_mut() {
+            for v in face.iter_mut() {
+                *v = *renumber.entry(*v).or_insert_with(|| {
+                    used.push(vertices[*v]);
+                    used.len() - 1
+                });
+            }
+        }
+
+        let center = Point3::centroid(&used);
+        let vertices: Vec<Point3<f64>> = used
+            .into_iter()
+            .map(|v| Point3::from_vec(v - center))
+            .collect();
+
+        // Six times the signed volume. Negative when the faces are wound inwards.
+        let volume: f64 = faces
+            .iter()
+            .flat_map(|face| {
+                (1..face.len() - 1).map(move |i| (face[0], face[i], face[i + 1]))
+            })
+            .map(|(v1, v2, v3)| {
+                let (v1, v2, v3): (Vector3<f64>, Vector3<f64>, Vector3<f64>) =
+                    (vertices[v1].to_vec(), vertices[v2].to_vec(), vertices[v3].to_vec());
+                v1.dot(v2.cross(v3))
+            })
+            .sum();
+        if volume < 0.0 {
+            for face in faces.iter_mut() {
+                face.reverse();
+            }
+        }
+
+        Ok(CustomSeed {
+            polyhedron: Polyhedron {
+                data: VtFc {
+                    center: Point3::origin(),
+                    vertices,
+                    faces: faces.into(),
+                },
+            },
+        })
+    }
+}
+
+impl Seed for CustomSeed {
+    fn solid(&self) -> SeedSolid {
+        SeedSolid::Custom
+    }
+
+    fn polyhedron(&self) -> Polyhedron<VtFc> {
+        self.polyhedron.clone()
+    }
+}
+
+/// Problems with the vertices and faces given to make a `CustomSeed`. Faces and vertices
+/// are numbered by their position starting at zero.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SeedError {
+    NoFaces,
+    FaceTooSmall(usize),
+    InvalidIndex(usize, usize),
+    NotManifold(usize, usize),
+    NotClosed(usize, usize),
+}
+
+impl fmt::Display for SeedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SeedError::NoFaces => write!(f, "No faces."),
+            SeedError::FaceTooSmall(face) => {
+                write!(f, "Face {} has less than three vertices.", face)
+            },
+            SeedError::InvalidIndex(face, v) => {
+                write!(f, "Face {} refers to missing vertex {}.", face, v)
+            },
+            SeedError::NotManifold(v1, v2) => write!(
+                f, "Edge from vertex {} to {} is shared by faces wound the same way.", v1, v2,
+            ),
+            SeedError::NotClosed(v1, v2) => {
+                write!(f, "Edge from vertex {} to {} is only part of one face.", v1, v2)
+            },
+        }
+    }
+}
+
+impl error::Error for SeedError {
+    fn description(&self) -> &str {
+        "Error checking a custom seed."
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::polyhedron::{ConwayDescription, VertexAndFaceOps};
+
+    /// A square pyramid off to the side with its faces wound inwards and a spare vertex.
+    fn pyramid() -> (Vec<Point3<f64>>, Vec<Vec<usize>>) {
+        let vertices = vec![
+            Point3::new(5.0, 0.0, 0.0),
+            Point3::new(6.0, 0.0, 0.0),
+            Point3::new(6.0, 1.0, 0.0),
+            Point3::new(5.0, 1.0, 0.0),
+            Point3::new(5.5, 0.5, 1.0),
+            Point3::new(9.0, 9.0, 9.0),
+        ];
+        let faces = vec![
+            vec![0, 1, 2, 3],
+            vec![1, 0, 4],
+            vec![2, 1, 4],
+            vec![3, 2, 4],
+            vec![0, 3, 4],
+        ];
+        (vertices, faces)
+    }
+
+    #[test]
+    fn pyramid_seed() {
+        let (vertices, faces) = pyramid();
+        let seed = CustomSeed::new(vertices, faces).unwrap();
+        let pyramid = seed.polyhedron();
+        let (vertices, faces) = pyramid.vertices_and_faces();
+
+        assert!(vertices.len() == 5);
+        assert!(Point3::centroid(vertices).to_vec().magnitude() < 1e-9);
+        assert!(faces[0] == vec![3, 2, 1, 0]);
+
+        let kp = ConwayDescription::new()
+            .seed(&seed).unwrap()
+            .kis().unwrap()
+            .emit().unwrap();
+        assert!(kp.produce().vertices_and_faces().1.len() == 16);
+    }
+
+    #[test]
+    fn rejected() {
+        let (vertices, mut faces) = pyramid();
+        assert!(CustomSeed::new(vertices.clone(), vec![]).unwrap_err() == SeedError::NoFaces);
+
+        faces[1] = vec![1, 0, 6];
+        let err = CustomSeed::new(vertices.clone(), faces.clone()).unwrap_err();
+        assert!(err == SeedError::InvalidIndex(1, 6));
+
+        faces[1] = vec![0, 1, 4];
+        match CustomSeed::new(vertices.clone(), faces.clone()) {
+            Err(SeedError::NotManifold(_, _)) => (),
+            _ => panic!("Inconsistent winding accepted."),
+        }
+
+        faces.remove(1);
+        match CustomSeed::new(vertices, faces) {
+            Err(SeedError::NotClosed(_, _)) => (),
+            _ => panic!("Open mesh accepted."),
+        }
+    }
+}
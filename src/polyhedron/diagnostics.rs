@@ -0,0 +1,131 @@
+//! Find the ways a polyhedron can come out broken. Meant for checking the output of a new
+//! operator since a broken polyhedron usually still renders, just with holes or flicker.
+//!
+//! Each problem is reported with the indexes of the faces, edges or vertices involved so
+//! they can be looked up in what the operator made.
+use std::collections::BTreeMap;
+use std::fmt;
+
+use cgmath::prelude::*;
+use cgmath::Vector3;
+use derive_getters::Getters;
+
+use crate::polyhedron::VertexAndFaceOps;
+
+#[derive(Debug, Clone, PartialEq, Getters)]
+pub struct Diagnostics {
+    /// Edges that don't have exactly two faces along them, as the vertex indexes lowest
+    /// first along with how many faces do. One face leaves a hole and more than two leave
+    /// faces stuck together like pages of a book.
+    non_manifold_edges: Vec<((usize, usize), usize)>,
+
+    /// Faces with an area no more than the tolerance given. Their normals are nonsense.
+    degenerate_faces: Vec<usize>,
+
+    /// Faces that go through the same vertex more than once, along with that vertex.
+    repeated_vertices: Vec<(usize, usize)>,
+}
+
+impl Diagnostics {
+    /// Faces with an area of `tolerance` or less count as degenerate.
+    pub fn new<P: VertexAndFaceOps>(polyhedron: &P, tolerance: f64) -> Self {
+        let (vertices, faces) = polyhedron.vertices_and_faces();
+
+        let mut edges: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+        let mut degenerate_faces = Vec::new();
+        let mut repeated_vertices = Vec::new();
+
+        for (f_index, face) in faces.iter().enumerate() {
+            let len = face.len();
+            for i in 0..len {
+                let (v1, v2) = (face[i], face[(i + 1) % len]);
+                *edges.entry((v1.min(v2), v1.max(v2))).or_insert(0) += 1;
+
+                let repeated = face[..i].contains(&v1);
+                if repeated && !repeated_vertices.contains(&(f_index, v1)) {
+                    repeated_vertices.push((f_index, v1));
+                }
+            }
+
+            // Half the length of the sum of the cross products going around is the area
+            // even when the face isn't flat.
+            let area = (0..len)
+                .map(|i| {
+                    let next = vertices[face[(i + 1) % len]].to_vec();
+                    vertices[face[i]].to_vec().cross(next)
+                })
+                .fold(Vector3::zero(), |sum, n| sum + n)
+                .magnitude() / 2.0;
+            if area <= tolerance {
+                degenerate_faces.push(f_index);
+            }
+        }
+
+        let non_manifold_edges = edges
+            .into_iter()
+            .filter(|(_, count)| *count != 2)
+            .collect();
+
+        Diagnostics { non_manifold_edges, degenerate_faces, repeated_vertices }
+    }
+
+    /// Nothing wrong was found.
+    pub fn is_sound(&self) -> bool {
+        self.non_manifold_edges.is_empty()
+            && self.degenerate_faces.is_empty()
+            && self.repeated_vertices.is_empty()
+    }
+}
+
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_sound() {
+            return write!(f, "No problems found.");
+        }
+        for ((v1, v2), count) in self.non_manifold_edges.iter() {
+            writeln!(f, "Edge {}-{} has {} faces.", v1, v2, count)?;
+        }
+        for face in self.degenerate_faces.iter() {
+            writeln!(f, "Face {} has no area.", face)?;
+        }
+        for (face, vertex) in self.repeated_vertices.iter() {
+            writeln!(f, "Face {} goes through vertex {} more than once.", face, vertex)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cgmath::Point3;
+    use crate::polyhedron::{Polyhedron, Specification};
+
+    #[test]
+    fn sound_and_broken() {
+        for notation in ["tI", "t4aC", "t3k5aD", "lT", "xC", "du3I"].iter() {
+            let p = Specification::parse(notation).unwrap().produce();
+            assert!(Diagnostics::new(&p, 1e-9).is_sound(), "{}", notation);
+        }
+
+        // A tetrahedron missing a face, with a flat sliver on the hole and a face folded
+        // back on itself.
+        let vertices = [
+            Point3::new(1.0, 1.0, 1.0),
+            Point3::new(1.0, -1.0, -1.0),
+            Point3::new(-1.0, 1.0, -1.0),
+            Point3::new(-1.0, -1.0, 1.0),
+            Point3::new(0.0, 0.0, -1.0),
+        ];
+        let faces: [&[usize]; 4] = [&[0, 3, 1], &[0, 2, 3], &[1, 4, 2], &[0, 1, 3, 1]];
+        let broken = Polyhedron::unchecked(Point3::origin(), &vertices, &faces);
+        let diagnostics = Diagnostics::new(&broken, 1e-9);
+
+        assert!(!diagnostics.is_sound());
+        assert!(diagnostics.degenerate_faces() == &vec![2, 3]);
+        assert!(diagnostics.repeated_vertices() == &vec![(3, 1)]);
+        assert!(diagnostics.non_manifold_edges().contains(&((0, 2), 1)));
+        assert!(diagnostics.non_manifold_edges().contains(&((1, 3), 3)));
+        assert!(diagnostics.to_string().contains("Edge 1-3 has 3 faces."));
+    }
+}
@@ -0,0 +1,9 @@
+//! Write a polyhedron out in formats other programs can read.
+pub mod obj;
+pub mod stl;
+#[cfg(feature = "render")]
+pub mod gltf;
+pub mod ply;
+pub mod svg;
+pub mod json;
+pub mod graph;
@@ -0,0 +1,235 @@
+//! [glTF 2.0](https://www.khronos.org/gltf/) export of presented geometry. Takes the output
+//! of a presenter rather than a bare `Polyhedron` so the colours come along as `COLOR_0`.
+//! Presenters repeat the vertices of each face so the flat shading and per face colours
+//! survive the trip.
+//!
+//! `Format::Gltf` is a single JSON file with the binary data embedded as a base64 data URI.
+//! `Format::Glb` is the binary container which is smaller and quicker to load.
+use std::io::{self, Write};
+
+use crate::scene::{Geometry, Index, Vertex};
+
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+const FLOAT: u32 = 5126;
+const UNSIGNED_SHORT: u32 = 5123;
+const UNSIGNED_INT: u32 = 5125;
+
+const GLB_MAGIC: u32 = 0x4654_6C67;
+const GLB_VERSION: u32 = 2;
+const CHUNK_JSON: u32 = 0x4E4F_534A;
+const CHUNK_BIN: u32 = 0x004E_4942;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    Gltf,
+    Glb,
+}
+
+pub fn write<G: Geometry, W: Write>(
+    geometry: &G, format: Format, writer: &mut W,
+) -> io::Result<()> {
+    let (vertices, index) = geometry.geometry();
+    let (buffer, views) = buffer(&vertices, &index);
+
+    match format {
+        Format::Gltf => {
+            let uri = format!("data:application/octet-stream;base64,{}", base64(&buffer));
+            let json = json(&vertices, &index, &views, buffer.len(), Some(&uri));
+            writer.write_all(json.as_bytes())
+        },
+        Format::Glb => {
+            let mut json = json(&vertices, &index, &views, buffer.len(), None).into_bytes();
+            pad(&mut json, b' ');
+            let mut buffer = buffer;
+            pad(&mut buffer, 0);
+
+            let length = 12 + 8 + json.len() + 8 + buffer.len();
+            for word in [GLB_MAGIC, GLB_VERSION, length as u32].iter() {
+                writer.write_all(&word.to_le_bytes())?;
+            }
+            for (kind, chunk) in [(CHUNK_JSON, &json), (CHUNK_BIN, &buffer)].iter() {
+                writer.write_all(&(chunk.len() as u32).to_le_bytes())?;
+                writer.write_all(&kind.to_le_bytes())?;
+                writer.write_all(chunk)?;
+            }
+
+            Ok(())
+        },
+    }
+}
+
+/// Pad out to a four byte boundary as glTF requires.
+fn pad(bytes: &mut Vec<u8>, with: u8) {
+    while !bytes.len().is_multiple_of(4) {
+        bytes.push(with);
+    }
+}
+
+/// Lay out positions, normals, colours and then the index one after the other. Returns the
+/// buffer along with the offset and length of each.
+fn buffer(vertices: &[Vertex], index: &Index) -> (Vec<u8>, [(usize, usize); 4]) {
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut views = [(0, 0); 4];
+    let attributes: [fn(&Vertex) -> &[f32; 3]; 3] = [
+        Vertex::position, Vertex::normal, Vertex::colour,
+    ];
+
+    for (view, attribute) in views.iter_mut().zip(attributes.iter()) {
+        let start = buffer.len();
+        for v in vertices.iter() {
+            for c in attribute(v).iter() {
+                buffer.extend_from_slice(&c.to_le_bytes());
+            }
+        }
+        *view = (start, buffer.len() - start);
+    }
+
+    let start = buffer.len();
+    match index {
+        Index::U16(index) => index
+            .iter()
+            .for_each(|i| buffer.extend_from_slice(&i.to_le_bytes())),
+        Index::U32(index) => index
+            .iter()
+            .for_each(|i| buffer.extend_from_slice(&i.to_le_bytes())),
+    }
+    views[3] = (start, buffer.len() - start);
+
+    (buffer, views)
+}
+
+fn json(
+    vertices: &[Vertex],
+    index: &Index,
+    views: &[(usize, usize); 4],
+    buffer_len: usize,
+    uri: Option<&str>,
+) -> String {
+    // The position accessor must have its bounds.
+    let (min, max) = vertices
+        .iter()
+        .fold(([f32::MAX; 3], [f32::MIN; 3]), |(mut min, mut max), v| {
+            for i in 0..3 {
+                min[i] = min[i].min(v.position()[i]);
+                max[i] = max[i].max(v.position()[i]);
+            }
+            (min, max)
+        });
+
+    let buffer_views: Vec<String> = views
+        .iter()
+        .enumerate()
+        .map(|(i, (offset, length))| format!(
+            r#"{{"buffer":0,"byteOffset":{},"byteLength":{},"target":{}}}"#,
+            offset,
+            length,
+            if i < 3 { ARRAY_BUFFER } else { ELEMENT_ARRAY_BUFFER },
+        ))
+        .collect();
+
+    let vec3 = |view: usize| format!(
+        r#"{{"bufferView":{},"componentType":{},"count":{},"type":"VEC3"}}"#,
+        view, FLOAT, vertices.len(),
+    );
+    let accessors = [
+        format!(
+            concat!(
+                r#"{{"bufferView":0,"componentType":{},"count":{},"type":"VEC3","#,
+                r#""min":[{},{},{}],"max":[{},{},{}]}}"#,
+            ),
+            FLOAT, vertices.len(), min[0], min[1], min[2], max[0], max[1], max[2],
+        ),
+        vec3(1),
+        vec3(2),
+        format!(
+            r#"{{"bufferView":3,"componentType":{},"count":{},"type":"SCALAR"}}"#,
+            match index { Index::U16(_) => UNSIGNED_SHORT, Index::U32(_) => UNSIGNED_INT },
+            index.len(),
+        ),
+    ];
+
+    let uri = uri.map(|uri| format!(r#","uri":"{}""#, uri)).unwrap_or_default();
+
+    format!(
+        concat!(
+            r#"{{"asset":{{"version":"2.0","generator":"polyorb"}},"#,
+            r#""scene":0,"scenes":[{{"nodes":[0]}}],"nodes":[{{"mesh":0}}],"#,
+            r#""meshes":[{{"primitives":[{{"#,
+            r#""attributes":{{"POSITION":0,"NORMAL":1,"COLOR_0":2}},"indices":3,"mode":4"#,
+            r#"}}]}}],"#,
+            r#""buffers":[{{"byteLength":{}{}}}],"#,
+            r#""bufferViews":[{}],"#,
+            r#""accessors":[{}]}}"#,
+        ),
+        buffer_len,
+        uri,
+        buffer_views.join(","),
+        accessors.join(","),
+    )
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 0x3F) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::polyhedron::Seed;
+    use crate::platonic_solid::Cube2;
+    use crate::presenter::SingleColour;
+
+    #[test]
+    fn base64_padding() {
+        assert!(base64(b"polyorb") == "cG9seW9yYg==");
+        assert!(base64(b"orb") == "b3Ji");
+        assert!(base64(b"orbs") == "b3Jicw==");
+    }
+
+    #[test]
+    fn cube_glb() {
+        let cube = SingleColour::new([1.0, 0.0, 0.0], Cube2::new(1.0).polyhedron())
+            .to_cached();
+        let mut glb: Vec<u8> = Vec::new();
+        write(&cube, Format::Glb, &mut glb).unwrap();
+
+        assert!(glb.len().is_multiple_of(4));
+        assert!(glb[0..4] == *b"glTF");
+        assert!(glb[8..12] == (glb.len() as u32).to_le_bytes());
+
+        let mut gltf: Vec<u8> = Vec::new();
+        write(&cube, Format::Gltf, &mut gltf).unwrap();
+        let gltf = String::from_utf8(gltf).unwrap();
+        assert!(gltf.contains(r#""count":24,"type":"VEC3""#));
+        assert!(gltf.contains(r#""componentType":5123,"count":36,"type":"SCALAR""#));
+    }
+
+    #[test]
+    fn wide_index() {
+        let vertices = [Vertex::new([0.0; 3], [0.0, 0.0, 1.0], [1.0; 3]); 3];
+        let index = Index::U32(vec![0, 1, 2]);
+        let (buffer, views) = buffer(&vertices, &index);
+
+        assert!(views[3] == (buffer.len() - 12, 12));
+        let json = json(&vertices, &index, &views, buffer.len(), None);
+        assert!(json.contains(r#""componentType":5125,"count":3,"type":"SCALAR""#));
+    }
+}
@@ -0,0 +1,120 @@
+//! The graph of a polyhedron for graph tools such as [Graphviz](https://graphviz.org/). Either
+//! the vertices joined by the edges or the faces joined to the faces they share an edge
+//! with, which is the graph of the dual.
+//!
+//! Nodes are the vertex or face indexes. Each edge is written once with the lower index
+//! first. DOT lists every node before the edges so none go missing. An edge list is just a
+//! pair of indexes per line.
+//!
+//! ```text
+//! graph polyhedron {
+//!   0;
+//!   1;
+//!   0 -- 1;
+//! }
+//! ```
+use std::io::{self, Write};
+
+use crate::polyhedron::VertexAndFaceOps;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Graph {
+    /// Vertices joined by the edges.
+    Vertices,
+    /// Faces joined to the faces across their edges.
+    Faces,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    Dot,
+    EdgeList,
+}
+
+pub fn write<P: VertexAndFaceOps, W: Write>(
+    polyhedron: &P, graph: Graph, format: Format, writer: &mut W,
+) -> io::Result<()> {
+    let (nodes, edges) = nodes_and_edges(polyhedron, graph);
+
+    match format {
+        Format::Dot => {
+            writeln!(writer, "graph polyhedron {{")?;
+            for node in 0..nodes {
+                writeln!(writer, "  {};", node)?;
+            }
+            for (n1, n2) in edges {
+                writeln!(writer, "  {} -- {};", n1, n2)?;
+            }
+            writeln!(writer, "}}")
+        },
+        Format::EdgeList => {
+            for (n1, n2) in edges {
+                writeln!(writer, "{} {}", n1, n2)?;
+            }
+            Ok(())
+        },
+    }
+}
+
+/// How many nodes and the edges between them, lower index first and in order.
+fn nodes_and_edges<P: VertexAndFaceOps>(
+    polyhedron: &P, graph: Graph,
+) -> (usize, Vec<(usize, usize)>) {
+    let (vertices, faces) = polyhedron.vertices_and_faces();
+    let (nodes, mut edges) = match graph {
+        Graph::Vertices => (vertices.len(), polyhedron.edges()),
+        Graph::Faces => {
+            let adjacency = polyhedron.adjacency();
+            let mut edges: Vec<(usize, usize)> = Vec::new();
+            for f1 in 0..faces.len() {
+                for f2 in adjacency.face_neighbors(f1) {
+                    if f1 < *f2 {
+                        edges.push((f1, *f2));
+                    }
+                }
+            }
+            (faces.len(), edges)
+        },
+    };
+
+    // Two faces can share more than one edge but are only joined once.
+    edges.sort();
+    edges.dedup();
+    (nodes, edges)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::polyhedron::Specification;
+
+    #[test]
+    fn cube_graphs() {
+        let cube = Specification::parse("C").unwrap().produce();
+        let written = |graph, format| {
+            let mut out: Vec<u8> = Vec::new();
+            write(&cube, graph, format, &mut out).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        let dot = written(Graph::Vertices, Format::Dot);
+        assert!(dot.starts_with("graph polyhedron {\n  0;\n"));
+        assert!(dot.matches(" -- ").count() == 12);
+        assert!(dot.ends_with("}\n"));
+
+        // The faces of a cube are joined like the vertices of an octahedron. Each face is
+        // next to all but the one opposite.
+        let list = written(Graph::Faces, Format::EdgeList);
+        let pairs: Vec<(usize, usize)> = list
+            .lines()
+            .map(|line| {
+                let mut nodes = line.split(' ').map(|n| n.parse::<usize>().unwrap());
+                (nodes.next().unwrap(), nodes.next().unwrap())
+            })
+            .collect();
+        assert!(pairs.len() == 12);
+        for face in 0..6 {
+            assert!(pairs.iter().filter(|(f1, f2)| *f1 == face || *f2 == face).count() == 4);
+        }
+    }
+}
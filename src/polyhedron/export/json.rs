@@ -0,0 +1,81 @@
+//! Topology as JSON. Meant for using a polyhedron as a map, such as a Goldberg polyhedron
+//! as a world of hexagonal tiles, where which face is next to which matters more than how
+//! it looks.
+//!
+//! ```json
+//! {
+//!   "vertices": [[x, y, z], ...],
+//!   "faces": [[v, v, v, ...], ...],
+//!   "edges": [[v, v], ...],
+//!   "neighbors": [[f, f, f, ...], ...]
+//! }
+//! ```
+//!
+//! Faces list their vertices wound anticlockwise seen from outside. Each edge is listed
+//! once with the lower vertex index first. `neighbors` runs parallel to `faces` and gives
+//! the face on the other side of each face edge in the same order as the face vertices.
+use std::io::{self, Write};
+
+use crate::polyhedron::VertexAndFaceOps;
+
+pub fn write<P: VertexAndFaceOps, W: Write>(polyhedron: &P, writer: &mut W) -> io::Result<()> {
+    let (vertices, faces) = polyhedron.vertices_and_faces();
+
+    let adjacency = polyhedron.adjacency();
+    let list = |items: Vec<String>| format!("[{}]", items.join(","));
+    let indexes = |items: &[usize]| list(items.iter().map(|i| i.to_string()).collect());
+
+    let vertices = list(vertices
+        .iter()
+        .map(|v| format!("[{},{},{}]", v.x, v.y, v.z))
+        .collect());
+    let edges = list(polyhedron
+        .edges()
+        .into_iter()
+        .map(|(v1, v2)| indexes(&[v1, v2]))
+        .collect());
+    let neighbors = list((0..faces.len())
+        .map(|f_index| indexes(adjacency.face_neighbors(f_index)))
+        .collect());
+    let faces = list(faces.iter().map(indexes).collect());
+
+    writeln!(
+        writer,
+        r#"{{"vertices":{},"faces":{},"edges":{},"neighbors":{}}}"#,
+        vertices, faces, edges, neighbors,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::polyhedron::Seed;
+    use crate::platonic_solid::Tetrahedron2;
+
+    #[test]
+    fn tetrahedron_json() {
+        let tetrahedron = Tetrahedron2::new(1.0).polyhedron();
+        let mut json: Vec<u8> = Vec::new();
+        write(&tetrahedron, &mut json).unwrap();
+        let json = String::from_utf8(json).unwrap();
+
+        let section = |from: &str, to: &str| {
+            json[json.find(from).unwrap()..json.find(to).unwrap()].to_owned()
+        };
+        assert!(section(r#""edges""#, r#""neighbors""#).matches('[').count() == 1 + 6);
+
+        // Every face of a tetrahedron neighbors the other three.
+        let neighbors = section(r#""neighbors""#, "}");
+        let neighbors: Vec<&str> = neighbors
+            .split(['[', ']'])
+            .filter(|s| s.starts_with(char::is_numeric))
+            .collect();
+        assert!(neighbors.len() == 4);
+        for (f_index, n) in neighbors.iter().enumerate() {
+            let mut n: Vec<usize> = n.split(',').map(|f| f.parse().unwrap()).collect();
+            n.sort();
+            let others: Vec<usize> = (0..4).filter(|f| *f != f_index).collect();
+            assert!(n == others);
+        }
+    }
+}
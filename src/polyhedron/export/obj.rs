@@ -0,0 +1,48 @@
+//! [Wavefront OBJ](https://en.wikipedia.org/wiki/Wavefront_.obj_file) export. Faces are
+//! kept as polygons instead of being triangulated and each face gets its own normal.
+use std::io::{self, Write};
+
+use crate::polyhedron::{Polyhedron, VtFcNm};
+
+/// Write the vertices, normals and faces. OBJ indexes start from one.
+pub fn write<W: Write>(polyhedron: &Polyhedron<VtFcNm>, writer: &mut W) -> io::Result<()> {
+    let data = &polyhedron.data;
+
+    writeln!(writer, "o polyhedron")?;
+    for v in data.vertices.iter() {
+        writeln!(writer, "v {} {} {}", v.x, v.y, v.z)?;
+    }
+    for n in data.normals.iter() {
+        writeln!(writer, "vn {} {} {}", n.x, n.y, n.z)?;
+    }
+    for (i, face) in data.faces.iter().enumerate() {
+        write!(writer, "f")?;
+        for v in face.iter() {
+            write!(writer, " {}//{}", v + 1, i + 1)?;
+        }
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::polyhedron::Seed;
+    use crate::platonic_solid::Cube2;
+
+    #[test]
+    fn cube_obj() {
+        let cube = Cube2::new(1.0).polyhedron().normalize();
+        let mut obj: Vec<u8> = Vec::new();
+        write(&cube, &mut obj).unwrap();
+
+        let obj = String::from_utf8(obj).unwrap();
+        let count = |prefix: &str| obj.lines().filter(|l| l.starts_with(prefix)).count();
+        assert!(count("v ") == 8);
+        assert!(count("vn ") == 6);
+        assert!(count("f ") == 6);
+        assert!(obj.lines().last().unwrap().split_whitespace().count() == 5);
+    }
+}
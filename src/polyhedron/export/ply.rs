@@ -0,0 +1,68 @@
+//! ASCII [PLY](https://en.wikipedia.org/wiki/PLY_(file_format)) export. Each face carries
+//! its colour and degree as extra properties so the topology can be picked apart in tools
+//! such as MeshLab.
+use std::io::{self, Write};
+
+use crate::polyhedron::VertexAndFaceOps;
+
+/// Write the vertices and faces. `colour` is given the index and vertex indexes of each
+/// face and returns its colour with each channel from zero to one.
+pub fn write<P, F, W>(polyhedron: &P, colour: F, writer: &mut W) -> io::Result<()>
+where
+    P: VertexAndFaceOps,
+    F: Fn(usize, &[usize]) -> [f32; 3],
+    W: Write,
+{
+    let (vertices, faces) = polyhedron.vertices_and_faces();
+
+    writeln!(writer, "ply")?;
+    writeln!(writer, "format ascii 1.0")?;
+    writeln!(writer, "comment polyorb")?;
+    writeln!(writer, "element vertex {}", vertices.len())?;
+    writeln!(writer, "property double x")?;
+    writeln!(writer, "property double y")?;
+    writeln!(writer, "property double z")?;
+    writeln!(writer, "element face {}", faces.len())?;
+    writeln!(writer, "property list uchar uint vertex_indices")?;
+    writeln!(writer, "property uchar red")?;
+    writeln!(writer, "property uchar green")?;
+    writeln!(writer, "property uchar blue")?;
+    writeln!(writer, "property uint degree")?;
+    writeln!(writer, "end_header")?;
+
+    for v in vertices.iter() {
+        writeln!(writer, "{} {} {}", v.x, v.y, v.z)?;
+    }
+    for (i, face) in faces.iter().enumerate() {
+        write!(writer, "{}", face.len())?;
+        for v in face.iter() {
+            write!(writer, " {}", v)?;
+        }
+        for c in colour(i, face).iter() {
+            write!(writer, " {}", (c.clamp(0.0, 1.0) * 255.0).round() as u8)?;
+        }
+        writeln!(writer, " {}", face.len())?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::polyhedron::Specification;
+
+    #[test]
+    fn truncated_icosahedron_ply() {
+        let ti = Specification::parse("tI").unwrap().produce();
+        let mut ply: Vec<u8> = Vec::new();
+        let colour = |_, face: &[usize]| if face.len() == 5 { [1.0, 0.0, 0.0] } else { [1.0; 3] };
+        write(&ti, colour, &mut ply).unwrap();
+
+        let ply = String::from_utf8(ply).unwrap();
+        let body: Vec<&str> = ply.lines().skip_while(|l| *l != "end_header").skip(1).collect();
+        assert!(body.len() == 60 + 32);
+        assert!(body.iter().filter(|l| l.ends_with("255 0 0 5")).count() == 12);
+        assert!(body.iter().filter(|l| l.ends_with("255 255 255 6")).count() == 20);
+    }
+}
@@ -0,0 +1,98 @@
+//! [STL](https://en.wikipedia.org/wiki/STL_(file_format)) export. STL only knows about
+//! triangles so each face is fanned out into triangles that share the face normal. Binary
+//! is what most slicers for 3D printing expect. ASCII is easier to eyeball.
+use std::io::{self, Write};
+
+use cgmath::{Point3, Vector3};
+
+use crate::polyhedron::{Polyhedron, VtFcNm};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    Ascii,
+    Binary,
+}
+
+pub fn write<W: Write>(
+    polyhedron: &Polyhedron<VtFcNm>, format: Format, writer: &mut W,
+) -> io::Result<()> {
+    let triangles: Vec<(Vector3<f64>, [Point3<f64>; 3])> = polyhedron
+        .faces()
+        .flat_map(|face| {
+            let normal = face.normal();
+            face.triangles()
+                .map(|triangle| (normal, triangle))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    match format {
+        Format::Ascii => write_ascii(&triangles, writer),
+        Format::Binary => write_binary(&triangles, writer),
+    }
+}
+
+fn write_ascii<W: Write>(
+    triangles: &[(Vector3<f64>, [Point3<f64>; 3])], writer: &mut W,
+) -> io::Result<()> {
+    writeln!(writer, "solid polyhedron")?;
+    for (n, triangle) in triangles.iter() {
+        writeln!(writer, "facet normal {:e} {:e} {:e}", n.x, n.y, n.z)?;
+        writeln!(writer, "    outer loop")?;
+        for v in triangle.iter() {
+            writeln!(writer, "        vertex {:e} {:e} {:e}", v.x, v.y, v.z)?;
+        }
+        writeln!(writer, "    endloop")?;
+        writeln!(writer, "endfacet")?;
+    }
+    writeln!(writer, "endsolid polyhedron")?;
+
+    Ok(())
+}
+
+/// An 80 byte header that is ignored, the triangle count and then each triangle as twelve
+/// little endian `f32`s followed by a two byte attribute count which is always zero.
+fn write_binary<W: Write>(
+    triangles: &[(Vector3<f64>, [Point3<f64>; 3])], writer: &mut W,
+) -> io::Result<()> {
+    let mut header = [0u8; 80];
+    let title = b"polyorb";
+    header[..title.len()].copy_from_slice(title);
+    writer.write_all(&header)?;
+    writer.write_all(&(triangles.len() as u32).to_le_bytes())?;
+
+    for (n, triangle) in triangles.iter() {
+        let [v1, v2, v3] = triangle;
+        let coordinates = [
+            n.x, n.y, n.z, v1.x, v1.y, v1.z, v2.x, v2.y, v2.z, v3.x, v3.y, v3.z,
+        ];
+        for c in coordinates.iter() {
+            writer.write_all(&(*c as f32).to_le_bytes())?;
+        }
+        writer.write_all(&0u16.to_le_bytes())?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::polyhedron::Seed;
+    use crate::platonic_solid::Dodecahedron2;
+
+    #[test]
+    fn dodecahedron_stl() {
+        let dodecahedron = Dodecahedron2::new(1.0).polyhedron().normalize();
+
+        let mut binary: Vec<u8> = Vec::new();
+        write(&dodecahedron, Format::Binary, &mut binary).unwrap();
+        assert!(binary.len() == 84 + 36 * 50);
+        assert!(binary[80..84] == 36u32.to_le_bytes());
+
+        let mut ascii: Vec<u8> = Vec::new();
+        write(&dodecahedron, Format::Ascii, &mut ascii).unwrap();
+        let ascii = String::from_utf8(ascii).unwrap();
+        assert!(ascii.lines().filter(|l| l.starts_with("facet")).count() == 36);
+    }
+}
@@ -0,0 +1,280 @@
+//! Unfold a polyhedron into a flat net and write it as SVG for papercraft.
+//!
+//! A spanning tree is grown breadth first over the faces starting from the first face. Each
+//! face is flattened into its own plane and then rotated about the edge it shares with its
+//! parent in the tree so that it hangs off the parent. Tree edges become fold lines and
+//! every other edge is a cut line. Breadth first keeps the net compact but there is no
+//! guarantee that faces don't overlap on less well behaved polyhedra.
+//!
+//! A polyhedron made of separate pieces gets a net for each piece. They're laid out in a
+//! row from left to right.
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, Write};
+
+use cgmath::prelude::*;
+use cgmath::{Point2, Vector2};
+
+use crate::geop;
+use crate::polyhedron::VertexAndFaceOps;
+
+/// A face laid out on the net with a point for each of its vertices in order.
+type Placed = Vec<Point2<f64>>;
+
+/// Write the net. `scale` is how many millimetres one unit of the polyhedron is.
+pub fn write<P: VertexAndFaceOps, W: Write>(
+    polyhedron: &P, scale: f64, writer: &mut W,
+) -> io::Result<()> {
+    let (placed, folds) = unfold(polyhedron);
+
+    let (min, max) = placed
+        .iter()
+        .flat_map(|face| face.iter())
+        .fold(
+            ([f64::MAX; 2], [f64::MIN; 2]),
+            |(min, max), p| {
+                ([min[0].min(p.x), min[1].min(p.y)], [max[0].max(p.x), max[1].max(p.y)])
+            },
+        );
+    let (min, max) = (Point2::from(min), Point2::from(max));
+    let margin = 5.0;
+    let to_mm = |p: Point2<f64>| {
+        ((p.x - min.x) * scale + margin, (max.y - p.y) * scale + margin)
+    };
+    let width = (max.x - min.x) * scale + 2.0 * margin;
+    let height = (max.y - min.y) * scale + 2.0 * margin;
+
+    writeln!(
+        writer,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" \
+         width=\"{w}mm\" height=\"{h}mm\" viewBox=\"0 0 {w} {h}\">",
+        w = width,
+        h = height,
+    )?;
+    writeln!(writer, "<style>")?;
+    writeln!(writer, ".face {{ fill: #f4f4f4; stroke: none; }}")?;
+    writeln!(writer, ".cut {{ stroke: #000000; stroke-width: 0.2; }}")?;
+    writeln!(writer, ".fold {{ stroke: #808080; stroke-width: 0.2; stroke-dasharray: 1 1; }}")?;
+    writeln!(writer, "</style>")?;
+
+    for face in placed.iter() {
+        let points: Vec<String> = face
+            .iter()
+            .map(|p| {
+                let (x, y) = to_mm(*p);
+                format!("{},{}", x, y)
+            })
+            .collect();
+        writeln!(writer, r#"<polygon class="face" points="{}"/>"#, points.join(" "))?;
+    }
+
+    // Fold lines are drawn once from the parent side. Cut lines are drawn on both of the
+    // faces they separate since those end up in different places on the net.
+    for (f_index, face) in placed.iter().enumerate() {
+        for i in 0..face.len() {
+            let fold = folds.get(&(f_index, i));
+            if fold == Some(&false) {
+                continue;
+            }
+
+            let (x1, y1) = to_mm(face[i]);
+            let (x2, y2) = to_mm(face[(i + 1) % face.len()]);
+            writeln!(
+                writer,
+                r#"<line class="{}" x1="{}" y1="{}" x2="{}" y2="{}"/>"#,
+                if fold.is_some() { "fold" } else { "cut" }, x1, y1, x2, y2,
+            )?;
+        }
+    }
+
+    writeln!(writer, "</svg>")?;
+
+    Ok(())
+}
+
+/// Lay out each face on the plane. Also returns the fold edges keyed by face index and the
+/// position of the edge in the face. Each fold edge is in there from both of its faces with
+/// `true` for the parent side.
+fn unfold<P: VertexAndFaceOps>(
+    polyhedron: &P,
+) -> (Vec<Placed>, HashMap<(usize, usize), bool>) {
+    let (vertices, faces) = polyhedron.vertices_and_faces();
+
+    // The face and position in that face of each directed edge.
+    let edges: HashMap<(usize, usize), (usize, usize)> = faces
+        .iter()
+        .enumerate()
+        .flat_map(|(f_index, face)| {
+            (0..face.len())
+                .map(move |i| ((face[i], face[(i + 1) % face.len()]), (f_index, i)))
+        })
+        .collect();
+
+    let flatten = |face: &[usize]| -> Placed {
+        let origin = vertices[face[0]];
+        let normal = geop::triangle_normal(origin, vertices[face[1]], vertices[face[2]]);
+        let u = (vertices[face[1]] - origin).normalize();
+        let w = normal.cross(u);
+
+        face.iter()
+            .map(|v| {
+                let d = vertices[*v] - origin;
+                Point2::new(d.dot(u), d.dot(w))
+            })
+            .collect()
+    };
+
+    let mut placed: Vec<Option<Placed>> = vec![None; faces.len()];
+    let mut folds: HashMap<(usize, usize), bool> = HashMap::new();
+    let mut queue: VecDeque<usize> = VecDeque::new();
+    let mut right = 0.0;
+
+    // Any face not reached from the faces before it starts another piece.
+    for root in 0..faces.len() {
+        if placed[root].is_some() {
+            continue;
+        }
+        placed[root] = Some(flatten(&faces[root]));
+        queue.push_back(root);
+        let mut piece = vec![root];
+
+        while let Some(f_index) = queue.pop_front() {
+            let face = &faces[f_index];
+            let parent = placed[f_index].clone().unwrap();
+
+            for i in 0..face.len() {
+                let (a, b) = (face[i], face[(i + 1) % face.len()]);
+                let (g_index, j) = match edges.get(&(b, a)) {
+                    Some(across) => *across,
+                    None => continue,
+                };
+                if placed[g_index].is_some() {
+                    continue;
+                }
+
+                // The child traverses the shared edge from `b` to `a`. Rotate and move it so
+                // that its copy of the edge lands on the parent's.
+                let child = flatten(&faces[g_index]);
+                let (from_b, from_a) = (child[j], child[(j + 1) % child.len()]);
+                let (to_a, to_b) = (parent[i], parent[(i + 1) % parent.len()]);
+                let angle = angle_of(to_a - to_b) - angle_of(from_a - from_b);
+                let (sin, cos) = angle.sin_cos();
+
+                placed[g_index] = Some(
+                    child
+                        .iter()
+                        .map(|p| {
+                            let d = *p - from_b;
+                            to_b + Vector2::new(d.x * cos - d.y * sin, d.x * sin + d.y * cos)
+                        })
+                        .collect()
+                );
+                folds.insert((f_index, i), true);
+                folds.insert((g_index, j), false);
+                queue.push_back(g_index);
+                piece.push(g_index);
+            }
+        }
+
+        // Move the piece along to the right of the ones before with a gap of a tenth of its
+        // height, its bottom level with theirs.
+        let (min, max) = piece
+            .iter()
+            .flat_map(|f_index| placed[*f_index].as_ref().unwrap().iter())
+            .fold(
+                ([f64::MAX; 2], [f64::MIN; 2]),
+                |(min, max), p| {
+                    ([min[0].min(p.x), min[1].min(p.y)], [max[0].max(p.x), max[1].max(p.y)])
+                },
+            );
+        let gap = if root == 0 { 0.0 } else { (max[1] - min[1]) / 10.0 };
+        let shift = Vector2::new(right + gap - min[0], -min[1]);
+        for f_index in piece.iter() {
+            for p in placed[*f_index].as_mut().unwrap().iter_mut() {
+                *p += shift;
+            }
+        }
+        right += gap + max[0] - min[0];
+    }
+
+    let placed = placed
+        .into_iter()
+        .map(|face| face.unwrap())
+        .collect();
+
+    (placed, folds)
+}
+
+fn angle_of(v: Vector2<f64>) -> f64 {
+    v.y.atan2(v.x)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cgmath::{Point3, Vector3};
+    use crate::polyhedron::{Polyhedron, Seed};
+    use crate::platonic_solid::Cube2;
+
+    #[test]
+    fn cube_net() {
+        let cube = Cube2::new(1.0).polyhedron();
+        let (placed, folds) = unfold(&cube);
+
+        // Faces stay the same shape.
+        for face in placed.iter() {
+            for i in 0..4 {
+                assert!((face[i].distance(face[(i + 1) % 4]) - 1.0).abs() < 1e-9);
+            }
+        }
+
+        // No face is folded back on top of another.
+        let centers: Vec<Point2<f64>> = placed
+            .iter()
+            .map(|face| Point2::centroid(face))
+            .collect();
+        for (i, c1) in centers.iter().enumerate() {
+            assert!(centers[i + 1..].iter().all(|c2| c1.distance(*c2) > 0.5));
+        }
+
+        // Six faces need five folds.
+        assert!(folds.values().filter(|parent| **parent).count() == 5);
+
+        let mut svg: Vec<u8> = Vec::new();
+        write(&cube, 10.0, &mut svg).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+        assert!(svg.matches(r#"class="fold""#).count() == 5);
+        assert!(svg.matches(r#"class="cut""#).count() == 14);
+    }
+
+    #[test]
+    fn net_for_each_piece() {
+        // Two tetrahedra apart from each other.
+        let corners = [
+            Point3::new(1.0, 1.0, 1.0),
+            Point3::new(1.0, -1.0, -1.0),
+            Point3::new(-1.0, 1.0, -1.0),
+            Point3::new(-1.0, -1.0, 1.0),
+        ];
+        let vertices: Vec<Point3<f64>> = corners
+            .iter()
+            .chain(corners.iter())
+            .enumerate()
+            .map(|(i, p)| if i < 4 { *p } else { p + Vector3::new(5.0, 0.0, 0.0) })
+            .collect();
+        let faces: [&[usize]; 8] = [
+            &[0, 3, 1], &[0, 2, 3], &[0, 1, 2], &[1, 3, 2],
+            &[4, 7, 5], &[4, 6, 7], &[4, 5, 6], &[5, 7, 6],
+        ];
+        let pair = Polyhedron::new(Point3::origin(), &vertices, &faces).unwrap();
+        let (placed, folds) = unfold(&pair);
+
+        // Three folds in each piece and the second piece entirely to the right of the first.
+        assert!(folds.values().filter(|parent| **parent).count() == 6);
+        let furthest = placed[..4].iter().flatten().map(|p| p.x).fold(f64::MIN, f64::max);
+        assert!(placed[4..].iter().flatten().all(|p| p.x > furthest));
+
+        let mut svg: Vec<u8> = Vec::new();
+        write(&pair, 10.0, &mut svg).unwrap();
+        assert!(String::from_utf8(svg).unwrap().matches("<polygon").count() == 8);
+    }
+}
@@ -0,0 +1,185 @@
+//! The faces of a polyhedron kept in one flat list of vertex indexes with where each face
+//! starts, rather than a list of lists. A big polyhedron such as a high frequency Goldberg
+//! polyhedron has tens of thousands of faces and giving each its own allocation costs more
+//! memory and time than the indexes themselves.
+use std::iter::FromIterator;
+use std::ops::{Index, Range};
+
+/// The vertex indexes of each face going around it. Indexing gives a face as a slice.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Faces {
+    indexes: Vec<usize>,
+
+    /// Where each face starts in `indexes` followed by where the last one ends.
+    offsets: Vec<usize>,
+}
+
+impl Default for Faces {
+    fn default() -> Self {
+        Faces::new()
+    }
+}
+
+impl Faces {
+    pub fn new() -> Self {
+        Faces {
+            indexes: Vec::new(),
+            offsets: vec![0],
+        }
+    }
+
+    /// Room for `faces` faces with `indexes` vertex indexes between them.
+    pub fn with_capacity(faces: usize, indexes: usize) -> Self {
+        let mut offsets = Vec::with_capacity(faces + 1);
+        offsets.push(0);
+        Faces {
+            indexes: Vec::with_capacity(indexes),
+            offsets,
+        }
+    }
+
+    /// Add a face on the end.
+    pub fn push(&mut self, face: &[usize]) {
+        self.indexes.extend_from_slice(face);
+        self.offsets.push(self.indexes.len());
+    }
+
+    /// Add a face on the end as its vertex indexes come, without collecting them first.
+    pub fn push_from<I: IntoIterator<Item = usize>>(&mut self, face: I) {
+        self.indexes.extend(face);
+        self.offsets.push(self.indexes.len());
+    }
+
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, f_index: usize) -> Option<&[usize]> {
+        if f_index < self.len() {
+            Some(&self.indexes[self.range(f_index)])
+        } else {
+            None
+        }
+    }
+
+    pub fn iter(&self) -> FacesIter<'_> {
+        FacesIter {
+            faces: self,
+            f_indexes: 0..self.len(),
+        }
+    }
+
+    /// Every vertex index of every face one after the other.
+    pub fn indexes(&self) -> &[usize] {
+        &self.indexes
+    }
+
+    /// Copy out into a list of lists such as for writing out.
+    pub fn to_vecs(&self) -> Vec<Vec<usize>> {
+        self.iter().map(|face| face.to_vec()).collect()
+    }
+
+    fn range(&self, f_index: usize) -> Range<usize> {
+        self.offsets[f_index]..self.offsets[f_index + 1]
+    }
+}
+
+impl Index<usize> for Faces {
+    type Output = [usize];
+
+    fn index(&self, f_index: usize) -> &[usize] {
+        &self.indexes[self.range(f_index)]
+    }
+}
+
+impl<F: AsRef<[usize]>> Extend<F> for Faces {
+    fn extend<I: IntoIterator<Item = F>>(&mut self, faces: I) {
+        for face in faces {
+            self.push(face.as_ref());
+        }
+    }
+}
+
+impl<F: AsRef<[usize]>> FromIterator<F> for Faces {
+    fn from_iter<I: IntoIterator<Item = F>>(faces: I) -> Self {
+        let mut collected = Faces::new();
+        collected.extend(faces);
+        collected
+    }
+}
+
+impl From<Vec<Vec<usize>>> for Faces {
+    fn from(faces: Vec<Vec<usize>>) -> Self {
+        let count = faces.iter().map(|face| face.len()).sum();
+        let mut collected = Faces::with_capacity(faces.len(), count);
+        for face in faces.iter() {
+            collected.push(face);
+        }
+        collected
+    }
+}
+
+impl<'a> IntoIterator for &'a Faces {
+    type Item = &'a [usize];
+    type IntoIter = FacesIter<'a>;
+
+    fn into_iter(self) -> FacesIter<'a> {
+        self.iter()
+    }
+}
+
+/// Each face in order as a slice of vertex indexes.
+#[derive(Debug, Clone)]
+pub struct FacesIter<'a> {
+    faces: &'a Faces,
+    f_indexes: Range<usize>,
+}
+
+impl<'a> Iterator for FacesIter<'a> {
+    type Item = &'a [usize];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let faces = self.faces;
+        self.f_indexes.next().map(|f_index| &faces[f_index])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.f_indexes.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for FacesIter<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let faces = self.faces;
+        self.f_indexes.next_back().map(|f_index| &faces[f_index])
+    }
+}
+
+impl<'a> ExactSizeIterator for FacesIter<'a> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn flat_faces() {
+        let faces: Faces = vec![vec![0, 1, 2], vec![2, 1, 3, 4]].into();
+        assert!(faces.len() == 2);
+        assert!(faces[1] == [2, 1, 3, 4]);
+        assert!(faces.get(2).is_none());
+        assert!(faces.indexes() == [0, 1, 2, 2, 1, 3, 4]);
+        assert!(faces.iter().rev().map(|face| face.len()).collect::<Vec<_>>() == vec![4, 3]);
+        assert!(faces.iter().collect::<Faces>() == faces);
+
+        let mut extended = Faces::new();
+        extended.extend(vec![[0, 1, 2]]);
+        extended.push_from(vec![2, 1, 3, 4]);
+        assert!(extended == faces);
+        assert!(faces.to_vecs() == vec![vec![0, 1, 2], vec![2, 1, 3, 4]]);
+        assert!(Faces::new().is_empty());
+    }
+}
@@ -0,0 +1,103 @@
+//! Flag based polyhedron construction.
+//!
+//! Most Conway operators are much easier to express by naming each vertex of the new
+//! polyhedron after the part of the old polyhedron it came from and then listing the
+//! directed edges (flags) that go around each new face. Once all the flags have been
+//! added the faces are stitched together by following the flags from vertex to vertex.
+//! This is the approach taken by [polyHédronisme](https://levskaya.github.io/polyhedronisme/).
+use std::collections::HashMap;
+use std::iter;
+
+use cgmath::Point3;
+
+use super::{Faces, Polyhedron, VtFc};
+
+/// Name of a vertex or face of the polyhedron being built in terms of the vertices, faces
+/// and edges of the polyhedron it is being derived from. What each variant means is up to
+/// the operation using it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub (in crate::polyhedron) enum Name {
+    Vertex(usize),
+    Face(usize),
+    Edge(usize, usize),
+    FaceVertex(usize, usize),
+}
+
+/// Undirected edge name. The same name is produced regardless of vertex order.
+pub (in crate::polyhedron) fn edge(v1: usize, v2: usize) -> Name {
+    if v1 < v2 {
+        Name::Edge(v1, v2)
+    } else {
+        Name::Edge(v2, v1)
+    }
+}
+
+/// Collects named vertices and the flags of each face. The faces are kept in the order
+/// they were first referenced so that building is deterministic.
+#[derive(Debug, Clone, Default)]
+pub (in crate::polyhedron) struct Flags {
+    vertices: Vec<Point3<f64>>,
+    vertex_names: HashMap<Name, usize>,
+    faces: Vec<Vec<(usize, usize)>>,
+    face_names: HashMap<Name, usize>,
+}
+
+impl Flags {
+    pub fn new() -> Self {
+        Flags::default()
+    }
+
+    /// Add a vertex. Does nothing if a vertex with the same `name` was already added.
+    pub fn vertex(&mut self, name: Name, point: Point3<f64>) {
+        if !self.vertex_names.contains_key(&name) {
+            self.vertex_names.insert(name, self.vertices.len());
+            self.vertices.push(point);
+        }
+    }
+
+    /// Add the directed edge going `from` one vertex `to` another on `face`. Both vertices
+    /// must have already been added.
+    pub fn flag(&mut self, face: Name, from: Name, to: Name) {
+        let from = self.vertex_names[&from];
+        let to = self.vertex_names[&to];
+
+        let faces = &mut self.faces;
+        let index = *self.face_names
+            .entry(face)
+            .or_insert_with(|| {
+                faces.push(Vec::new());
+                faces.len() - 1
+            });
+
+        self.faces[index].push((from, to));
+    }
+
+    /// Stitch the flags of each face into a ring of vertex indexes.
+    pub fn build(self, center: Point3<f64>) -> Polyhedron<VtFc> {
+        let count = self.faces.iter().map(|flags| flags.len()).sum();
+        let mut faces = Faces::with_capacity(self.faces.len(), count);
+        for flags in self.faces.iter() {
+            let next: HashMap<usize, usize> = flags.iter().cloned().collect();
+            let start = flags[0].0;
+            let ring = iter::successors(Some(start), |current| {
+                let following = *next
+                    .get(current)
+                    .expect("Flags don't form a closed face.");
+                Some(following).filter(|following| *following != start)
+            });
+
+            // A ring longer than the flags never made it back to the start.
+            faces.push_from(ring.take(flags.len() + 1));
+            let face = &faces[faces.len() - 1];
+            assert!(face.len() <= flags.len(), "Flags don't form a single face.");
+        }
+
+        Polyhedron {
+            data: VtFc {
+                center,
+                vertices: self.vertices,
+                faces,
+            },
+        }
+    }
+}
@@ -0,0 +1,189 @@
+//! Geodesic subdivision of triangulated polyhedra.
+//!
+//! Each triangular face is overlaid with a triangular lattice such that getting from one
+//! face corner to the next takes `m` steps along the lattice and then `n` steps after
+//! turning 60 degrees. This is the [Goldberg-Coxeter construction](https://en.wikipedia.org/wiki/Goldberg%E2%80%93Coxeter_construction).
+//! Class I has `n` of zero, class II has `m` equal to `n` and the rest are class III.
+//!
+//! In class III the lattice triangles straddle the face edges. A lattice point outside a
+//! face is found by unfolding the face on the other side of the edge into the same plane.
+//! Every lattice point is given a key from its position relative to the original
+//! polyhedron so that it is the same no matter which face it is reached from.
+use std::collections::HashMap;
+
+use cgmath::Point3;
+use cgmath::prelude::*;
+
+use crate::geop;
+use super::{Faces, Lift, Polyhedron, VtFc};
+
+/// A lattice point. Weights are barycentric coordinates scaled up by the number of lattice
+/// triangles in each face so they stay whole numbers.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum LatticePoint {
+    /// On a vertex of the original polyhedron.
+    Corner(usize),
+
+    /// On an edge between the lower and higher numbered vertex. Has the weight of the
+    /// higher numbered vertex.
+    Edge(usize, usize, i64),
+
+    /// Inside a face. Has the weights of the first two vertices of the face.
+    Interior(usize, i64, i64),
+}
+
+impl LatticePoint {
+    fn new(f_index: usize, face: &[usize], weights: &[(usize, i64); 3]) -> Self {
+        let weight = |v: usize| weights.iter().find(|(w_v, _)| *w_v == v).unwrap().1;
+        let nonzero: Vec<(usize, i64)> = weights
+            .iter()
+            .filter(|(_, w)| *w != 0)
+            .cloned()
+            .collect();
+
+        match nonzero.as_slice() {
+            [(v, _)] => LatticePoint::Corner(*v),
+            [(v1, w1), (v2, w2)] => if v1 < v2 {
+                LatticePoint::Edge(*v1, *v2, *w2)
+            } else {
+                LatticePoint::Edge(*v2, *v1, *w1)
+            },
+            _ => LatticePoint::Interior(f_index, weight(face[0]), weight(face[1])),
+        }
+    }
+}
+
+/// Subdivide each triangular face into `m² + mn + n²` triangles. All the vertices are lifted
+/// onto the bounding sphere unless `lift` leaves them on the original faces.
+pub (in crate::polyhedron) fn subdivide(
+    p: Polyhedron<VtFc>, m: usize, n: usize, lift: Lift,
+) -> Polyhedron<VtFc> {
+    let (center, radius) = p.bounding_sphere();
+    let VtFc { vertices, faces, .. } = p.data;
+    assert!(faces.iter().all(|face| face.len() == 3), "Can only subdivide triangles.");
+
+    let (m, n) = (m as i64, n as i64);
+    let t = m * m + m * n + n * n;
+
+    // Which face each directed edge belongs to. The face across an edge is found by
+    // looking up the edge going the other way.
+    let edge_faces: HashMap<(usize, usize), usize> = faces
+        .iter()
+        .enumerate()
+        .flat_map(|(f_index, face)| {
+            (0..3).map(move |i| ((face[i], face[(i + 1) % 3]), f_index))
+        })
+        .collect();
+    let across = |v1: usize, v2: usize| -> (usize, usize) {
+        let f_index = edge_faces[&(v2, v1)];
+        let opposite = *faces[f_index]
+            .iter()
+            .find(|v| **v != v1 && **v != v2)
+            .unwrap();
+
+        (f_index, opposite)
+    };
+
+    let mut points: HashMap<LatticePoint, usize> = HashMap::new();
+    let mut new_vertices: Vec<Point3<f64>> = Vec::new();
+    let mut new_faces = Faces::new();
+
+    for (f_index, face) in faces.iter().enumerate() {
+        let (a, b, c) = (face[0], face[1], face[2]);
+
+        // Lattice coordinates `(i, j)` are `i` steps along and `j` steps at 60 degrees.
+        // Corner `a` is at `(0, 0)`, `b` is at `(m, n)` and `c` is at `(-n, m + n)`. Solving
+        // for the weights of `b` and `c` gives the `alpha` and `beta` below.
+        let weights = |(i, j): (i64, i64)| -> (i64, i64) {
+            (i * (m + n) + n * j, m * j - n * i)
+        };
+
+        // A lattice triangle belongs to the face its centroid is in. When the centroid is on
+        // a face edge the triangle goes to whichever face has the edge going from the lower
+        // numbered vertex to the higher so that it isn't added twice.
+        let owns = |triangle: &[(i64, i64); 3]| -> bool {
+            let (alpha, beta) = triangle
+                .iter()
+                .map(|point| weights(*point))
+                .fold((0, 0), |(sa, sb), (wa, wb)| (sa + wa, sb + wb));
+
+            if alpha < 0 || beta < 0 || alpha + beta > 3 * t {
+                false
+            } else if beta == 0 {
+                a < b
+            } else if alpha + beta == 3 * t {
+                b < c
+            } else if alpha == 0 {
+                c < a
+            } else {
+                true
+            }
+        };
+
+        for i in -n..=m {
+            for j in 0..=(m + n) {
+                let up = [(i, j), (i + 1, j), (i, j + 1)];
+                let down = [(i + 1, j), (i + 1, j + 1), (i, j + 1)];
+
+                for triangle in [up, down].iter().filter(|triangle| owns(triangle)) {
+                    let new_face = triangle
+                        .iter()
+                        .map(|point| {
+                            let (alpha, beta) = weights(*point);
+                            let (owner, weights) = if beta < 0 {
+                                let (g, d) = across(a, b);
+                                (g, [(a, t - alpha), (b, alpha + beta), (d, -beta)])
+                            } else if alpha + beta > t {
+                                let (g, e) = across(b, c);
+                                (g, [(b, t - beta), (c, t - alpha), (e, alpha + beta - t)])
+                            } else if alpha < 0 {
+                                let (g, f) = across(c, a);
+                                (g, [(c, alpha + beta), (a, t - beta), (f, -alpha)])
+                            } else {
+                                (f_index, [(a, t - alpha - beta), (b, alpha), (c, beta)])
+                            };
+                            assert!(
+                                weights.iter().all(|(_, w)| *w >= 0),
+                                "Lattice point is more than one face away.",
+                            );
+
+                            let key = LatticePoint::new(owner, &faces[owner], &weights);
+                            *points
+                                .entry(key)
+                                .or_insert_with(|| {
+                                    let point = weights
+                                        .iter()
+                                        .fold(Point3::new(0.0, 0.0, 0.0), |s, (v, w)| {
+                                            s + (vertices[*v] - center) * (*w as f64)
+                                        });
+                                    let point = Point3::new(
+                                        point.x / t as f64,
+                                        point.y / t as f64,
+                                        point.z / t as f64,
+                                    );
+
+                                    let point = match lift {
+                                        Lift::Sphere => {
+                                            geop::point_line_lengthen(&point, radius)
+                                        },
+                                        Lift::Flat => point,
+                                    };
+                                    new_vertices.push(center + point.to_vec());
+                                    new_vertices.len() - 1
+                                })
+                        });
+
+                    new_faces.push_from(new_face);
+                }
+            }
+        }
+    }
+
+    Polyhedron {
+        data: VtFc {
+            center,
+            vertices: new_vertices,
+            faces: new_faces,
+        }
+    }
+}
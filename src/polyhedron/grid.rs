@@ -0,0 +1,317 @@
+//! A Goldberg polyhedron as a map of hexagonal cells, such as the world of a game. Twelve
+//! of the cells are pentagons however big the map.
+//!
+//! Cells are the faces of the polyhedron and go by their face index. Building the same
+//! class of polyhedron always lists the faces in the same order, so a cell keeps its index
+//! from one run to the next and can be saved.
+//!
+//! A place on the sphere is in the cell whose face the line out from the center through
+//! the place crosses. Latitude and longitude are as in `geop::Spherical`.
+//!
+//! With the `petgraph` feature the cells can be turned into a graph to find paths across
+//! the map with the algorithms in petgraph such as A* and Dijkstra.
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::ops::Range;
+
+use cgmath::{Point3, Rad, Vector3};
+use cgmath::prelude::*;
+#[cfg(feature = "petgraph")]
+use petgraph::graph::UnGraph;
+
+use super::{goldberg, Adjacency, OpError, Polyhedron, VertexAndFaceOps, VtFc};
+use crate::geop::{self, Spherical};
+
+#[derive(Debug, Clone)]
+pub struct GoldbergGrid {
+    polyhedron: Polyhedron<VtFc>,
+    adjacency: Adjacency,
+    centroids: Vec<Point3<f64>>,
+    normals: Vec<Vector3<f64>>,
+}
+
+impl GoldbergGrid {
+    /// The cells of GP(m, n). See `polyhedron::goldberg`.
+    pub fn new(m: usize, n: usize, radius: f64) -> Result<Self, OpError> {
+        Ok(GoldbergGrid::from_polyhedron(goldberg(m, n, radius)?))
+    }
+
+    /// Use the faces of any closed polyhedron as cells, such as one built with Conway
+    /// notation like `cdD`.
+    pub fn from_polyhedron(polyhedron: Polyhedron<VtFc>) -> Self {
+        let adjacency = polyhedron.adjacency();
+        let (vertices, faces) = polyhedron.vertices_and_faces();
+        let corners = |face: &[usize]| -> Vec<Point3<f64>> {
+            face.iter().map(|v| vertices[*v]).collect()
+        };
+
+        let centroids = faces
+            .iter()
+            .map(|face| geop::convex_planar_polygon_centroid(&corners(face)))
+            .collect();
+
+        // Newell's method so faces that aren't quite flat still get a fair normal.
+        let normals = faces
+            .iter()
+            .map(|face| {
+                let corners = corners(face);
+                let next = |i: usize| corners[(i + 1) % corners.len()].to_vec();
+                (0..corners.len())
+                    .map(|i| corners[i].to_vec().cross(next(i)))
+                    .fold(Vector3::zero(), |sum, n| sum + n)
+                    .normalize()
+            })
+            .collect();
+
+        GoldbergGrid { polyhedron, adjacency, centroids, normals }
+    }
+
+    pub fn cell_count(&self) -> usize {
+        self.centroids.len()
+    }
+
+    /// Every cell.
+    pub fn cells(&self) -> Range<usize> {
+        0..self.cell_count()
+    }
+
+    /// The cells sharing an edge with `cell`. They go anticlockwise around it seen from
+    /// outside, the first across the edge from its first corner to its second.
+    pub fn neighbors(&self, cell: usize) -> &[usize] {
+        self.adjacency.face_neighbors(cell)
+    }
+
+    pub fn is_pentagon(&self, cell: usize) -> bool {
+        self.neighbors(cell).len() == 5
+    }
+
+    pub fn centroid(&self, cell: usize) -> Point3<f64> {
+        self.centroids[cell]
+    }
+
+    /// Pointing outwards with a length of one.
+    pub fn normal(&self, cell: usize) -> Vector3<f64> {
+        self.normals[cell]
+    }
+
+    /// Where the centroid of `cell` is seen from the center.
+    pub fn location(&self, cell: usize) -> Spherical {
+        Spherical::from_cartesian(&Point3::from_vec(self.centroid(cell) - self.center()))
+    }
+
+    /// The cell at a place on the sphere.
+    pub fn cell_at(&self, latitude: Rad<f64>, longitude: Rad<f64>) -> usize {
+        self.cell_towards(Spherical::new(latitude, longitude, 1.0).to_cartesian().to_vec())
+    }
+
+    /// The cell the line out from the center going in `direction` crosses.
+    pub fn cell_towards(&self, direction: Vector3<f64>) -> usize {
+        // The cell with the nearest centroid almost always has it. Close to a corner it can
+        // be a neighbour when the cells aren't all the same size.
+        let center = self.center();
+        let nearest = self
+            .cells()
+            .map(|cell| (cell, (self.centroid(cell) - center).normalize().dot(direction)))
+            .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+            .map(|(cell, _)| cell)
+            .unwrap_or(0);
+
+        self.disk(nearest, 1)
+            .into_iter()
+            .find(|cell| self.contains(*cell, direction))
+            .unwrap_or(nearest)
+    }
+
+    /// Whether the line going in `direction` crosses the face of `cell`. It has to be on the
+    /// inside of the plane through the center and each edge.
+    fn contains(&self, cell: usize, direction: Vector3<f64>) -> bool {
+        let center = self.center();
+        let corners = self.corners(cell);
+        (0..corners.len()).all(|i| {
+            let (c1, c2) = (corners[i] - center, corners[(i + 1) % corners.len()] - center);
+            c1.cross(c2).dot(direction) >= 0.0
+        })
+    }
+
+    /// A node for each cell joined to its neighbours. The node index is the cell and so is
+    /// the node weight. Each edge is weighted by the distance over the sphere between the
+    /// centroids of the two cells at the radius of the polyhedron.
+    #[cfg(feature = "petgraph")]
+    pub fn graph(&self) -> UnGraph<usize, f64> {
+        let center = self.center();
+        let radius = self.polyhedron.bounding_sphere().1;
+        let mut graph = UnGraph::with_capacity(self.cell_count(), self.cell_count() * 3);
+        for cell in self.cells() {
+            graph.add_node(cell);
+        }
+        for cell in self.cells() {
+            for neighbor in self.neighbors(cell).iter().filter(|n| **n > cell) {
+                let distance = geop::great_circle_distance(
+                    &Point3::from_vec(self.centroid(cell) - center),
+                    &Point3::from_vec(self.centroid(*neighbor) - center),
+                    radius,
+                );
+                graph.add_edge((cell as u32).into(), (*neighbor as u32).into(), distance);
+            }
+        }
+
+        graph
+    }
+
+    fn center(&self) -> Point3<f64> {
+        self.polyhedron.data.center
+    }
+
+    /// The corners of `cell` going anticlockwise seen from outside.
+    pub fn corners(&self, cell: usize) -> Vec<Point3<f64>> {
+        let (vertices, faces) = self.polyhedron.vertices_and_faces();
+        faces[cell].iter().map(|v| vertices[*v]).collect()
+    }
+
+    /// The cells exactly `k` steps from `cell`, stepping across an edge each time. Nearer
+    /// cells come first in `disk` so this is in the same order as the end of it.
+    pub fn ring(&self, cell: usize, k: usize) -> Vec<usize> {
+        self.rings(cell, k).pop().unwrap_or_default()
+    }
+
+    /// The cells up to `k` steps from `cell` from nearest to furthest, starting with `cell`.
+    pub fn disk(&self, cell: usize, k: usize) -> Vec<usize> {
+        self.rings(cell, k).concat()
+    }
+
+    /// Breadth first out to `k` steps. One list for each step taken.
+    fn rings(&self, cell: usize, k: usize) -> Vec<Vec<usize>> {
+        let mut steps: Vec<Option<usize>> = vec![None; self.cell_count()];
+        let mut rings: Vec<Vec<usize>> = vec![Vec::new(); k + 1];
+        let mut pending = VecDeque::new();
+        steps[cell] = Some(0);
+        pending.push_back(cell);
+
+        while let Some(current) = pending.pop_front() {
+            let step = steps[current].unwrap();
+            rings[step].push(current);
+            if step == k {
+                continue;
+            }
+            for neighbor in self.neighbors(current) {
+                if steps[*neighbor].is_none() {
+                    steps[*neighbor] = Some(step + 1);
+                    pending.push_back(*neighbor);
+                }
+            }
+        }
+
+        rings
+    }
+
+    pub fn polyhedron(&self) -> &Polyhedron<VtFc> {
+        &self.polyhedron
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cells_of_gp_2_1() {
+        let grid = GoldbergGrid::new(2, 1, 1.0).unwrap();
+        assert!(grid.cell_count() == 72);
+        assert!(grid.cells().filter(|cell| grid.is_pentagon(*cell)).count() == 12);
+
+        for cell in grid.cells() {
+            let neighbors = grid.neighbors(cell);
+            assert!(neighbors.len() == if grid.is_pentagon(cell) { 5 } else { 6 });
+            assert!(neighbors.iter().all(|n| grid.neighbors(*n).contains(&cell)));
+
+            let outwards = grid.centroid(cell).to_vec().normalize();
+            assert!(grid.normal(cell).dot(outwards) > 0.99);
+        }
+
+        // The same cells every time.
+        let again = GoldbergGrid::new(2, 1, 1.0).unwrap();
+        assert!(grid.cells().all(|cell| grid.centroid(cell) == again.centroid(cell)));
+    }
+
+    #[test]
+    fn cells_at_places() {
+        let grid = GoldbergGrid::new(4, 2, 1.0).unwrap();
+        for cell in grid.cells() {
+            let location = grid.location(cell);
+            assert!(grid.cell_at(*location.latitude(), *location.longitude()) == cell);
+        }
+
+        // Either side of the edge between two neighbours.
+        let cell = 17;
+        let corners = grid.corners(cell);
+        let neighbor = grid.neighbors(cell)[0];
+        let edge = corners[0].midpoint(corners[1]).to_vec();
+        let inward = (grid.centroid(cell).to_vec() - edge) * 0.01;
+        assert!(grid.cell_towards(edge + inward) == cell);
+        assert!(grid.cell_towards(edge - inward) == neighbor);
+
+        let north = grid.cell_at(Rad(std::f64::consts::FRAC_PI_2), Rad(0.0));
+        assert!(grid.contains(north, Vector3::unit_z()));
+    }
+
+    #[cfg(feature = "petgraph")]
+    #[test]
+    fn shortest_path() {
+        use std::f64::consts::PI;
+        use petgraph::algo::astar;
+        use petgraph::graph::NodeIndex;
+
+        let grid = GoldbergGrid::new(3, 1, 2.0).unwrap();
+        let graph = grid.graph();
+        assert!(graph.node_count() == grid.cell_count());
+        assert!(graph.edge_count() == grid.polyhedron().edge_count());
+
+        // Over to the cell on the other side. The path can't be shorter than the straight
+        // way over the sphere.
+        let start = grid.location(0);
+        let opposite = grid.cell_at(-*start.latitude(), *start.longitude() + Rad(PI));
+        let on_sphere = |cell: usize| {
+            Point3::from_vec(grid.centroid(cell).to_vec().normalize() * 2.0)
+        };
+        let remaining = |cell| {
+            geop::great_circle_distance(&on_sphere(cell), &on_sphere(opposite), 2.0)
+        };
+        let (length, path) = astar(
+            &graph,
+            NodeIndex::new(0),
+            |node| node.index() == opposite,
+            |edge| *edge.weight(),
+            |node| remaining(node.index()),
+        ).unwrap();
+
+        assert!(path.first() == Some(&NodeIndex::new(0)));
+        assert!(path.last() == Some(&NodeIndex::new(opposite)));
+        assert!(path.windows(2).all(|step| {
+            grid.neighbors(step[0].index()).contains(&step[1].index())
+        }));
+        assert!(length >= remaining(0) - 1e-9);
+        assert!(remaining(0) > 0.9 * 2.0 * PI);
+    }
+
+    #[test]
+    fn rings_and_disks() {
+        let grid = GoldbergGrid::new(5, 0, 1.0).unwrap();
+        let pentagon = grid.cells().find(|cell| grid.is_pentagon(*cell)).unwrap();
+        let hexagon = grid.cells().find(|cell| {
+            grid.disk(*cell, 2).iter().all(|near| !grid.is_pentagon(*near))
+        }).unwrap();
+
+        assert!(grid.ring(hexagon, 0) == vec![hexagon]);
+        assert!(grid.ring(hexagon, 1) == grid.neighbors(hexagon));
+        assert!(grid.ring(hexagon, 2).len() == 12);
+        assert!(grid.disk(hexagon, 2).len() == 19);
+        assert!(grid.ring(pentagon, 1).len() == 5);
+        assert!(grid.disk(pentagon, 1).len() == 6);
+
+        // Far enough covers the whole sphere once and nothing is left further out.
+        let mut all = grid.disk(pentagon, grid.cell_count());
+        all.sort();
+        assert!(all == grid.cells().collect::<Vec<usize>>());
+        assert!(grid.ring(pentagon, grid.cell_count()).is_empty());
+    }
+}
@@ -0,0 +1,2 @@
+//! Read polyhedra in from formats other programs write.
+pub mod obj;
@@ -0,0 +1,199 @@
+//! [Wavefront OBJ](https://en.wikipedia.org/wiki/Wavefront_.obj_file) import as a `Seed` so
+//! Conway operations can be run on any closed mesh.
+//!
+//! Only the `v` and `f` lines are read. Texture coordinates and normals on the face lines
+//! are skipped along with every other kind of line. All objects and groups in the file are
+//! merged into the one polyhedron.
+//!
+//! The mesh is then checked and centered the same as a `CustomSeed`.
+use std::{error, fmt};
+use std::io::{self, BufRead};
+
+use cgmath::Point3;
+
+use crate::polyhedron::{CustomSeed, Polyhedron, SeedError, VtFc, Seed, SeedSolid};
+
+/// A polyhedron read in from an OBJ file.
+#[derive(Debug, Clone)]
+pub struct ObjSeed {
+    seed: CustomSeed,
+}
+
+impl Seed for ObjSeed {
+    fn solid(&self) -> SeedSolid {
+        SeedSolid::Custom
+    }
+
+    fn polyhedron(&self) -> Polyhedron<VtFc> {
+        self.seed.polyhedron()
+    }
+}
+
+/// Read a closed manifold mesh.
+pub fn read<R: BufRead>(reader: R) -> Result<ObjSeed, ImportError> {
+    let mut vertices: Vec<Point3<f64>> = Vec::new();
+    let mut faces: Vec<Vec<usize>> = Vec::new();
+
+    for (number, line) in reader.lines().enumerate() {
+        let line = line?;
+        let number = number + 1;
+        let mut tokens = line.split_whitespace();
+
+        match tokens.next() {
+            Some("v") => {
+                let coordinates: Vec<f64> = tokens
+                    .take(3)
+                    .map(|t| t.parse::<f64>().map_err(|_| ImportError::Parse(number)))
+                    .collect::<Result<_, _>>()?;
+                if coordinates.len() != 3 {
+                    return Err(ImportError::Parse(number));
+                }
+                vertices.push(Point3::new(coordinates[0], coordinates[1], coordinates[2]));
+            },
+            Some("f") => {
+                // Indexes start from one. Negative indexes count back from the latest vertex.
+                let face: Vec<usize> = tokens
+                    .map(|t| {
+                        let index: i64 = t
+                            .split('/')
+                            .next()
+                            .and_then(|i| i.parse().ok())
+                            .ok_or(ImportError::Parse(number))?;
+                        let index = match index {
+                            i if i > 0 => i - 1,
+                            i if i < 0 => vertices.len() as i64 + i,
+                            _ => return Err(ImportError::InvalidIndex(number)),
+                        };
+                        if index < 0 || index >= vertices.len() as i64 {
+                            return Err(ImportError::InvalidIndex(number));
+                        }
+                        Ok(index as usize)
+                    })
+                    .collect::<Result<_, _>>()?;
+                if face.len() < 3 {
+                    return Err(ImportError::FaceTooSmall(number));
+                }
+                faces.push(face);
+            },
+            _ => (),
+        }
+    }
+
+    let seed = CustomSeed::new(vertices, faces).map_err(|e| match e {
+        SeedError::NoFaces => ImportError::NoFaces,
+        SeedError::NotManifold(v1, v2) => ImportError::NotManifold(v1 + 1, v2 + 1),
+        SeedError::NotClosed(v1, v2) => ImportError::NotClosed(v1 + 1, v2 + 1),
+        SeedError::FaceTooSmall(_) | SeedError::InvalidIndex(_, _) => {
+            unreachable!("Faces are checked as they're read.")
+        },
+    })?;
+
+    Ok(ObjSeed { seed })
+}
+
+/// Problems reading an OBJ file. Line numbers start at one as do vertex numbers so they
+/// match the file.
+#[derive(Debug)]
+pub enum ImportError {
+    Io(io::Error),
+    Parse(usize),
+    InvalidIndex(usize),
+    FaceTooSmall(usize),
+    NoFaces,
+    NotManifold(usize, usize),
+    NotClosed(usize, usize),
+}
+
+impl From<io::Error> for ImportError {
+    fn from(e: io::Error) -> Self {
+        ImportError::Io(e)
+    }
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImportError::Io(e) => write!(f, "Couldn't read OBJ: {}", e),
+            ImportError::Parse(line) => write!(f, "Malformed line {}.", line),
+            ImportError::InvalidIndex(line) => {
+                write!(f, "Face on line {} refers to a missing vertex.", line)
+            },
+            ImportError::FaceTooSmall(line) => {
+                write!(f, "Face on line {} has less than three vertices.", line)
+            },
+            ImportError::NoFaces => write!(f, "No faces."),
+            ImportError::NotManifold(v1, v2) => write!(
+                f, "Edge from vertex {} to {} is shared by faces wound the same way.", v1, v2,
+            ),
+            ImportError::NotClosed(v1, v2) => {
+                write!(f, "Edge from vertex {} to {} is only part of one face.", v1, v2)
+            },
+        }
+    }
+}
+
+impl error::Error for ImportError {
+    fn description(&self) -> &str {
+        "Error importing OBJ."
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cgmath::prelude::*;
+
+    use super::*;
+    use crate::polyhedron::{ConwayDescription, VertexAndFaceOps};
+
+    /// A unit cube off to the side with its faces wound inwards.
+    const CUBE: &str = "
+        o cube
+        v 10 0 0
+        v 11 0 0
+        v 11 1 0
+        v 10 1 0
+        v 10 0 1
+        v 11 0 1
+        v 11 1 1
+        v 10 1 1
+        vn 0 0 1
+        f 1//1 2//1 3//1 4//1
+        f 8 7 6 5
+        f 1 5 6 2
+        f 2 6 7 3
+        f 3 7 8 4
+        f -4 -8 -5 -1
+    ";
+
+    #[test]
+    fn cube_seed() {
+        let seed = read(CUBE.as_bytes()).unwrap();
+        let cube = seed.polyhedron();
+        let (vertices, faces) = cube.vertices_and_faces();
+
+        let radius = 0.75f64.sqrt();
+        assert!(vertices.iter().all(|v| (v.to_vec().magnitude() - radius).abs() < 1e-9));
+        assert!(faces[0] == vec![3, 2, 1, 0]);
+
+        let kc = ConwayDescription::new()
+            .seed(&seed).unwrap()
+            .kis().unwrap()
+            .emit().unwrap();
+        assert!(kc.notation() == "kX");
+        assert!(kc.produce().vertices_and_faces().1.len() == 24);
+    }
+
+    #[test]
+    fn open_mesh() {
+        let open: String = CUBE
+            .lines()
+            .filter(|l| !l.contains("f -4"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        match read(open.as_bytes()) {
+            Err(ImportError::NotClosed(_, _)) => (),
+            _ => panic!("Open mesh accepted."),
+        }
+        assert!(read("v 0 0 0\nf 1 2 3".as_bytes()).is_err());
+    }
+}
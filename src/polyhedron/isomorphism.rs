@@ -0,0 +1,133 @@
+//! Whether two polyhedra are put together the same way whatever their shape, sizes or the
+//! order their vertices and faces are listed in. Mirror images count as the same.
+//!
+//! Uses Weinberg's method. Pick a half-edge in one and try lining it up with each half-edge
+//! in the other. Once one pair is lined up, walking to the next half-edge around the face
+//! and across to the twin on both at the same time decides every other pair. If they never
+//! disagree the two are the same.
+use std::collections::HashMap;
+
+use super::{Polyhedron, VtFc};
+use crate::geop::HalfEdgeMesh;
+
+impl Polyhedron<VtFc> {
+    /// Both must be closed with their faces wound consistently, as every polyhedron made
+    /// by the Conway operations is.
+    pub fn is_isomorphic_to(&self, other: &Polyhedron<VtFc>) -> bool {
+        let mesh = HalfEdgeMesh::from(self);
+        let other_mesh = HalfEdgeMesh::from(other);
+        if signatures(&mesh) != signatures(&other_mesh) {
+            return false;
+        }
+
+        // The mirror image is the same faces wound the other way.
+        let mirrored: Vec<Vec<usize>> = other.data.faces
+            .iter()
+            .map(|face| face.iter().rev().cloned().collect())
+            .collect();
+        let mirror_mesh = HalfEdgeMesh::new(other.data.vertices.clone(), &mirrored);
+
+        // Start from the kind of half-edge there's least of to have the fewest to try.
+        let mut kinds: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for h in 0..mesh.edge_count() * 2 {
+            kinds.entry(signature(&mesh, h)).or_default().push(h);
+        }
+        let (kind, start) = match kinds.iter().min_by_key(|(_, hs)| hs.len()) {
+            Some((kind, hs)) => (*kind, hs[0]),
+            None => return true,
+        };
+
+        [&other_mesh, &mirror_mesh].iter().any(|target| {
+            (0..target.edge_count() * 2)
+                .filter(|h| signature(target, *h) == kind)
+                .any(|h| lines_up(&mesh, start, target, h))
+        })
+    }
+}
+
+/// The degree of the vertex a half-edge leaves and of the face it goes around.
+fn signature(mesh: &HalfEdgeMesh, h: usize) -> (usize, usize) {
+    (mesh.vertex_degree(mesh.origin(h)), mesh.face_degree(mesh.face(h)))
+}
+
+/// How many half-edges there are of each signature.
+fn signatures(mesh: &HalfEdgeMesh) -> Vec<((usize, usize), usize)> {
+    let mut counts: HashMap<(usize, usize), usize> = HashMap::new();
+    for h in 0..mesh.edge_count() * 2 {
+        *counts.entry(signature(mesh, h)).or_insert(0) += 1;
+    }
+    let mut counts: Vec<((usize, usize), usize)> = counts.into_iter().collect();
+    counts.sort();
+    counts
+}
+
+/// Whether pairing `start` with `target_start` pairs up every half-edge one to one.
+fn lines_up(
+    mesh: &HalfEdgeMesh, start: usize, target: &HalfEdgeMesh, target_start: usize,
+) -> bool {
+    let count = mesh.edge_count() * 2;
+    let mut forward: Vec<Option<usize>> = vec![None; count];
+    let mut backward: Vec<Option<usize>> = vec![None; count];
+    let mut pending = vec![(start, target_start)];
+
+    while let Some((h, g)) = pending.pop() {
+        match (forward[h], backward[g]) {
+            (Some(paired), _) if paired == g => continue,
+            (None, None) => {
+                forward[h] = Some(g);
+                backward[g] = Some(h);
+            },
+            _ => return false,
+        }
+        if signature(mesh, h) != signature(target, g) {
+            return false;
+        }
+
+        pending.push((mesh.next(h), target.next(g)));
+        pending.push((mesh.twin(h), target.twin(g)));
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::polyhedron::{Specification, VertexAndFaceOps};
+
+    fn produce(notation: &str) -> Polyhedron<VtFc> {
+        Specification::parse(notation).unwrap().produce()
+    }
+
+    #[test]
+    fn operator_identities() {
+        assert!(produce("ddC").is_isomorphic_to(&produce("C")));
+        assert!(produce("tT").is_isomorphic_to(&produce("dkdT")));
+        assert!(produce("aC").is_isomorphic_to(&produce("aO")));
+        assert!(produce("jD").is_isomorphic_to(&produce("daI")));
+        assert!(!produce("kC").is_isomorphic_to(&produce("kO")));
+        assert!(!produce("tO").is_isomorphic_to(&produce("tC")));
+    }
+
+    #[test]
+    fn relabelled_and_mirrored() {
+        let snub = produce("sC");
+        let (vertices, faces) = snub.vertices_and_faces();
+
+        // Every vertex moved along by one and the faces listed backwards and wound the
+        // other way.
+        let shift = |v: usize| (v + 1) % vertices.len();
+        let mut moved = vertices.to_vec();
+        moved.rotate_right(1);
+        let mirrored: Vec<Vec<usize>> = faces
+            .iter()
+            .rev()
+            .map(|face| face.iter().rev().map(|v| shift(*v)).collect())
+            .collect();
+        let mirrored: Vec<&[usize]> = mirrored.iter().map(|face| face.as_slice()).collect();
+        let other = Polyhedron::new(snub.data.center, &moved, &mirrored).unwrap();
+
+        assert!(snub.is_isomorphic_to(&other));
+        assert!(other.is_isomorphic_to(&snub));
+    }
+}
@@ -0,0 +1,266 @@
+//! Go over the faces, edges and vertices of a polyhedron without copying them out. Each
+//! item borrows the polyhedron and works out what's asked of it, such as a face normal,
+//! only when asked.
+use std::collections::HashSet;
+use std::iter::Enumerate;
+use std::slice;
+
+use cgmath::prelude::*;
+use cgmath::{BaseFloat, Point3, Vector3};
+
+use crate::planar;
+use super::{Faces, FacesIter};
+
+/// A face of a polyhedron.
+#[derive(Debug, Copy, Clone)]
+pub struct FaceRef<'a, S: BaseFloat = f64> {
+    index: usize,
+    face: &'a [usize],
+    vertices: &'a [Point3<S>],
+}
+
+impl<'a, S: BaseFloat> FaceRef<'a, S> {
+    /// Position among the faces of the polyhedron.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Indexes of the vertices going around the face.
+    pub fn vertex_indexes(&self) -> &'a [usize] {
+        self.face
+    }
+
+    /// Number of sides which is also the number of vertices.
+    pub fn sides(&self) -> usize {
+        self.face.len()
+    }
+
+    /// The vertices going around the face.
+    pub fn points(&self) -> impl Iterator<Item = Point3<S>> + 'a {
+        let vertices = self.vertices;
+        self.face.iter().map(move |v| vertices[*v])
+    }
+
+    /// Same centroid as `Polyhedron::centroidize` works out. The face is split into a fan
+    /// of triangles from its first vertex and their centers weighted by their areas.
+    pub fn centroid(&self) -> Point3<S> {
+        let first = self.vertices[self.face[0]];
+        let three = S::from(3.0).unwrap();
+        let (sum, area) = self.face[1..]
+            .windows(2)
+            .map(|pair| (self.vertices[pair[0]], self.vertices[pair[1]]))
+            .fold((Vector3::zero(), S::zero()), |(sum, total), (p2, p3)| {
+                let area = (p2 - first).cross(p3 - first).magnitude();
+                let center = (first.to_vec() + p2.to_vec() + p3.to_vec()) / three;
+                (sum + center * area, total + area)
+            });
+
+        Point3::from_vec(sum / area)
+    }
+
+    /// Unit normal pointing out of the face the way it winds. Newell's method copes with
+    /// faces that aren't quite flat.
+    pub fn normal(&self) -> Vector3<S> {
+        let len = self.face.len();
+        (0..len)
+            .map(|i| {
+                let next = self.vertices[self.face[(i + 1) % len]].to_vec();
+                self.vertices[self.face[i]].to_vec().cross(next)
+            })
+            .fold(Vector3::zero(), |sum, n| sum + n)
+            .normalize()
+    }
+
+    /// Copy the face out as a polygon with its normal.
+    pub fn polygon(&self) -> planar::Polygon<S> {
+        let points: Vec<Point3<S>> = self.points().collect();
+        planar::Polygon::new(&points, self.normal())
+    }
+}
+
+/// The faces of a polyhedron in order. See `VertexAndFaceOps::iter_faces`.
+#[derive(Debug, Clone)]
+pub struct FaceIter<'a, S: BaseFloat = f64> {
+    faces: Enumerate<FacesIter<'a>>,
+    vertices: &'a [Point3<S>],
+}
+
+impl<'a, S: BaseFloat> FaceIter<'a, S> {
+    pub (in crate::polyhedron) fn new(
+        vertices: &'a [Point3<S>], faces: &'a Faces,
+    ) -> Self {
+        FaceIter { faces: faces.iter().enumerate(), vertices }
+    }
+}
+
+impl<'a, S: BaseFloat> Iterator for FaceIter<'a, S> {
+    type Item = FaceRef<'a, S>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let vertices = self.vertices;
+        self.faces
+            .next()
+            .map(|(index, face)| FaceRef { index, face, vertices })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.faces.size_hint()
+    }
+}
+
+impl<'a, S: BaseFloat> ExactSizeIterator for FaceIter<'a, S> {}
+
+/// An edge of a polyhedron.
+#[derive(Debug, Copy, Clone)]
+pub struct EdgeRef<'a, S: BaseFloat = f64> {
+    v1: usize,
+    v2: usize,
+    vertices: &'a [Point3<S>],
+}
+
+impl<'a, S: BaseFloat> EdgeRef<'a, S> {
+    /// Indexes of the vertices at each end, lowest first.
+    pub fn vertex_indexes(&self) -> (usize, usize) {
+        (self.v1, self.v2)
+    }
+
+    pub fn points(&self) -> (Point3<S>, Point3<S>) {
+        (self.vertices[self.v1], self.vertices[self.v2])
+    }
+
+    pub fn length(&self) -> S {
+        self.vertices[self.v1].distance(self.vertices[self.v2])
+    }
+
+    pub fn midpoint(&self) -> Point3<S> {
+        self.vertices[self.v1].midpoint(self.vertices[self.v2])
+    }
+}
+
+/// Each edge once in the order they are first reached going around the faces, the same as
+/// `VertexAndFaceOps::edges`. See `VertexAndFaceOps::iter_edges`.
+#[derive(Debug, Clone)]
+pub struct EdgeIter<'a, S: BaseFloat = f64> {
+    faces: FacesIter<'a>,
+    face: &'a [usize],
+    corner: usize,
+    seen: HashSet<(usize, usize)>,
+    vertices: &'a [Point3<S>],
+}
+
+impl<'a, S: BaseFloat> EdgeIter<'a, S> {
+    pub (in crate::polyhedron) fn new(
+        vertices: &'a [Point3<S>], faces: &'a Faces,
+    ) -> Self {
+        EdgeIter {
+            faces: faces.iter(),
+            face: &[],
+            corner: 0,
+            seen: HashSet::new(),
+            vertices,
+        }
+    }
+}
+
+impl<'a, S: BaseFloat> Iterator for EdgeIter<'a, S> {
+    type Item = EdgeRef<'a, S>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            while self.corner == self.face.len() {
+                self.face = self.faces.next()?;
+                self.corner = 0;
+            }
+
+            let len = self.face.len();
+            let (v1, v2) = (self.face[self.corner], self.face[(self.corner + 1) % len]);
+            self.corner += 1;
+
+            let (v1, v2) = if v1 < v2 { (v1, v2) } else { (v2, v1) };
+            if self.seen.insert((v1, v2)) {
+                return Some(EdgeRef { v1, v2, vertices: self.vertices });
+            }
+        }
+    }
+}
+
+/// A vertex of a polyhedron.
+#[derive(Debug, Copy, Clone)]
+pub struct VertexRef<'a, S: BaseFloat = f64> {
+    index: usize,
+    point: &'a Point3<S>,
+}
+
+impl<'a, S: BaseFloat> VertexRef<'a, S> {
+    /// Position among the vertices of the polyhedron.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn point(&self) -> Point3<S> {
+        *self.point
+    }
+}
+
+/// The vertices of a polyhedron in order. See `VertexAndFaceOps::iter_vertices`.
+#[derive(Debug, Clone)]
+pub struct VertexIter<'a, S: BaseFloat = f64> {
+    vertices: Enumerate<slice::Iter<'a, Point3<S>>>,
+}
+
+impl<'a, S: BaseFloat> VertexIter<'a, S> {
+    pub (in crate::polyhedron) fn new(vertices: &'a [Point3<S>]) -> Self {
+        VertexIter { vertices: vertices.iter().enumerate() }
+    }
+}
+
+impl<'a, S: BaseFloat> Iterator for VertexIter<'a, S> {
+    type Item = VertexRef<'a, S>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.vertices
+            .next()
+            .map(|(index, point)| VertexRef { index, point })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.vertices.size_hint()
+    }
+}
+
+impl<'a, S: BaseFloat> ExactSizeIterator for VertexIter<'a, S> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::polyhedron::{Specification, VertexAndFaceOps};
+
+    #[test]
+    fn faces_edges_and_vertices() {
+        let p = Specification::parse("tC").unwrap().produce();
+        let (vertices, faces) = p.vertices_and_faces();
+
+        assert!(p.iter_faces().len() == faces.len());
+        assert!(p.iter_vertices().len() == vertices.len());
+        let edges: Vec<(usize, usize)> = p.iter_edges().map(|e| e.vertex_indexes()).collect();
+        assert!(edges == p.edges());
+
+        let centroids = p.clone().centroidize();
+        let normals = p.clone().normalize();
+        for ((face, centroid), polygon) in p
+            .iter_faces()
+            .zip(centroids.data.centroids.iter())
+            .zip(normals.faces())
+        {
+            assert!(face.vertex_indexes() == &faces[face.index()]);
+            assert!(face.centroid().distance(*centroid) < 1e-9);
+            assert!((face.normal() - polygon.normal()).magnitude() < 1e-9);
+            assert!(face.points().count() == face.sides());
+        }
+
+        // Every edge of the truncated cube is the same length.
+        let first = p.iter_edges().next().unwrap().length();
+        assert!(p.iter_edges().all(|edge| (edge.length() - first).abs() < 1e-9));
+        assert!(p.iter_vertices().all(|v| v.point() == vertices[v.index()]));
+    }
+}
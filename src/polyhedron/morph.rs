@@ -0,0 +1,139 @@
+//! Animate the change the last operation makes by sliding the vertices of the result out
+//! from the surface of the polyhedron it was made from.
+//!
+//! The operations don't say where each new vertex came from so it's worked out afterwards.
+//! Each vertex starts where the ray from the centre out through it first crosses the faces
+//! of the polyhedron before. The operations only put vertices on or above the old faces so
+//! at the start the new polyhedron lies flat over the old one and looks just like it.
+use std::time::Duration;
+
+use cgmath::Point3;
+
+use crate::geop;
+use super::{Polyhedron, Specification, VtFc};
+
+/// A polyhedron with somewhere for each vertex to start from.
+#[derive(Debug, Clone)]
+pub struct Morph {
+    starts: Vec<Point3<f64>>,
+    after: Polyhedron<VtFc>,
+}
+
+impl Morph {
+    /// From `before` to `after` where `after` was made by running operations on `before`.
+    pub fn new(before: &Polyhedron<VtFc>, after: Polyhedron<VtFc>) -> Self {
+        let center = before.data.center;
+        let triangles: Vec<[Point3<f64>; 3]> = before.data.faces
+            .iter()
+            .flat_map(|face| (1..face.len() - 1).map(move |i| [face[0], face[i], face[i + 1]]))
+            .map(|t| [
+                before.data.vertices[t[0]],
+                before.data.vertices[t[1]],
+                before.data.vertices[t[2]],
+            ])
+            .collect();
+
+        let starts = after.data.vertices
+            .iter()
+            .map(|vertex| {
+                let direction = vertex - center;
+                triangles
+                    .iter()
+                    .filter_map(|t| geop::ray_triangle_intersection(center, direction, *t))
+                    .fold(None, |nearest: Option<f64>, distance| match nearest {
+                        Some(nearest) if nearest <= distance => Some(nearest),
+                        _ => Some(distance),
+                    })
+                    .map(|distance| center + direction * distance)
+                    .unwrap_or(*vertex)
+            })
+            .collect();
+
+        Morph { starts, after }
+    }
+
+    pub fn after(&self) -> &Polyhedron<VtFc> {
+        &self.after
+    }
+
+    /// Part way through where `t` of 0 lies over the polyhedron before and 1 is the one
+    /// after. `t` is kept between 0 and 1.
+    pub fn at(&self, t: f64) -> Polyhedron<VtFc> {
+        let t = t.clamp(0.0, 1.0);
+        let vertices = self.starts
+            .iter()
+            .zip(self.after.data.vertices.iter())
+            .map(|(start, end)| start + (end - start) * t)
+            .collect();
+
+        Polyhedron {
+            data: VtFc {
+                center: self.after.data.center,
+                vertices,
+                faces: self.after.data.faces.clone(),
+            },
+        }
+    }
+
+    /// Part way through a morph taking `duration`, `elapsed` since it started. Eases in
+    /// and out rather than moving at a steady pace.
+    pub fn at_time(&self, elapsed: Duration, duration: Duration) -> Polyhedron<VtFc> {
+        let seconds = |d: Duration| d.as_secs() as f64 + f64::from(d.subsec_nanos()) * 1e-9;
+        let t = match seconds(duration) {
+            total if total > 0.0 => (seconds(elapsed) / total).min(1.0),
+            _ => 1.0,
+        };
+
+        self.at(t * t * (3.0 - 2.0 * t))
+    }
+}
+
+impl Specification {
+    /// Produce with the last operation as a `Morph` from the polyhedron before it. `None`
+    /// when there are no operations after the seed.
+    pub fn produce_morph(&self) -> Option<Morph> {
+        let last = self.operations.len() - 1;
+        if last == 0 {
+            return None;
+        }
+
+        let before = Specification::new(&self.operations[..last]).produce();
+        let after = Specification::apply(&self.operations[last..], before.clone());
+        Some(Morph::new(&before, after))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cgmath::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn kis_rises_from_the_faces() {
+        let morph = Specification::parse("kC").unwrap().produce_morph().unwrap();
+        let cube = Specification::parse("C").unwrap().produce();
+        let (start, end) = (morph.at(0.0), morph.at(1.0));
+
+        // The cube corners stay put and the pyramid tips start in the middle of the faces.
+        for (v, corner) in cube.data.vertices.iter().enumerate() {
+            assert!(start.data.vertices[v].distance(*corner) < 1e-9);
+        }
+        let radius = end.bounding_sphere().1;
+        for tip in start.data.vertices[cube.data.vertices.len()..].iter() {
+            let distance = tip.distance(end.data.center);
+            assert!((distance - radius / 3f64.sqrt()).abs() < 1e-6);
+        }
+
+        let finished = morph.after();
+        for (v1, v2) in end.data.vertices.iter().zip(finished.data.vertices.iter()) {
+            assert!(v1.distance(*v2) < 1e-12);
+        }
+        assert!(end.data.faces == finished.data.faces);
+    }
+
+    #[test]
+    fn seed_alone_has_no_morph() {
+        assert!(Specification::parse("D").unwrap().produce_morph().is_none());
+    }
+}
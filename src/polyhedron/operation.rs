@@ -0,0 +1,742 @@
+//! The Conway operations themselves. Each takes the polyhedron produced so far and returns
+//! the next one. New vertices are lifted onto the bounding sphere of the polyhedron given,
+//! which is what Kis and Dual do unless told otherwise with `Lift` and `DualCenter`.
+use std::collections::HashMap;
+use std::iter;
+
+use cgmath::{Point3, Vector3};
+use cgmath::prelude::*;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::geop::HalfEdgeMesh;
+use super::{DualCenter, Faces, Lift, Polyhedron, VertexAndFaceOps, VtFc, VtFcCt};
+use super::flag::{Flags, Name, edge};
+use super::geodesic;
+use super::parallel;
+
+/// Each vertex of a face along with the vertex before and after it.
+fn corners(face: &[usize]) -> impl Iterator<Item = (usize, usize, usize)> + '_ {
+    let len = face.len();
+
+    (0..len).map(move |i| (face[(i + len - 1) % len], face[i], face[(i + 1) % len]))
+}
+
+/// Unit normal of a face wound anticlockwise seen from outside. Newell's method copes with
+/// faces that aren't quite flat.
+fn face_normal(vertices: &[Point3<f64>], face: &[usize]) -> Vector3<f64> {
+    face.iter()
+        .zip(face.iter().cycle().skip(1))
+        .map(|(v1, v2)| vertices[*v1].to_vec().cross(vertices[*v2].to_vec()))
+        .fold(Vector3::zero(), |sum, n| sum + n)
+        .normalize()
+}
+
+/// Where the line out from `center` through `point` crosses the sphere of `radius` around it.
+fn onto_sphere(point: Point3<f64>, center: Point3<f64>, radius: f64) -> Point3<f64> {
+    center + (point - center).normalize_to(radius)
+}
+
+/// The point `t` of the way along the line from `p1` to `p2`.
+fn lerp(p1: Point3<f64>, p2: Point3<f64>, t: f64) -> Point3<f64> {
+    p1 + (p2 - p1) * t
+}
+
+/// Replace each face with a vertex and each vertex with a face. The new vertices are put
+/// where `placement` says for each face. Going around the faces at each vertex by
+/// following the half-edges gives the new faces already wound outwards, even when the
+/// faces are far from regular.
+pub (in crate::polyhedron) fn dual(
+    p: Polyhedron<VtFc>, placement: DualCenter,
+) -> Polyhedron<VtFc> {
+    let mesh = HalfEdgeMesh::from(&p);
+    let (center, radius) = p.bounding_sphere();
+    let VtFcCt { vertices, faces, centroids, .. } = p.centroidize().data;
+
+    let vertices = parallel::each_index(faces.len())
+        .map(|f_index| {
+            let centroid = centroids[f_index];
+            match placement {
+                DualCenter::Circumsphere => onto_sphere(centroid, center, radius),
+                DualCenter::FacePlaneTangent => {
+                    let normal = face_normal(&vertices, &faces[f_index]);
+                    center + normal * normal.dot(centroid - center)
+                },
+                DualCenter::Centroid => centroid,
+            }
+        })
+        .collect();
+    let faces = parallel::faces(mesh.vertex_count(), |v| mesh.vertex_faces(v));
+
+    Polyhedron {
+        data: VtFc {
+            center,
+            vertices,
+            faces,
+        },
+    }
+}
+
+/// Raise a pyramid on each face. When `degree` is given only the faces with that many
+/// vertices are touched and the rest are kept as they are.
+pub (in crate::polyhedron) fn kis(
+    p: Polyhedron<VtFc>, degree: Option<usize>, lift: Lift,
+) -> Polyhedron<VtFc> {
+    let mesh = HalfEdgeMesh::from(&p);
+    let (center, radius) = p.bounding_sphere();
+    let VtFcCt { mut vertices, centroids, .. } = p.centroidize().data;
+    let mut faces = Faces::new();
+
+    // The centroids form the tips of pyramids rising from each face. Thus each face is
+    // subdivided into multiple triangle faces. To rise the centroids we increase the
+    // magnitude to equal the radius of the bounding sphere unless told to leave them. The
+    // pyramid tips are attached to the end of the vertices as they're made.
+    for (f_index, centroid) in centroids.iter().enumerate() {
+        if degree.is_some_and(|degree| degree != mesh.face_degree(f_index)) {
+            faces.push_from(mesh.face_vertices(f_index));
+            continue;
+        }
+
+        let pyramid_tip_index = vertices.len();
+        vertices.push(match lift {
+            Lift::Sphere => onto_sphere(*centroid, center, radius),
+            Lift::Flat => *centroid,
+        });
+
+        faces.extend(
+            mesh.face_half_edges(f_index)
+                .map(|h| [mesh.origin(h), mesh.destination(h), pyramid_tip_index])
+        );
+    }
+
+    Polyhedron {
+        data: VtFc {
+            center,
+            vertices,
+            faces,
+        }
+    }
+}
+
+/// Raise a prism on each face. A smaller copy of each face is joined to the original
+/// edges by a ring of quadrilaterals.
+pub (in crate::polyhedron) fn loft(p: Polyhedron<VtFc>) -> Polyhedron<VtFc> {
+    prism(p, 0.5, 0.0, Lift::Sphere)
+}
+
+/// Push each face `height` out along its normal and join it to where it was by a wall of
+/// quadrilaterals. The original vertices stay where they are.
+pub (in crate::polyhedron) fn extrude(p: Polyhedron<VtFc>, height: f64) -> Polyhedron<VtFc> {
+    prism(p, 0.0, height, Lift::Flat)
+}
+
+/// Shrink each face `ratio` of the way towards its centroid and join it to the original
+/// edges by a ring of quadrilaterals. Nothing is raised or lifted.
+pub (in crate::polyhedron) fn inset(p: Polyhedron<VtFc>, ratio: f64) -> Polyhedron<VtFc> {
+    prism(p, ratio, 0.0, Lift::Flat)
+}
+
+/// Replace each face with a copy moved `inset` of the way to its centroid and `height` out
+/// along its normal, joined to the original edges by a ring of quadrilaterals. Every vertex
+/// is lifted if `lift` says so.
+///
+/// `Name::Edge` is directed here and names the quadrilateral standing on that edge.
+fn prism(p: Polyhedron<VtFc>, inset: f64, height: f64, lift: Lift) -> Polyhedron<VtFc> {
+    let (center, radius) = p.bounding_sphere();
+    let VtFcCt { vertices, faces, centroids, .. } = p.centroidize().data;
+    let lift = |point: Point3<f64>| match lift {
+        Lift::Sphere => onto_sphere(point, center, radius),
+        Lift::Flat => point,
+    };
+    let mut flags = Flags::new();
+
+    for (f_index, face) in faces.iter().enumerate() {
+        let raise = face_normal(&vertices, face) * height;
+        for (v1, v2, _) in corners(face) {
+            for v in [v1, v2].iter() {
+                let inner = lerp(vertices[*v], centroids[f_index], inset) + raise;
+                flags.vertex(Name::Vertex(*v), lift(vertices[*v]));
+                flags.vertex(Name::FaceVertex(f_index, *v), lift(inner));
+            }
+
+            let inner1 = Name::FaceVertex(f_index, v1);
+            let inner2 = Name::FaceVertex(f_index, v2);
+            let quad = Name::Edge(v1, v2);
+            flags.flag(Name::Face(f_index), inner1, inner2);
+            flags.flag(quad, Name::Vertex(v1), Name::Vertex(v2));
+            flags.flag(quad, Name::Vertex(v2), inner2);
+            flags.flag(quad, inner2, inner1);
+            flags.flag(quad, inner1, Name::Vertex(v1));
+        }
+    }
+
+    flags.build(center)
+}
+
+/// Raise an antiprism on each face. A smaller copy of each face, rotated so that its
+/// vertices sit opposite the middle of the original edges, is joined to the original
+/// edges by a ring of triangles.
+///
+/// `Name::Edge` is directed here. As a vertex it is the inner vertex opposite that edge
+/// and as a face it is the triangle standing on that edge.
+pub (in crate::polyhedron) fn lace(p: Polyhedron<VtFc>) -> Polyhedron<VtFc> {
+    let (center, radius) = p.bounding_sphere();
+    let VtFcCt { vertices, faces, centroids, .. } = p.centroidize().data;
+    let lift = |point: Point3<f64>| onto_sphere(point, center, radius);
+    let mut flags = Flags::new();
+
+    for (f_index, face) in faces.iter().enumerate() {
+        for (v1, v2, v3) in corners(face) {
+            flags.vertex(Name::Vertex(v1), lift(vertices[v1]));
+            flags.vertex(Name::Vertex(v2), lift(vertices[v2]));
+            for (a, b) in [(v1, v2), (v2, v3)].iter() {
+                let midpoint = vertices[*a].midpoint(vertices[*b]);
+                let inner = lerp(midpoint, centroids[f_index], 0.5);
+                flags.vertex(Name::Edge(*a, *b), lift(inner));
+            }
+
+            let outer = Name::Edge(v1, v2);
+            let inner = Name::FaceVertex(f_index, v2);
+            flags.flag(Name::Face(f_index), Name::Edge(v1, v2), Name::Edge(v2, v3));
+            flags.flag(outer, Name::Vertex(v1), Name::Vertex(v2));
+            flags.flag(outer, Name::Vertex(v2), Name::Edge(v1, v2));
+            flags.flag(outer, Name::Edge(v1, v2), Name::Vertex(v1));
+            flags.flag(inner, Name::Edge(v1, v2), Name::Vertex(v2));
+            flags.flag(inner, Name::Vertex(v2), Name::Edge(v2, v3));
+            flags.flag(inner, Name::Edge(v2, v3), Name::Edge(v1, v2));
+        }
+    }
+
+    flags.build(center)
+}
+
+/// A new vertex is placed at the midpoint of each edge. Each face is replaced by the face
+/// joining the midpoints of its edges and each vertex by the face joining the midpoints of
+/// the edges that met there.
+pub (in crate::polyhedron) fn ambo(p: Polyhedron<VtFc>) -> Polyhedron<VtFc> {
+    let (center, radius) = p.bounding_sphere();
+    let VtFc { vertices, faces, .. } = p.data;
+    let mut flags = Flags::new();
+
+    for (f_index, face) in faces.iter().enumerate() {
+        for (v1, v2, v3) in corners(face) {
+            for (a, b) in [(v1, v2), (v2, v3)].iter() {
+                let midpoint = vertices[*a].midpoint(vertices[*b]);
+                flags.vertex(edge(*a, *b), onto_sphere(midpoint, center, radius));
+            }
+
+            flags.flag(Name::Face(f_index), edge(v1, v2), edge(v2, v3));
+            flags.flag(Name::Vertex(v2), edge(v2, v3), edge(v1, v2));
+        }
+    }
+
+    flags.build(center)
+}
+
+/// How far along an edge to cut so that truncating a regular face with `degree` vertices
+/// leaves a regular face with twice as many vertices.
+fn regular_truncation_ratio(degree: usize) -> f64 {
+    1.0 / (2.0 + 2.0 * (std::f64::consts::PI / degree as f64).cos())
+}
+
+/// Uniform truncation. Each face gains a new edge at each of its cut corners and each cut
+/// vertex is replaced by the face left by the cut. When `degree` is given only the
+/// vertices where that many faces meet are cut off.
+///
+/// `ratio` is how far along each edge, from each end, the cut is made. If no ratio is
+/// given each edge is cut to leave the faces either side of it as regular as possible.
+///
+/// Each half-edge leaving a cut vertex gets a new vertex where it is cut.
+pub (in crate::polyhedron) fn truncate(
+    p: Polyhedron<VtFc>, degree: Option<usize>, ratio: Option<f64>,
+) -> Polyhedron<VtFc> {
+    let (center, radius) = p.bounding_sphere();
+    let VtFc { vertices, faces, .. } = p.data;
+    let mesh = HalfEdgeMesh::new(vertices, &faces);
+    let vertices = mesh.vertices();
+    let cut = |v: usize| degree.is_none_or(|degree| degree == mesh.vertex_degree(v));
+
+    // Two faces of different degree can't both be made regular so the ratio for each is
+    // averaged when none is given.
+    let edge_ratio = |h: usize| ratio.unwrap_or_else(|| {
+        let r1 = regular_truncation_ratio(mesh.face_degree(mesh.face(h)));
+        let r2 = regular_truncation_ratio(mesh.face_degree(mesh.face(mesh.twin(h))));
+        (r1 + r2) / 2.0
+    });
+
+    // Kept vertices first, then the cut points.
+    let mut new_vertices: Vec<Point3<f64>> = Vec::new();
+    let mut kept: Vec<Option<usize>> = vec![None; vertices.len()];
+    for v in (0..vertices.len()).filter(|v| !cut(*v)) {
+        kept[v] = Some(new_vertices.len());
+        new_vertices.push(onto_sphere(vertices[v], center, radius));
+    }
+
+    let mut cuts: HashMap<usize, usize> = HashMap::new();
+    for v in (0..vertices.len()).filter(|v| cut(*v)) {
+        for h in mesh.vertex_half_edges(v) {
+            let point = lerp(vertices[v], vertices[mesh.destination(h)], edge_ratio(h));
+            cuts.insert(h, new_vertices.len());
+            new_vertices.push(onto_sphere(point, center, radius));
+        }
+    }
+
+    // Each corner of a face is either kept or replaced by where the edges either side of it
+    // were cut.
+    let mut new_faces = parallel::faces(mesh.face_count(), |f| {
+        mesh.face_half_edges(f)
+            .flat_map(|h| {
+                let v = mesh.origin(h);
+                match kept[v] {
+                    Some(index) => iter::once(index).chain(None),
+                    None => iter::once(cuts[&mesh.twin(mesh.prev(h))]).chain(Some(cuts[&h])),
+                }
+            })
+    });
+
+    for v in (0..vertices.len()).filter(|v| cut(*v)) {
+        new_faces.push_from(mesh.vertex_half_edges(v).map(|h| cuts[&h]));
+    }
+
+    Polyhedron {
+        data: VtFc {
+            center,
+            vertices: new_vertices,
+            faces: new_faces,
+        }
+    }
+}
+
+/// Cantellation. The faces are pulled apart from each other with a square filling the gap
+/// left along each edge and a vertex figure filling the gap left at each vertex. This is
+/// the same as applying ambo twice.
+pub (in crate::polyhedron) fn expand(p: Polyhedron<VtFc>) -> Polyhedron<VtFc> {
+    ambo(ambo(p))
+}
+
+/// Each edge is replaced by a hexagon. The faces shrink towards their centroids, keeping
+/// their shape, and the original vertices stay where they were.
+///
+/// How far each face shrinks is chosen so that the hexagon edges match the shrunken face
+/// edges when the face is regular.
+pub (in crate::polyhedron) fn chamfer(p: Polyhedron<VtFc>) -> Polyhedron<VtFc> {
+    let (center, radius) = p.bounding_sphere();
+    let VtFcCt { vertices, faces, centroids, .. } = p.centroidize().data;
+    let lift = |point: Point3<f64>| onto_sphere(point, center, radius);
+    let mut flags = Flags::new();
+
+    for (f_index, face) in faces.iter().enumerate() {
+        let side = 2.0 * (std::f64::consts::PI / face.len() as f64).sin();
+        let shrink = side / (1.0 + side);
+
+        for (v1, v2, _) in corners(face) {
+            for v in [v1, v2].iter() {
+                let inner = lerp(vertices[*v], centroids[f_index], shrink);
+                flags.vertex(Name::Vertex(*v), lift(vertices[*v]));
+                flags.vertex(Name::FaceVertex(f_index, *v), lift(inner));
+            }
+
+            let inner1 = Name::FaceVertex(f_index, v1);
+            let inner2 = Name::FaceVertex(f_index, v2);
+            let hexagon = edge(v1, v2);
+            flags.flag(Name::Face(f_index), inner1, inner2);
+            flags.flag(hexagon, Name::Vertex(v2), inner2);
+            flags.flag(hexagon, inner2, inner1);
+            flags.flag(hexagon, inner1, Name::Vertex(v1));
+        }
+    }
+
+    flags.build(center)
+}
+
+/// Each n sided face is split into n pentagons around a new vertex at the face centroid.
+/// Every edge gains two vertices a third of the way in from each end so the pentagons
+/// spiral around the centroid, making the result chiral.
+///
+/// `Name::Edge` is directed here. `Edge(v1, v2)` is the vertex nearest to `v1`.
+pub (in crate::polyhedron) fn gyro(p: Polyhedron<VtFc>) -> Polyhedron<VtFc> {
+    let (center, radius) = p.bounding_sphere();
+    let VtFcCt { vertices, faces, centroids, .. } = p.centroidize().data;
+    let lift = |point: Point3<f64>| onto_sphere(point, center, radius);
+    let mut flags = Flags::new();
+
+    for (f_index, face) in faces.iter().enumerate() {
+        flags.vertex(Name::Face(f_index), lift(centroids[f_index]));
+
+        for (v1, v2, v3) in corners(face) {
+            flags.vertex(Name::Vertex(v2), lift(vertices[v2]));
+            for (a, b) in [(v1, v2), (v2, v1), (v2, v3)].iter() {
+                let third = lerp(vertices[*a], vertices[*b], 1.0 / 3.0);
+                flags.vertex(Name::Edge(*a, *b), lift(third));
+            }
+
+            let pentagon = Name::FaceVertex(f_index, v1);
+            flags.flag(pentagon, Name::Face(f_index), Name::Edge(v1, v2));
+            flags.flag(pentagon, Name::Edge(v1, v2), Name::Edge(v2, v1));
+            flags.flag(pentagon, Name::Edge(v2, v1), Name::Vertex(v2));
+            flags.flag(pentagon, Name::Vertex(v2), Name::Edge(v2, v3));
+            flags.flag(pentagon, Name::Edge(v2, v3), Name::Face(f_index));
+        }
+    }
+
+    flags.build(center)
+}
+
+/// Each n sided face is replaced by a smaller, rotated n sided face surrounded by n
+/// quadrilaterals, one at each original vertex.
+///
+/// `Name::Edge` is directed here. `Edge(v1, v2)` is the vertex nearest to `v1`.
+pub (in crate::polyhedron) fn propeller(p: Polyhedron<VtFc>) -> Polyhedron<VtFc> {
+    let (center, radius) = p.bounding_sphere();
+    let VtFc { vertices, faces, .. } = p.data;
+    let lift = |point: Point3<f64>| onto_sphere(point, center, radius);
+    let mut flags = Flags::new();
+
+    for (f_index, face) in faces.iter().enumerate() {
+        for (v1, v2, v3) in corners(face) {
+            flags.vertex(Name::Vertex(v2), lift(vertices[v2]));
+            for (a, b) in [(v1, v2), (v2, v1), (v2, v3)].iter() {
+                let third = lerp(vertices[*a], vertices[*b], 1.0 / 3.0);
+                flags.vertex(Name::Edge(*a, *b), lift(third));
+            }
+
+            let quad = Name::FaceVertex(f_index, v2);
+            flags.flag(Name::Face(f_index), Name::Edge(v1, v2), Name::Edge(v2, v3));
+            flags.flag(quad, Name::Edge(v1, v2), Name::Edge(v2, v1));
+            flags.flag(quad, Name::Edge(v2, v1), Name::Vertex(v2));
+            flags.flag(quad, Name::Vertex(v2), Name::Edge(v2, v3));
+            flags.flag(quad, Name::Edge(v2, v3), Name::Edge(v1, v2));
+        }
+    }
+
+    flags.build(center)
+}
+
+/// Like gyro but the centre of each face is kept as a smaller, rotated copy of the face.
+/// Each edge becomes two hexagons spiralling around these copies. Applied to the
+/// dodecahedron it makes the class III Goldberg polyhedron GP(2, 1).
+///
+/// `Name::Edge` is directed here. `Edge(v1, v2)` is the vertex nearest to `v1`.
+/// `Name::FaceVertex(f, v)` is the vertex of the inner copy of face `f` closest to the
+/// edge leaving `v`.
+pub (in crate::polyhedron) fn whirl(p: Polyhedron<VtFc>) -> Polyhedron<VtFc> {
+    let (center, radius) = p.bounding_sphere();
+    let VtFcCt { vertices, faces, centroids, .. } = p.centroidize().data;
+    let lift = |point: Point3<f64>| onto_sphere(point, center, radius);
+    let third = |a: usize, b: usize| lerp(vertices[a], vertices[b], 1.0 / 3.0);
+    let mut flags = Flags::new();
+
+    for (f_index, face) in faces.iter().enumerate() {
+        for (v1, v2, v3) in corners(face) {
+            flags.vertex(Name::Vertex(v2), lift(vertices[v2]));
+            for (a, b) in [(v1, v2), (v2, v1), (v2, v3)].iter() {
+                flags.vertex(Name::Edge(*a, *b), lift(third(*a, *b)));
+            }
+            for (a, b) in [(v1, v2), (v2, v3)].iter() {
+                let inner = lerp(centroids[f_index], third(*a, *b), 1.0 / 3.0);
+                flags.vertex(Name::FaceVertex(f_index, *a), lift(inner));
+            }
+
+            let inner1 = Name::FaceVertex(f_index, v1);
+            let inner2 = Name::FaceVertex(f_index, v2);
+            let hexagon = Name::FaceVertex(f_index, v1);
+            flags.flag(hexagon, inner1, Name::Edge(v1, v2));
+            flags.flag(hexagon, Name::Edge(v1, v2), Name::Edge(v2, v1));
+            flags.flag(hexagon, Name::Edge(v2, v1), Name::Vertex(v2));
+            flags.flag(hexagon, Name::Vertex(v2), Name::Edge(v2, v3));
+            flags.flag(hexagon, Name::Edge(v2, v3), inner2);
+            flags.flag(hexagon, inner2, inner1);
+            flags.flag(Name::Face(f_index), inner1, inner2);
+        }
+    }
+
+    flags.build(center)
+}
+
+/// The dual of gyro. Each original vertex and face is surrounded by a ring of triangles
+/// with a further triangle filling each corner where the rings meet. The twist matches
+/// that of `gyro` so chaining operators keeps the same handedness throughout.
+pub (in crate::polyhedron) fn snub(p: Polyhedron<VtFc>) -> Polyhedron<VtFc> {
+    dual(gyro(p), DualCenter::Circumsphere)
+}
+
+/// Truncated rectification. Each face keeps its place but with twice the edges, each
+/// vertex becomes a face with twice the edges and each edge becomes a square.
+pub (in crate::polyhedron) fn bevel(p: Polyhedron<VtFc>) -> Polyhedron<VtFc> {
+    truncate(ambo(p), None, None)
+}
+
+/// The dual of expand. Each n sided face is split into n quadrilaterals meeting at the
+/// face centroid.
+pub (in crate::polyhedron) fn ortho(p: Polyhedron<VtFc>) -> Polyhedron<VtFc> {
+    dual(expand(p), DualCenter::Circumsphere)
+}
+
+/// The dual of ambo. Each edge is replaced by a quadrilateral joining the two vertices
+/// and the two face centroids either side of it.
+pub (in crate::polyhedron) fn join(p: Polyhedron<VtFc>) -> Polyhedron<VtFc> {
+    dual(ambo(p), DualCenter::Circumsphere)
+}
+
+/// Kis of join. Each n sided face is split into 2n triangles meeting at the face
+/// centroid.
+pub (in crate::polyhedron) fn meta(p: Polyhedron<VtFc>) -> Polyhedron<VtFc> {
+    kis(join(p), None, Lift::Sphere)
+}
+
+/// Kis of dual. Each vertex becomes the tip of a pyramid and each edge is replaced by a
+/// pair of triangles.
+pub (in crate::polyhedron) fn needle(p: Polyhedron<VtFc>) -> Polyhedron<VtFc> {
+    kis(dual(p, DualCenter::Circumsphere), None, Lift::Sphere)
+}
+
+/// Dual of kis. Each face and each vertex is replaced by a face with twice the edges.
+pub (in crate::polyhedron) fn zip(p: Polyhedron<VtFc>) -> Polyhedron<VtFc> {
+    dual(kis(p, None, Lift::Sphere), DualCenter::Circumsphere)
+}
+
+/// Split each triangle into `frequency²` smaller triangles. Faces that aren't triangles
+/// first have a pyramid raised on them so every face can be split.
+pub (in crate::polyhedron) fn subdivide(
+    p: Polyhedron<VtFc>, frequency: usize, lift: Lift,
+) -> Polyhedron<VtFc> {
+    let p = p.face_census()
+        .keys()
+        .filter(|degree| **degree != 3)
+        .cloned()
+        .collect::<Vec<usize>>()
+        .into_iter()
+        .fold(p, |p, degree| kis(p, Some(degree), lift));
+
+    geodesic::subdivide(p, frequency, 0, lift)
+}
+
+/// Move each vertex `strength` of the way along the line from where it is to where the line
+/// out from the center crosses the bounding sphere. A vertex at the center stays there.
+pub (in crate::polyhedron) fn spherize(
+    p: Polyhedron<VtFc>, strength: f64,
+) -> Polyhedron<VtFc> {
+    let (center, radius) = p.bounding_sphere();
+    let VtFc { vertices, faces, .. } = p.data;
+
+    let vertices = parallel::each(&vertices)
+        .map(|vertex| {
+            let out = vertex - center;
+            if out.magnitude2() == 0.0 {
+                *vertex
+            } else {
+                lerp(*vertex, center + out.normalize_to(radius), strength)
+            }
+        })
+        .collect();
+
+    Polyhedron {
+        data: VtFc {
+            center,
+            vertices,
+            faces,
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::polyhedron::Specification;
+
+    fn produce(notation: &str) -> Polyhedron<VtFc> {
+        Specification::parse(notation).unwrap().produce()
+    }
+
+    /// Vertex, edge and face counts along with how many faces there are of each degree.
+    fn census(notation: &str) -> ((usize, usize, usize), BTreeMap<usize, usize>) {
+        let p = produce(notation);
+        let (vertices, faces) = p.vertices_and_faces();
+        let mut degrees = BTreeMap::new();
+        for face in faces.iter() {
+            *degrees.entry(face.len()).or_insert(0) += 1;
+        }
+
+        ((vertices.len(), p.edge_count(), faces.len()), degrees)
+    }
+
+    fn degrees(counts: &[(usize, usize)]) -> BTreeMap<usize, usize> {
+        counts.iter().cloned().collect()
+    }
+
+    fn on_sphere(p: &Polyhedron<VtFc>) -> bool {
+        let (center, radius) = p.bounding_sphere();
+        p.vertices_and_faces().0.iter().all(|v| (v.distance(center) - radius).abs() < 1e-9)
+    }
+
+    fn edge_lengths(p: &Polyhedron<VtFc>) -> Vec<f64> {
+        let vertices = p.vertices_and_faces().0;
+        p.edges().iter().map(|(v1, v2)| vertices[*v1].distance(vertices[*v2])).collect()
+    }
+
+    /// Whether every face goes anticlockwise seen from outside.
+    fn wound_outwards(p: &Polyhedron<VtFc>) -> bool {
+        let (vertices, faces) = p.vertices_and_faces();
+        let center = p.bounding_sphere().0;
+        faces.iter().all(|face| {
+            let centroid = face
+                .iter()
+                .fold(Vector3::zero(), |sum, v| sum + (vertices[*v] - center))
+                / face.len() as f64;
+            face_normal(vertices, face).dot(centroid) > 0.0
+        })
+    }
+
+    #[test]
+    fn ambo_cube_is_cuboctahedron() {
+        assert!(census("aC") == ((12, 24, 14), degrees(&[(3, 8), (4, 6)])));
+
+        // The edge midpoints of the cube are all the same distance apart.
+        let cuboctahedron = produce("aC");
+        assert!(on_sphere(&cuboctahedron));
+        let lengths = edge_lengths(&cuboctahedron);
+        assert!(lengths.iter().all(|l| (l - lengths[0]).abs() < 1e-9));
+    }
+
+    #[test]
+    fn gyro_gives_pentagons() {
+        assert!(census("gC") == ((38, 60, 24), degrees(&[(5, 24)])));
+        assert!(census("gD") == ((92, 150, 60), degrees(&[(5, 60)])));
+        assert!(wound_outwards(&produce("gD")));
+    }
+
+    #[test]
+    fn snub_cube_and_dodecahedron() {
+        assert!(census("sC") == ((24, 60, 38), degrees(&[(3, 32), (4, 6)])));
+        assert!(census("sD") == ((60, 150, 92), degrees(&[(3, 80), (5, 12)])));
+        assert!(census("dgD") == census("sD"));
+
+        // Five faces meet at every vertex and the new triangles all face out.
+        let snub = produce("sC");
+        let (vertices, faces) = snub.vertices_and_faces();
+        let mut meeting = vec![0; vertices.len()];
+        for v in faces.iter().flatten() {
+            meeting[*v] += 1;
+        }
+        assert!(meeting.iter().all(|count| *count == 5));
+        assert!(wound_outwards(&snub));
+    }
+
+    #[test]
+    fn expand_gives_rhombicuboctahedron() {
+        assert!(census("eC") == ((24, 48, 26), degrees(&[(3, 8), (4, 18)])));
+        assert!(census("eD") == ((60, 120, 62), degrees(&[(3, 20), (4, 30), (5, 12)])));
+        assert!(census("aaC") == census("eC"));
+        assert!(wound_outwards(&produce("eD")));
+    }
+
+    #[test]
+    fn bevel_gives_truncated_cuboctahedron() {
+        assert!(census("bC") == ((48, 72, 26), degrees(&[(4, 12), (6, 8), (8, 6)])));
+        assert!(census("taC") == census("bC"));
+        assert!(wound_outwards(&produce("bC")));
+    }
+
+    #[test]
+    fn ortho_and_join_give_quads() {
+        // Ortho of the cube is the deltoidal icositetrahedron, the dual of expand.
+        assert!(census("oC") == ((26, 48, 24), degrees(&[(4, 24)])));
+        assert!(census("deC") == census("oC"));
+        assert!(wound_outwards(&produce("oC")));
+
+        // Join of the cube is the rhombic dodecahedron, the dual of ambo.
+        assert!(census("jC") == ((14, 24, 12), degrees(&[(4, 12)])));
+        assert!(census("daC") == census("jC"));
+        assert!(wound_outwards(&produce("jC")));
+    }
+
+    #[test]
+    fn chamfer_gives_class_two_goldberg() {
+        // GP(2,0) and GP(4,0) have 12 pentagons with 10T - 10 hexagons for T of 4 and 16.
+        assert!(census("cD") == ((80, 120, 42), degrees(&[(5, 12), (6, 30)])));
+        assert!(census("ccD") == ((320, 480, 162), degrees(&[(5, 12), (6, 150)])));
+        assert!(wound_outwards(&produce("ccD")));
+    }
+
+    #[test]
+    fn whirl_gives_class_three_goldberg() {
+        // GP(2,1) has T of 7.
+        assert!(census("wD") == ((140, 210, 72), degrees(&[(5, 12), (6, 60)])));
+        assert!(census("wC") == ((56, 84, 30), degrees(&[(4, 6), (6, 24)])));
+        assert!(wound_outwards(&produce("wD")));
+    }
+
+    #[test]
+    fn propeller_rings_faces_with_quads() {
+        assert!(census("pC") == ((32, 60, 30), degrees(&[(4, 30)])));
+        assert!(census("pD") == ((80, 150, 72), degrees(&[(4, 60), (5, 12)])));
+        assert!(wound_outwards(&produce("pD")));
+    }
+
+    #[test]
+    fn meta_needle_and_zip() {
+        // The hexakis octahedron, triakis octahedron and truncated octahedron.
+        assert!(census("mC") == ((26, 72, 48), degrees(&[(3, 48)])));
+        assert!(census("nC") == ((14, 36, 24), degrees(&[(3, 24)])));
+        assert!(census("zC") == ((24, 36, 14), degrees(&[(4, 6), (6, 8)])));
+
+        assert!(census("kjC") == census("mC"));
+        assert!(census("kdC") == census("nC"));
+        assert!(census("dkC") == census("zC"));
+        assert!(["mD", "nD", "zD"].iter().all(|n| wound_outwards(&produce(n))));
+    }
+
+    #[test]
+    fn loft_and_lace_ring_faces() {
+        // Loft rings each face with quads and lace with pairs of triangles.
+        assert!(census("lC") == ((32, 60, 30), degrees(&[(4, 30)])));
+        assert!(census("LC") == ((32, 84, 54), degrees(&[(3, 48), (4, 6)])));
+        assert!(census("LD") == ((80, 210, 132), degrees(&[(3, 120), (5, 12)])));
+        assert!(wound_outwards(&produce("lD")));
+        assert!(wound_outwards(&produce("LD")));
+    }
+
+    #[test]
+    fn kis_only_faces_of_degree() {
+        // Only the 12 pentagons of the truncated icosahedron get pyramids.
+        assert!(census("k5tI") == ((72, 150, 80), degrees(&[(3, 60), (6, 20)])));
+        assert!(census("k4tI") == census("tI"));
+
+        let before = produce("tI");
+        let after = produce("k5tI");
+        let (vertices, faces) = after.vertices_and_faces();
+        assert!(before.vertices_and_faces().0 == &vertices[..60]);
+        let hexagons = |faces: &Faces| -> Vec<Vec<usize>> {
+            faces.iter().filter(|face| face.len() == 6).map(|face| face.to_vec()).collect()
+        };
+        assert!(hexagons(before.vertices_and_faces().1) == hexagons(faces));
+    }
+
+    #[test]
+    fn truncate_only_vertices_of_degree() {
+        // The six apexes of the tetrakis hexahedron meet four faces and the eight corners of
+        // the cube six. Cutting the apexes turns every triangle into a quad.
+        assert!(census("t4kC") == ((32, 60, 30), degrees(&[(4, 30)])));
+        assert!(census("t3kC") == census("kC"));
+
+        let before = produce("kC");
+        let after = produce("t4kC");
+        let (vertices, faces) = before.vertices_and_faces();
+        let mut meeting = vec![0; vertices.len()];
+        for v in faces.iter().flatten() {
+            meeting[*v] += 1;
+        }
+        let corners: Vec<&Point3<f64>> = vertices
+            .iter()
+            .zip(meeting.iter())
+            .filter(|(_, count)| **count == 6)
+            .map(|(vertex, _)| vertex)
+            .collect();
+        assert!(corners.len() == 8);
+        let kept = after.vertices_and_faces().0;
+        assert!(corners.iter().all(|corner| kept.contains(corner)));
+        assert!(vertices.iter().filter(|v| kept.contains(v)).count() == 8);
+    }
+}
@@ -0,0 +1,56 @@
+//! Iterators that run in parallel when the `rayon` feature is on and one after the other
+//! when it isn't. Stick to adaptors both `Iterator` and `rayon::iter::ParallelIterator`
+//! have, such as `map` and `collect`, so that the code using them builds either way.
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use super::Faces;
+
+#[cfg(feature = "rayon")]
+pub (in crate::polyhedron) fn each<T: Sync>(items: &[T]) -> rayon::slice::Iter<'_, T> {
+    items.par_iter()
+}
+
+#[cfg(not(feature = "rayon"))]
+pub (in crate::polyhedron) fn each<T>(items: &[T]) -> std::slice::Iter<'_, T> {
+    items.iter()
+}
+
+/// From zero up to but not including `end`.
+#[cfg(feature = "rayon")]
+pub (in crate::polyhedron) fn each_index(end: usize) -> rayon::range::Iter<usize> {
+    (0..end).into_par_iter()
+}
+
+#[cfg(not(feature = "rayon"))]
+pub (in crate::polyhedron) fn each_index(end: usize) -> std::ops::Range<usize> {
+    0..end
+}
+
+/// The faces `face` gives for each index from zero up to but not including `end`. In
+/// parallel each face is collected on its own and they're put together at the end.
+#[cfg(feature = "rayon")]
+pub (in crate::polyhedron) fn faces<F, I>(end: usize, face: F) -> Faces
+where
+    F: Fn(usize) -> I + Sync + Send,
+    I: IntoIterator<Item = usize>,
+{
+    (0..end)
+        .into_par_iter()
+        .map(|index| face(index).into_iter().collect::<Vec<usize>>())
+        .collect::<Vec<Vec<usize>>>()
+        .into()
+}
+
+#[cfg(not(feature = "rayon"))]
+pub (in crate::polyhedron) fn faces<F, I>(end: usize, face: F) -> Faces
+where
+    F: Fn(usize) -> I,
+    I: IntoIterator<Item = usize>,
+{
+    let mut faces = Faces::new();
+    for index in 0..end {
+        faces.push_from(face(index));
+    }
+    faces
+}
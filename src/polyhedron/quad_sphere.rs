@@ -0,0 +1,125 @@
+//! A cube with each face split into a grid of squares then puffed out onto a sphere. Every
+//! face is a quadrilateral. Eight vertices have three faces and the rest four.
+//!
+//! The grid lines are spaced by angle rather than evenly along the cube face so the cells
+//! near the middle of a cube face are about the same size as those near its corners.
+use std::collections::HashMap;
+use std::f64::consts::FRAC_PI_4;
+
+use cgmath::{Point3, Vector3};
+use cgmath::prelude::*;
+
+use super::{OpError, Polyhedron, Seed, SeedSolid, VtFc};
+
+#[derive(Debug, Copy, Clone)]
+pub struct QuadSphere {
+    resolution: usize,
+    radius: f64,
+}
+
+impl QuadSphere {
+    /// Split each side of the cube `resolution` times, giving `6 * resolution²` faces. A
+    /// resolution of one is just the cube.
+    pub fn new(resolution: usize, radius: f64) -> Result<Self, OpError> {
+        if resolution == 0 {
+            return Err(OpError::ZeroFrequency);
+        }
+        Ok(QuadSphere { resolution, radius })
+    }
+
+    pub fn generate(&self) -> Polyhedron<VtFc> {
+        let n = self.resolution as i64;
+
+        // Each side of the cube as a corner and the two directions across it. The first
+        // crossed with the second points outwards so the faces wind anticlockwise.
+        let sides: [([i64; 3], [i64; 3], [i64; 3]); 6] = [
+            ([n, 0, 0], [0, 1, 0], [0, 0, 1]),
+            ([0, 0, 0], [0, 0, 1], [0, 1, 0]),
+            ([0, n, 0], [0, 0, 1], [1, 0, 0]),
+            ([0, 0, 0], [1, 0, 0], [0, 0, 1]),
+            ([0, 0, n], [1, 0, 0], [0, 1, 0]),
+            ([0, 0, 0], [0, 1, 0], [1, 0, 0]),
+        ];
+
+        // Grid points are shared along the edges of the cube so they're found by their
+        // whole number position.
+        let mut indexes: HashMap<[i64; 3], usize> = HashMap::new();
+        let mut vertices: Vec<Point3<f64>> = Vec::new();
+        let mut faces: Vec<Vec<usize>> = Vec::with_capacity(6 * (n * n) as usize);
+        let radius = self.radius;
+        let mut vertex = |p: [i64; 3]| -> usize {
+            *indexes.entry(p).or_insert_with(|| {
+                let warp = |c: i64| (FRAC_PI_4 * (2.0 * c as f64 / n as f64 - 1.0)).tan();
+                let direction = Vector3::new(warp(p[0]), warp(p[1]), warp(p[2])).normalize();
+                vertices.push(Point3::from_vec(direction * radius));
+                vertices.len() - 1
+            })
+        };
+
+        for (corner, u, v) in sides.iter() {
+            let at = |a: i64, b: i64| -> [i64; 3] {
+                [
+                    corner[0] + a * u[0] + b * v[0],
+                    corner[1] + a * u[1] + b * v[1],
+                    corner[2] + a * u[2] + b * v[2],
+                ]
+            };
+            for a in 0..n {
+                for b in 0..n {
+                    faces.push(vec![
+                        vertex(at(a, b)),
+                        vertex(at(a + 1, b)),
+                        vertex(at(a + 1, b + 1)),
+                        vertex(at(a, b + 1)),
+                    ]);
+                }
+            }
+        }
+
+        let faces: Vec<&[usize]> = faces.iter().map(|face| face.as_slice()).collect();
+        Polyhedron::unchecked(Point3::origin(), &vertices, &faces)
+    }
+}
+
+impl Seed for QuadSphere {
+    fn solid(&self) -> SeedSolid {
+        SeedSolid::Custom
+    }
+
+    fn polyhedron(&self) -> Polyhedron<VtFc> {
+        self.generate()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::polyhedron::{ConwayDescription, VertexAndFaceOps};
+
+    #[test]
+    fn quad_sphere() {
+        assert!(QuadSphere::new(0, 1.0).is_err());
+
+        let sphere = QuadSphere::new(3, 2.0).unwrap().generate();
+        let (vertices, faces) = sphere.vertices_and_faces();
+        assert!(faces.len() == 54);
+        assert!(vertices.len() == 56);
+        assert!(faces.iter().all(|face| face.len() == 4));
+        assert!(vertices.iter().all(|v| (v.to_vec().magnitude() - 2.0).abs() < 1e-9));
+
+        // Closed, wound consistently and outwards.
+        let faces: Vec<&[usize]> = faces.iter().collect();
+        assert!(Polyhedron::new(Point3::origin(), vertices, &faces).is_ok());
+        for face in faces {
+            let (a, b, c) = (vertices[face[0]], vertices[face[1]], vertices[face[2]]);
+            assert!((b - a).cross(c - a).dot(a.to_vec()) > 0.0);
+        }
+
+        let seed = QuadSphere::new(2, 1.0).unwrap();
+        let kis = ConwayDescription::new()
+            .seed(&seed).unwrap()
+            .kis().unwrap()
+            .emit().unwrap();
+        assert!(kis.produce().vertices_and_faces().1.len() == 96);
+    }
+}
@@ -0,0 +1,122 @@
+//! Smooth out the cells of a round polyhedron. Repeated operations such as several chamfers
+//! leave the faces near the old vertices squashed and those in between stretched.
+//!
+//! Each round every edge acts like a spring with the average edge length as its rest length.
+//! A vertex moves part of the way along the average pull of its edges and is put back on
+//! the sphere. Only the part of the move across the sphere counts so the vertices slide
+//! around the surface. This is Laplacian smoothing with each neighbour weighted by how
+//! stretched the edge to it is.
+//!
+//! Triangulated polyhedra can't have all their edges the same length so they only even out
+//! as far as the vertices where five and six triangles meet allow.
+use cgmath::prelude::*;
+use cgmath::Vector3;
+
+use super::{Polyhedron, VertexAndFaceOps, VtFc};
+
+/// How far of the way along the pull of its edges a vertex moves each round. Going all the
+/// way makes some vertices overshoot back and forth.
+const STEP: f64 = 0.5;
+
+impl Polyhedron<VtFc> {
+    /// Even out the edge lengths over `iterations` rounds keeping the faces. Every vertex
+    /// ends up on the bounding sphere.
+    pub fn relax(mut self, iterations: usize) -> Self {
+        let (center, radius) = self.bounding_sphere();
+        let edges = self.edges();
+        let mut vertices: Vec<Vector3<f64>> = self.data.vertices
+            .iter()
+            .map(|vertex| onto_sphere(vertex - center, radius))
+            .collect();
+
+        for _ in 0..iterations {
+            let length = |&(v1, v2): &(usize, usize)| vertices[v1].distance(vertices[v2]);
+            let rest = edges.iter().map(length).sum::<f64>() / edges.len() as f64;
+
+            // Each edge pulls its ends together when longer than the rest length and pushes
+            // them apart when shorter.
+            let mut moves = vec![Vector3::zero(); vertices.len()];
+            let mut degrees = vec![0usize; vertices.len()];
+            for edge in edges.iter() {
+                let (v1, v2) = *edge;
+                let stretch = length(edge);
+                if stretch == 0.0 {
+                    continue;
+                }
+                let pull = (vertices[v2] - vertices[v1]) * ((stretch - rest) / stretch);
+                moves[v1] += pull;
+                moves[v2] -= pull;
+                degrees[v1] += 1;
+                degrees[v2] += 1;
+            }
+
+            // Drop the part of each move along the line out from the center.
+            for ((vertex, towards), degree) in vertices.iter_mut().zip(moves).zip(degrees) {
+                if vertex.magnitude2() == 0.0 || degree == 0 {
+                    continue;
+                }
+                let towards = towards / degree as f64;
+                let normal = vertex.normalize();
+                let across = towards - normal * towards.dot(normal);
+                *vertex = onto_sphere(*vertex + across * STEP, radius);
+            }
+        }
+
+        self.data.vertices = vertices
+            .into_iter()
+            .map(|vertex| center + vertex)
+            .collect();
+        self
+    }
+}
+
+/// Out to `radius` from the center unless it's on the center.
+fn onto_sphere(v: Vector3<f64>, radius: f64) -> Vector3<f64> {
+    if v.magnitude2() == 0.0 {
+        v
+    } else {
+        v.normalize_to(radius)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cgmath::Point3;
+    use crate::polyhedron::Specification;
+
+    /// Longest edge over the shortest.
+    fn spread(p: &Polyhedron<VtFc>) -> f64 {
+        let vertices = &p.data.vertices;
+        let lengths: Vec<f64> = p.edges()
+            .iter()
+            .map(|&(v1, v2)| vertices[v1].distance(vertices[v2]))
+            .collect();
+        let longest = lengths.iter().cloned().fold(0.0, f64::max);
+        let shortest = lengths.iter().cloned().fold(f64::INFINITY, f64::min);
+
+        longest / shortest
+    }
+
+    #[test]
+    fn evens_out_edges() {
+        let p = Specification::parse("cccD").unwrap().produce();
+        let (_, radius) = p.bounding_sphere();
+        let faces = p.vertices_and_faces().1.clone();
+        let relaxed = p.clone().relax(50);
+
+        assert!(spread(&relaxed) < spread(&p));
+        assert!(relaxed.vertices_and_faces().1 == &faces);
+        assert!(relaxed.data.vertices
+            .iter()
+            .all(|v| (v.distance(Point3::origin()) - radius).abs() < 1e-9));
+        for face in faces.iter() {
+            let (a, b, c) = (
+                relaxed.data.vertices[face[0]],
+                relaxed.data.vertices[face[1]],
+                relaxed.data.vertices[face[2]],
+            );
+            assert!((b - a).cross(c - a).dot(a.to_vec()) > 0.0);
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! Build up a polyhedron one command at a time, such as typed in at a prompt.
+//!
+//! ```text
+//! > dual
+//! > kis 5
+//! > undo
+//! > export foo.obj
+//! ```
+//!
+//! Operations go by name or by their letter in the notation. A word that isn't a command is
+//! taken as notation so `dk5` runs kis on the pentagons then takes the dual. Each operation
+//! only runs on the polyhedron already built rather than starting again from the seed.
+use std::{error, fmt};
+use std::path::PathBuf;
+
+use super::{NotationError, Polyhedron, Specification, VtFc};
+
+/// Operation names and their letter in the notation.
+const OPERATIONS: &[(&str, &str)] = &[
+    ("dual", "d"),
+    ("kis", "k"),
+    ("truncate", "t"),
+    ("ambo", "a"),
+    ("gyro", "g"),
+    ("snub", "s"),
+    ("expand", "e"),
+    ("bevel", "b"),
+    ("ortho", "o"),
+    ("join", "j"),
+    ("chamfer", "c"),
+    ("whirl", "w"),
+    ("propeller", "p"),
+    ("meta", "m"),
+    ("needle", "n"),
+    ("zip", "z"),
+    ("loft", "l"),
+    ("lace", "L"),
+    ("subdivide", "u"),
+    ("spherize", "S"),
+    ("extrude", "x"),
+    ("inset", "i"),
+];
+
+pub const HELP: &str = "\
+Operations by name or letter: dual, kis [degree], truncate [degree], ambo, gyro, snub,
+expand, bevel, ortho, join, chamfer, whirl, propeller, meta, needle, zip, loft, lace,
+subdivide [frequency], spherize, extrude, inset.
+Notation such as dk5 runs several at once.
+undo            Take back the last operation.
+export <file>   Write out to .obj, .stl, .ply, .gltf, .glb, .svg, .json or .dot.
+show            Print the notation and how many vertices, edges and faces.
+help            Print this.
+quit            Stop.";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Run operations given in notation, most recent on the left.
+    Operation(String),
+    Undo,
+    Export(PathBuf),
+    Show,
+    Help,
+    Quit,
+}
+
+impl Command {
+    /// `None` for a blank line.
+    pub fn parse(line: &str) -> Result<Option<Command>, CommandError> {
+        let mut words = line.split_whitespace();
+        let word = match words.next() {
+            Some(word) => word,
+            None => return Ok(None),
+        };
+        let argument = words.next();
+        if let Some(extra) = words.next() {
+            return Err(CommandError::UnexpectedArgument(extra.to_owned()));
+        }
+        let no_argument = |command: Command| match argument {
+            Some(argument) => Err(CommandError::UnexpectedArgument(argument.to_owned())),
+            None => Ok(Some(command)),
+        };
+
+        match word {
+            "undo" => no_argument(Command::Undo),
+            "show" => no_argument(Command::Show),
+            "help" | "?" => no_argument(Command::Help),
+            "quit" | "exit" => no_argument(Command::Quit),
+            "export" => argument
+                .map(|path| Some(Command::Export(PathBuf::from(path))))
+                .ok_or(CommandError::MissingPath),
+            _ => {
+                let letter = OPERATIONS
+                    .iter()
+                    .find(|(name, _)| *name == word)
+                    .map(|(_, letter)| *letter);
+                match (letter, argument) {
+                    (Some(letter), Some(degree)) if "ktu".contains(letter) => {
+                        let degree: usize = degree
+                            .parse()
+                            .map_err(|_| CommandError::BadDegree(degree.to_owned()))?;
+                        Ok(Some(Command::Operation(format!("{}{}", letter, degree))))
+                    },
+                    (Some(letter), None) => Ok(Some(Command::Operation(letter.to_owned()))),
+                    (None, None) => Ok(Some(Command::Operation(word.to_owned()))),
+                    (_, Some(argument)) => {
+                        Err(CommandError::UnexpectedArgument(argument.to_owned()))
+                    },
+                }
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommandError {
+    MissingPath,
+    BadDegree(String),
+    UnexpectedArgument(String),
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CommandError::MissingPath => write!(f, "Export where? Give a file name."),
+            CommandError::BadDegree(d) => write!(f, "Degree '{}' isn't a whole number.", d),
+            CommandError::UnexpectedArgument(a) => write!(f, "Didn't expect '{}'.", a),
+        }
+    }
+}
+
+impl error::Error for CommandError {
+    fn description(&self) -> &str {
+        "Error reading a command."
+    }
+}
+
+/// The polyhedron after every operation so far so they can be undone.
+#[derive(Debug, Clone)]
+pub struct Session {
+    history: Vec<(Specification, Polyhedron<VtFc>)>,
+}
+
+impl Session {
+    /// Start from the polyhedron made by `notation`, usually just a seed.
+    pub fn new(notation: &str) -> Result<Self, NotationError> {
+        let specification = Specification::parse(notation)?;
+        let polyhedron = specification.produce();
+        Ok(Session { history: vec![(specification, polyhedron)] })
+    }
+
+    /// Run `operations`, given in notation, on the current polyhedron.
+    pub fn apply(&mut self, operations: &str) -> Result<&Polyhedron<VtFc>, NotationError> {
+        let (base, produced) = self.current();
+        let notation = format!("{}{}", operations, base.notation());
+        let specification = Specification::parse(&notation)?;
+        let polyhedron = specification
+            .produce_from(base, produced.clone())
+            .expect("Made by adding operations to the base.");
+        self.history.push((specification, polyhedron));
+
+        Ok(self.polyhedron())
+    }
+
+    /// Go back to before the last operation. `false` when there's nothing left to undo.
+    pub fn undo(&mut self) -> bool {
+        if self.history.len() > 1 {
+            self.history.pop();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn polyhedron(&self) -> &Polyhedron<VtFc> {
+        &self.current().1
+    }
+
+    pub fn notation(&self) -> &str {
+        self.current().0.notation()
+    }
+
+    fn current(&self) -> &(Specification, Polyhedron<VtFc>) {
+        self.history.last().expect("Always holds the start.")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::VertexAndFaceOps;
+
+    #[test]
+    fn parse_commands() {
+        let parse = |line| Command::parse(line).unwrap();
+        assert!(parse("  ").is_none());
+        assert!(parse("dual") == Some(Command::Operation("d".to_owned())));
+        assert!(parse("subdivide 3") == Some(Command::Operation("u3".to_owned())));
+        assert!(parse("kis 5") == Some(Command::Operation("k5".to_owned())));
+        assert!(parse("dk5") == Some(Command::Operation("dk5".to_owned())));
+        assert!(parse("undo") == Some(Command::Undo));
+        assert!(parse("export foo.obj") == Some(Command::Export(PathBuf::from("foo.obj"))));
+
+        assert!(Command::parse("export") == Err(CommandError::MissingPath));
+        assert!(Command::parse("kis five") == Err(CommandError::BadDegree("five".to_owned())));
+        assert!(Command::parse("dual 5").is_err());
+        assert!(Command::parse("undo now").is_err());
+    }
+
+    #[test]
+    fn apply_and_undo() {
+        let mut session = Session::new("D").unwrap();
+        session.apply("k").unwrap();
+        session.apply("d").unwrap();
+        assert!(session.notation() == "dkD");
+
+        let direct = Specification::parse("dkD").unwrap().produce();
+        assert!(session.polyhedron().vertices_and_faces().0.len()
+            == direct.vertices_and_faces().0.len());
+
+        assert!(session.apply("q").is_err());
+        assert!(session.notation() == "dkD");
+
+        assert!(session.undo());
+        assert!(session.undo());
+        assert!(session.notation() == "D");
+        assert!(!session.undo());
+    }
+}
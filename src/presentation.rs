@@ -1,15 +1,40 @@
 //! Present the whole thing
 
-use log::{info, trace};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use log::{info, trace, warn};
 use cgmath::{Vector3, Rad, Matrix4, Point3, Deg};
-use wgpu::winit;
+use winit::dpi::{PhysicalPosition, PhysicalSize};
+use winit::event::{ElementState, Event, KeyEvent, WindowEvent};
+use winit::event_loop::{ControlFlow, EventLoop};
+use winit::keyboard::{KeyCode, PhysicalKey};
+use winit::window::WindowBuilder;
+
+use crate::Error;
+use crate::input::{self, ActionState, Playback, Recording};
 
-use crate::input;
+/// How often to look for changes to the shader files.
+const SHADER_POLL: Duration = Duration::from_millis(500);
+
+/// Key bindings looked for in the working directory. The defaults are used without it.
+const BINDINGS_FILE: &str = "bindings.toml";
 
 mod show;
 mod camera;
+mod screenshot;
+mod turntable;
+mod spin;
+mod offscreen;
+mod path;
 
 use camera::{View, Perspective, Camera};
+pub use camera::Preset;
+pub use screenshot::Screenshot;
+pub use turntable::Turntable;
+pub use spin::Spin;
+pub use offscreen::Offscreen;
+pub use path::{CameraPath, Easing};
 
 #[derive(Debug, Copy, Clone)]
 pub struct Rot {
@@ -33,14 +58,32 @@ impl Default for Rot {
 /// All types that want to be shown must implement this trait. This must be the result of
 /// calling `init` from implementing the `Initializable` trait.
 pub trait Renderable {
-    //fn resize(&mut self, desc: &wgpu::SwapChainDescriptor, device: &mut wgpu::Device);
+    /// The window was resized and the surface reconfigured to match `config`. Only needs
+    /// doing something about when holding on to things sized to fit the window.
+    fn resize(&mut self, _config: &wgpu::SurfaceConfiguration, _device: &wgpu::Device) {}
+
+    /// Draw onto `target`. Either the frame from the surface or an offscreen texture.
     fn render(
         &mut self,
         projection: &Matrix4<f32>,
         rotation: &Matrix4<f32>,
-        frame: &wgpu::SwapChainOutput,
-        device: &mut wgpu::Device,
+        eye: &Point3<f32>,
+        target: &wgpu::TextureView,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
     );
+
+    /// The face hit first by the ray starting at `origin` heading in `direction`. The ray is
+    /// in the space of the geometry before it's rotated.
+    fn pick(&self, _origin: Point3<f32>, _direction: Vector3<f32>) -> Option<usize> {
+        None
+    }
+
+    /// Draw `face` in the highlight colour instead of its own. `None` clears the highlight.
+    fn highlight(&mut self, _face: Option<usize>, _queue: &wgpu::Queue) {}
+
+    /// Called every so often to pick up any changes to the shader files.
+    fn reload_shaders(&mut self, _device: &wgpu::Device) {}
 }
 
 /// All types that want to be rendered must be convertible via this trait into a
@@ -50,99 +93,411 @@ pub trait Initializable {
     type Ready;
     
     fn init(
-        self, desc: &wgpu::SwapChainDescriptor, device: &mut wgpu::Device
+        self, config: &wgpu::SurfaceConfiguration, device: &wgpu::Device, queue: &wgpu::Queue,
     ) -> Self::Ready;
 }
 
 trait Presentation {
     fn update(&mut self, movement: Vector3<f32>, rot: Rot) -> (&View<f32>, &Rot);    
-    fn present_frame(&mut self, frame: &wgpu::SwapChainOutput, device: &mut wgpu::Device);
+    fn orbit(&mut self, yaw: Rad<f32>, pitch: Rad<f32>) -> &View<f32>;
+    fn look(&mut self, position: Point3<f32>, target: Point3<f32>) -> &View<f32>;
+    fn preset(&mut self, preset: Preset) -> &View<f32>;
+    fn resize(&mut self, config: &wgpu::SurfaceConfiguration, device: &wgpu::Device);
+    fn present_frame(
+        &mut self, target: &wgpu::TextureView, device: &wgpu::Device, queue: &wgpu::Queue,
+    );
+    fn reload_shaders(&mut self, device: &wgpu::Device);
+
+    /// Highlight the face under the point on the screen given in device coordinates and
+    /// return its index. Clears the highlight if there's no face there.
+    fn pick(&mut self, x: f32, y: f32, queue: &wgpu::Queue) -> Option<usize>;
 }
 
 /// Taken heavily from the examples in wgpu crate. I have no idea otherwise how to use.
-pub fn run<T>(title: &str, scene: T) -> Result<(), Box<dyn std::error::Error>>
+pub fn run<T>(title: &str, scene: T) -> crate::Result<()>
 where T: Initializable,
       T::Ready: Renderable,
 {
-    info!("Initializing the renderer.");
-    
-    let instance = wgpu::Instance::new();
-    let adapter = instance.get_adapter(&wgpu::AdapterDescriptor {
-        power_preference: wgpu::PowerPreference::LowPower,
-    });
-    let mut device = adapter.create_device(&wgpu::DeviceDescriptor {
-        extensions: wgpu::Extensions {
-            anisotropic_filtering: false,
-        },
-    });
+    run_picking(title, scene, |_| ())
+}
+
+/// Same as `run` but clicking on the shape highlights the face under the cursor and hands
+/// its index to `on_pick`. Clicking off the shape hands over `None`.
+pub fn run_picking<T, F>(
+    title: &str, scene: T, on_pick: F,
+) -> crate::Result<()>
+where T: Initializable,
+      T::Ready: Renderable,
+      F: FnMut(Option<usize>),
+{
+    run_inner(title, scene, Spin::default().disabled(), Script::Live, on_pick, |_, _, _, _| ())
+}
+
+/// Same as `run` but `animate` gets to change the scene before every frame. It's handed
+/// how long it has been since the window opened.
+pub fn run_animated<T, A>(
+    title: &str, scene: T, animate: A,
+) -> crate::Result<()>
+where T: Initializable,
+      T::Ready: Renderable,
+      A: FnMut(&mut T::Ready, Duration, &wgpu::Device, &wgpu::Queue),
+{
+    run_inner(title, scene, Spin::default().disabled(), Script::Live, |_| (), animate)
+}
+
+/// Same as `run` but the scene turns by itself whenever it's been left alone for a while.
+/// The toggle key, space by default, stops and starts it.
+pub fn run_spinning<T>(
+    title: &str, scene: T, spin: Spin,
+) -> crate::Result<()>
+where T: Initializable,
+      T::Ready: Renderable,
+{
+    run_inner(title, scene, spin, Script::Live, |_| (), |_, _, _, _| ())
+}
 
+/// Same as `run` but the keyboard actions are saved to `filepath` when the window closes.
+/// See `input::Recording` for what's kept.
+pub fn run_recording<T, P>(
+    title: &str, scene: T, filepath: P,
+) -> crate::Result<()>
+where T: Initializable,
+      T::Ready: Renderable,
+      P: Into<PathBuf>,
+{
+    let script = Script::Record(filepath.into(), Recording::new());
+    run_inner(title, scene, Spin::default().disabled(), script, |_| (), |_, _, _, _| ())
+}
+
+/// Same as `run` but the keyboard is ignored in favour of playing back `recording`. The
+/// window stays open afterwards until closed.
+pub fn run_playback<T>(
+    title: &str, scene: T, recording: Recording,
+) -> crate::Result<()>
+where T: Initializable,
+      T::Ready: Renderable,
+{
+    let script = Script::Play(recording.play());
+    run_inner(title, scene, Spin::default().disabled(), script, |_| (), |_, _, _, _| ())
+}
+
+/// Same as `run` but the camera flies along `path` and the keyboard is ignored. The
+/// window stays open at the end of the path until closed.
+pub fn run_path<T>(
+    title: &str, scene: T, path: CameraPath,
+) -> crate::Result<()>
+where T: Initializable,
+      T::Ready: Renderable,
+{
+    let script = Script::Fly(path);
+    run_inner(title, scene, Spin::default().disabled(), script, |_| (), |_, _, _, _| ())
+}
+
+/// Where the keyboard actions come from and whether they're kept.
+enum Script {
+    Live,
+    Record(PathBuf, Recording),
+    Play(Playback),
+    Fly(CameraPath),
+}
+
+fn run_inner<T, F, A>(
+    title: &str, scene: T, mut spin: Spin, mut script: Script, mut on_pick: F, mut animate: A,
+) -> crate::Result<()>
+where T: Initializable,
+      T::Ready: Renderable,
+      F: FnMut(Option<usize>),
+      A: FnMut(&mut T::Ready, Duration, &wgpu::Device, &wgpu::Queue),
+{
     info!("Setting up the window.");
-    let mut event_loop = winit::EventsLoop::new();
-    let window = winit::Window::new(&event_loop)?;
-    window.set_title(title);
-    let w_size = window
-        .get_inner_size()
-        .unwrap()
-        .to_physical(window.get_hidpi_factor());
-    let w_width = w_size.width.round() as f32;
-    let w_height = w_size.height.round() as f32;
+    let event_loop = EventLoop::new()?;
+    let window = WindowBuilder::new().with_title(title).build(&event_loop)?;
+    let mut w_size = window.inner_size();
+    let w_width = w_size.width.max(1) as f32;
+    let w_height = w_size.height.max(1) as f32;
 
-    //                                                                       [View Dist].
-    let perspective = Perspective::new(Deg(45f32), w_width / w_height, 1f32, 100f32);
-    let view = View::new(
-        Point3::new(0f32, -4f32, 4f32), Point3::new(0f32, 0f32, 0f32), -Vector3::unit_z()
-    );
-    let camera = Camera::new(perspective, view);
+    let camera = default_camera(w_width / w_height);
     
-    let bindings = input::Bindings::default();
+    let bindings = input::Bindings::load_or_default(BINDINGS_FILE);
     let mut act_state: u16 = 0;
+    let mut mouse = input::Mouse::default();
 
-    let surface = instance.create_surface(&window);
-    let desc = wgpu::SwapChainDescriptor {
-        usage: wgpu::TextureUsageFlags::OUTPUT_ATTACHMENT,
-        format: wgpu::TextureFormat::Bgra8Unorm,
-        width: w_width as u32,
-        height: w_height as u32,
-    };
-    let mut swap_chain = device.create_swap_chain(&surface, &desc);
+    info!("Initializing the renderer.");
+    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+    let surface = instance.create_surface(&window)?;
+    let adapter = open_adapter(&instance, Some(&surface))?;
+
+    // Screenshots are copied straight out of the frame so the surface has to allow that.
+    // Colours are left as the shaders work them out rather than taken as sRGB. A
+    // multisampled scene draws into a texture of its own and resolves it into the frame.
+    let mut config = surface
+        .get_default_config(&adapter, w_width as u32, w_height as u32)
+        .ok_or(Error::NoAdapter)?;
+    let capabilities = surface.get_capabilities(&adapter);
+    config.usage |= capabilities.usages & wgpu::TextureUsages::COPY_SRC;
+    if let Some(format) = capabilities.formats.iter().find(|format| !format.is_srgb()) {
+        config.format = *format;
+    }
+    let (device, queue) = open_device(&adapter, config.format)?;
+    surface.configure(&device, &config);
 
     info!("Initializing the scene.");
-    let mut show = show::Show::new(scene.init(&desc, &mut device), camera);
+    let mut show = show::Show::new(scene.init(&config, &device, &queue), camera);
 
     info!("Entering event loop.");
-    let mut running = true;
-    while running {
-        event_loop.poll_events(|event| match event {
-            winit::Event::WindowEvent { event, .. } => match event {
-                winit::WindowEvent::KeyboardInput {
-                    input: winit::KeyboardInput {
-                        virtual_keycode: Some(winit::VirtualKeyCode::Escape),
-                        state: winit::ElementState::Pressed,
+    let mut screenshots: Vec<Screenshot> = Vec::new();
+    let started = Instant::now();
+    let mut shaders_polled = started;
+    let mut last_frame = started;
+    let mut last_input: Option<Instant> = None;
+    let mut resized = false;
+    event_loop.set_control_flow(ControlFlow::Poll);
+    event_loop.run(|event, elwt| {
+        match event {
+            Event::WindowEvent { event: WindowEvent::RedrawRequested, .. } => (),
+            Event::WindowEvent { event, .. } => {
+                match event {
+                    WindowEvent::KeyboardInput {
+                        event: KeyEvent {
+                            physical_key: PhysicalKey::Code(KeyCode::Escape),
+                            state: ElementState::Pressed,
+                            ..
+                        },
                         ..
+                    }
+                    | WindowEvent::CloseRequested => {
+                        elwt.exit();
+                    },
+                    WindowEvent::Resized(size) => {
+                        w_size = size;
+                        resized = true;
+                    },
+                    WindowEvent::KeyboardInput { event: key_event, .. } => {
+                        let action = match key_event.physical_key {
+                            PhysicalKey::Code(code) => bindings.action(&code),
+                            PhysicalKey::Unidentified(_) => None,
+                        };
+                        match (&mut script, action) {
+                            (Script::Play(_), _) | (Script::Fly(_), _) => return,
+                            (Script::Record(_, recording), Some(action)) => {
+                                recording.record(started.elapsed(), action, key_event.state);
+                            },
+                            _ => (),
+                        }
+                        last_input = Some(Instant::now());
+                        let maybie = input::handle_keyboard(
+                            &key_event, &bindings, &mut act_state
+                        );
+                        if let Some((camera_movement, rot_x, rot_y)) = maybie {
+                            let rot = Rot::new(rot_x, rot_y, Rad(0.0));
+                            let (view, rot) = show.update(camera_movement, rot);
+                            trace!("{:?} && {:?}", view, rot);
+                        }
                     },
-                    ..
+                    WindowEvent::CursorMoved { position, .. } => {
+                        let maybie = input::handle_cursor_moved(
+                            position, &bindings, &mut mouse,
+                        );
+                        if let Some((yaw, pitch)) = maybie {
+                            last_input = Some(Instant::now());
+                            let view = show.orbit(yaw, pitch);
+                            trace!("{:?}", view);
+                        }
+                        if let Some((rot_x, rot_y)) = input::handle_mouse(&bindings, &mouse) {
+                            last_input = Some(Instant::now());
+                            let (view, rot) = show.update(
+                                Vector3::new(0.0, 0.0, 0.0), Rot::new(rot_x, rot_y, Rad(0.0)),
+                            );
+                            trace!("{:?} && {:?}", view, rot);
+                        }
+                    },
+                    WindowEvent::CursorLeft { .. } => mouse.left(),
+                    WindowEvent::MouseInput { state, button, .. } => {
+                        last_input = Some(Instant::now());
+                        let maybie = input::handle_mouse_button(
+                            button, state, &bindings, &mut mouse,
+                        );
+                        if let Some(cursor) = maybie {
+                            let (x, y) = device_coordinates(cursor, w_size);
+                            let face = show.pick(x, y, &queue);
+                            trace!("Picked face {:?}", face);
+                            on_pick(face);
+                        }
+                    },
+                    _ => (),
                 }
-                | winit::WindowEvent::CloseRequested => {
-                    running = false;
-                },
-                winit::WindowEvent::KeyboardInput { input: keyboard_input, .. } => {
-                    let maybie = input::handle_keyboard(
-                        &keyboard_input, &bindings, &mut act_state
-                    );
-                    if let Some((camera_movement, rot_x, rot_y)) = maybie {
-                        let rot = Rot::new(rot_x, rot_y, Rad(0.0));
-                        let (view, rot) = show.update(camera_movement, rot);
-                        trace!("{:?} && {:?}", view, rot);
-                    }
-                },
-                _ => (),
+                return;
             },
-            _ => (),
-        });
+            // Draw again as soon as the events so far are dealt with.
+            Event::AboutToWait => {
+                window.request_redraw();
+                return;
+            },
+            _ => return,
+        }
+
+        // Only reconfigure the surface once however many resizes came in. A minimized
+        // window has no area to draw on so wait until it's restored.
+        let (width, height) = (w_size.width, w_size.height);
+        if width == 0 || height == 0 {
+            return;
+        }
+        if resized && (width, height) != (config.width, config.height) {
+            info!("Resizing to {}x{}.", width, height);
+            config.width = width;
+            config.height = height;
+            surface.configure(&device, &config);
+            show.resize(&config, &device);
+        }
+        resized = false;
+
+        if shaders_polled.elapsed() >= SHADER_POLL {
+            shaders_polled = Instant::now();
+            show.reload_shaders(&device);
+        }
+
+        if let Script::Play(playback) = &mut script {
+            for event in playback.due(started.elapsed()) {
+                let (camera_movement, rot_x, rot_y) = input::handle_action(
+                    event.action(), event.state(), &bindings, &mut act_state,
+                );
+                let rot = Rot::new(rot_x, rot_y, Rad(0.0));
+                let (view, rot) = show.update(camera_movement, rot);
+                trace!("Played {:?} && {:?}", view, rot);
+            }
+        }
+
+        if let Script::Fly(path) = &script {
+            if let Some((position, target)) = path.view_at(started.elapsed()) {
+                let view = show.look(position, target);
+                trace!("Flying {:?}", view);
+            }
+        }
+
+        if let Some(preset) = act_state.preset() {
+            let view = show.preset(preset);
+            trace!("{:?} view {:?}", preset, view);
+        }
+
+        // Toggling counts as leaving the scene alone so it starts turning straight away.
+        if act_state.toggle_spin() {
+            spin.toggle();
+            last_input = None;
+            info!("Spin {}.", if spin.is_enabled() { "on" } else { "off" });
+        }
+        let frame_time = last_frame.elapsed();
+        last_frame = Instant::now();
+        let turn = spin.increment(frame_time, last_input.map(|input| input.elapsed()));
+        show.update(Vector3::new(0.0, 0.0, 0.0), turn);
+
+        animate(show.scene_mut(), started.elapsed(), &device, &queue);
+
+        // A lost or outdated surface only needs configuring again to carry on.
+        let frame = match surface.get_current_texture() {
+            Ok(frame) => frame,
+            Err(e) => {
+                warn!("Skipping a frame: {}", e);
+                if let wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated = e {
+                    surface.configure(&device, &config);
+                }
+                return;
+            },
+        };
+        let view = frame.texture.create_view(&wgpu::TextureViewDescriptor::default());
+        show.present_frame(&view, &device, &queue);
+
+        if act_state.screenshot() {
+            if config.usage.contains(wgpu::TextureUsages::COPY_SRC) {
+                let path = format!("polyorb-{}.png", timestamp());
+                screenshots.push(Screenshot::capture(path, &frame, &device, &queue));
+            } else {
+                warn!("The window can't be copied out of for screenshots.");
+            }
+        }
+        frame.present();
 
-        let frame = swap_chain.get_next_texture();
-        show.present_frame(&frame, &mut device);
+        // Gets the screenshots read back.
+        device.poll(wgpu::Maintain::Poll);
+        screenshots.retain(|screenshot| !screenshot.is_done());
+    })?;
+
+    if let Script::Record(filepath, recording) = script {
+        info!("Saving {} actions to {}.", recording.events().len(), filepath.display());
+        recording.save(filepath)?;
     }
     
     Ok(())
 }
+
+/// The video card or whatever else there is to draw with. It has to be able to draw onto
+/// `surface` if given one.
+fn open_adapter(
+    instance: &wgpu::Instance, surface: Option<&wgpu::Surface>,
+) -> crate::Result<wgpu::Adapter> {
+    let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+        power_preference: wgpu::PowerPreference::LowPower,
+        compatible_surface: surface,
+        force_fallback_adapter: false,
+    }));
+
+    adapter.ok_or(Error::NoAdapter)
+}
+
+/// The video device drawing into images of `format` and the queue to send it work on.
+/// Only one and four samples per pixel can be drawn with unless the device is opened with
+/// the adapter's own format features, so they're asked for when eight are supported.
+fn open_device(
+    adapter: &wgpu::Adapter, format: wgpu::TextureFormat,
+) -> crate::Result<(wgpu::Device, wgpu::Queue)> {
+    let specific = wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES;
+    let eight = adapter.get_texture_format_features(format).flags.sample_count_supported(8);
+    let required_features = if adapter.features().contains(specific) && eight {
+        specific
+    } else {
+        wgpu::Features::empty()
+    };
+
+    let desc = wgpu::DeviceDescriptor { required_features, ..Default::default() };
+    Ok(pollster::block_on(adapter.request_device(&desc, None))?)
+}
+
+/// Looking down on the scene at an angle from a little way off.
+fn default_camera(aspect_ratio: f32) -> Camera<f32> {
+    //                                                                       [View Dist].
+    let perspective = Perspective::new(Deg(45f32), aspect_ratio, 1f32, 100f32);
+    let view = View::new(
+        Point3::new(0f32, -4f32, 4f32), Point3::new(0f32, 0f32, 0f32), -Vector3::unit_z()
+    );
+    Camera::new(perspective, view)
+}
+
+/// Where `cursor` is in device coordinates going from -1 to 1 left to right and bottom to
+/// top as wgpu has them.
+fn device_coordinates(cursor: PhysicalPosition<f64>, size: PhysicalSize<u32>) -> (f32, f32) {
+    let x = 2.0 * cursor.x / size.width as f64 - 1.0;
+    let y = 1.0 - 2.0 * cursor.y / size.height as f64;
+    (x as f32, y as f32)
+}
+
+/// Seconds since the epoch for naming screenshots.
+fn timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cursor_to_device_coordinates() {
+        let size = PhysicalSize::new(800, 600);
+
+        assert!(device_coordinates(PhysicalPosition::new(0.0, 0.0), size) == (-1.0, 1.0));
+        assert!(device_coordinates(PhysicalPosition::new(800.0, 600.0), size) == (1.0, -1.0));
+        assert!(device_coordinates(PhysicalPosition::new(400.0, 300.0), size) == (0.0, 0.0));
+        assert!(device_coordinates(PhysicalPosition::new(600.0, 150.0), size) == (0.5, 0.5));
+    }
+}
@@ -1,6 +1,7 @@
 //! Perspective handling and viewport.
 
-use cgmath::{BaseFloat, Rad, Vector3, Point3, Matrix4};
+use cgmath::{BaseFloat, Basis3, Rad, Vector3, Vector4, Point3, Matrix4};
+use cgmath::prelude::*;
 
 #[derive(Debug, Copy, Clone)]
 pub struct Perspective<S: BaseFloat> {
@@ -18,6 +19,24 @@ impl<S: BaseFloat>  Perspective<S> {
     pub fn as_matrix(&self) -> Matrix4<S> {
         cgmath::perspective(self.fov, self.aspect_ratio, self.near, self.far)
     }
+
+    pub fn set_aspect_ratio(&mut self, aspect_ratio: S) {
+        self.aspect_ratio = aspect_ratio;
+    }
+}
+
+/// Named places to put the camera. Each keeps to the distance and the point looked at that
+/// the camera started with so they also bring it back from wherever it was moved to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Preset {
+    /// Exactly as the camera started.
+    Home,
+    /// Level with what it's looking at.
+    Front,
+    /// Straight down on what it's looking at.
+    Top,
+    /// Equally along all three axes from what it's looking at.
+    Isometric,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -39,26 +58,205 @@ impl<S: BaseFloat> View<S> {
     pub fn move_camera(&mut self, increment: Vector3<S>) {
         self.from += increment;
     }
+
+    pub fn from(&self) -> Point3<S> {
+        self.from
+    }
+
+    /// Swing the camera around the point it's looking at keeping the same distance. `yaw`
+    /// turns it around the up direction and `pitch` raises it towards the up direction. It
+    /// stops just short of looking straight down the up direction so the view never flips.
+    pub fn orbit(&mut self, yaw: Rad<S>, pitch: Rad<S>) {
+        let up = self.up.normalize();
+        let offset = Basis3::from_axis_angle(up, yaw).rotate_vector(self.from - self.at);
+        let right = offset.cross(up);
+        if right.magnitude2() == S::zero() {
+            self.from = self.at + offset;
+            return;
+        }
+
+        let limit = Rad::<S>::turn_div_4().0 - S::from(0.01).unwrap();
+        let elevation = up.dot(offset.normalize()).max(-S::one()).min(S::one()).asin();
+        let raised = (elevation + pitch.0).max(-limit).min(limit);
+        let offset = Basis3::from_axis_angle(right.normalize(), Rad(raised - elevation))
+            .rotate_vector(offset);
+
+        self.from = self.at + offset;
+    }
+
+    /// The same distance away looking at the same point but turned `yaw` around the up
+    /// direction and raised `elevation` above level. Raised is towards whichever end of the
+    /// up direction the view is already nearer, so the top is the side it started on.
+    fn facing(&self, yaw: Rad<S>, elevation: Rad<S>) -> Self {
+        let offset = self.from - self.at;
+        let mut up = self.up.normalize();
+        if up.dot(offset) < S::zero() {
+            up = -up;
+        }
+        let level = offset - up * up.dot(offset);
+        if level.magnitude2() == S::zero() {
+            return *self;
+        }
+        let level = Basis3::from_axis_angle(up, yaw).rotate_vector(level.normalize());
+
+        // The same limit as `orbit` so looking from the top doesn't flip the view.
+        let limit = Rad::<S>::turn_div_4().0 - S::from(0.01).unwrap();
+        let elevation = elevation.0.max(-limit).min(limit);
+        let direction = level * elevation.cos() + up * elevation.sin();
+
+        View { from: self.at + direction * offset.magnitude(), ..*self }
+    }
 }
 
 #[derive(Debug, Copy, Clone)]
 pub struct Camera<S: BaseFloat> {
     perspective: Perspective<S>,
     view: View<S>,
+    home: View<S>,
 }
 
 impl<S: BaseFloat> Camera<S> {
+    /// `view` is kept as home for the presets to go back to.
     pub fn new(perspective: Perspective<S>, view: View<S>) -> Self {
-        Camera { perspective, view }
+        Camera { perspective, view, home: view }
     }
 
     pub fn projection(&self) -> Matrix4<S> {
         self.perspective.as_matrix() * self.view.as_matrix()
     }
 
+    /// Where the camera is looking from.
+    pub fn position(&self) -> Point3<S> {
+        self.view.from()
+    }
+
     /// Move the camera position by the supplied increment and return a ref to the view.
     pub fn move_camera(&mut self, increment: Vector3<S>) -> &View<S> {
         self.view.move_camera(increment);
         &self.view
     }
+
+    /// Match the width over height of the window being drawn into.
+    pub fn aspect_ratio(&mut self, aspect_ratio: S) {
+        self.perspective.set_aspect_ratio(aspect_ratio);
+    }
+
+    /// Orbit the camera around what it's looking at and return a ref to the view.
+    pub fn orbit(&mut self, yaw: Rad<S>, pitch: Rad<S>) -> &View<S> {
+        self.view.orbit(yaw, pitch);
+        &self.view
+    }
+
+    /// Put the camera at `position` looking at `target` and return a ref to the view.
+    pub fn look(&mut self, position: Point3<S>, target: Point3<S>) -> &View<S> {
+        self.view = View::new(position, target, self.view.up);
+        &self.view
+    }
+
+    /// Jump to one of the preset views and return a ref to it.
+    pub fn preset(&mut self, preset: Preset) -> &View<S> {
+        let zero = Rad(S::zero());
+        self.view = match preset {
+            Preset::Home => self.home,
+            Preset::Front => self.home.facing(zero, zero),
+            Preset::Top => self.home.facing(zero, Rad::turn_div_4()),
+            Preset::Isometric => {
+                let elevation = (S::one() / S::from(3).unwrap().sqrt()).asin();
+                self.home.facing(Rad::full_turn() / S::from(8).unwrap(), Rad(elevation))
+            },
+        };
+        &self.view
+    }
+
+    /// The ray from the near plane out through a point on the screen. The point is given in
+    /// device coordinates going from -1 to 1 left to right and bottom to top as wgpu has
+    /// them. Returns the start of the ray and its normalized direction.
+    pub fn ray(&self, x: S, y: S) -> Option<(Point3<S>, Vector3<S>)> {
+        // The scene flips y when rendering as the projection has it pointing down.
+        let flip = Matrix4::from_nonuniform_scale(S::one(), -S::one(), S::one());
+        let inverse = (flip * self.projection()).invert()?;
+        let unproject = |z: S| {
+            Point3::from_homogeneous(inverse * Vector4::new(x, y, z, S::one()))
+        };
+        let near = unproject(-S::one());
+        let far = unproject(S::one());
+
+        Some((near, (far - near).normalize()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cgmath::Deg;
+
+    #[test]
+    fn ray_through_the_middle() {
+        let perspective = Perspective::new(Deg(45.0), 1.5, 1.0f64, 100.0);
+        let view = View::new(
+            Point3::new(0.0, -4.0, 4.0), Point3::new(0.0, 0.0, 0.0), -Vector3::unit_z()
+        );
+        let (origin, direction) = Camera::new(perspective, view).ray(0.0, 0.0).unwrap();
+
+        let towards = Vector3::new(0.0, 1.0, -1.0).normalize();
+        assert!(direction.dot(towards) > 1.0 - 1e-9);
+        assert!((origin.distance(Point3::new(0.0, -4.0, 4.0)) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn ray_through_the_top() {
+        let perspective = Perspective::new(Deg(45.0), 1.5, 1.0f64, 100.0);
+        let view = View::new(
+            Point3::new(0.0, -4.0, 4.0), Point3::new(0.0, 0.0, 0.0), -Vector3::unit_z()
+        );
+        let camera = Camera::new(perspective, view);
+        let (_, middle) = camera.ray(0.0, 0.0).unwrap();
+        let (_, top) = camera.ray(0.0, 1.0).unwrap();
+
+        // Up the screen is up the z axis the scene is drawn with.
+        assert!(top.z > middle.z);
+    }
+
+    #[test]
+    fn orbit() {
+        let from = Point3::new(0.0, -4.0, 4.0f64);
+        let mut view = View::new(from, Point3::new(0.0, 0.0, 0.0), -Vector3::unit_z());
+
+        for _ in 0..4 {
+            view.orbit(Deg(90.0).into(), Rad(0.0));
+        }
+        assert!(view.from.distance(from) < 1e-9);
+
+        // Tilting well past the up direction stops short of it.
+        view.orbit(Rad(0.0), Deg(500.0).into());
+        let direction = (view.from - view.at).normalize();
+        assert!((view.from.distance(view.at) - from.distance(view.at)).abs() < 1e-9);
+        assert!(direction.dot(-Vector3::unit_z()) > 0.99);
+        assert!(direction.dot(-Vector3::unit_z()) < 1.0);
+    }
+
+    #[test]
+    fn presets_come_back_home() {
+        let perspective = Perspective::new(Deg(45.0), 1.5, 1.0f64, 100.0);
+        let home = View::new(
+            Point3::new(0.0, -4.0, 4.0), Point3::new(0.0, 0.0, 0.0), -Vector3::unit_z()
+        );
+        let mut camera = Camera::new(perspective, home);
+        let distance = home.from.distance(home.at);
+
+        camera.move_camera(Vector3::new(100.0, 0.0, 0.0));
+        let front = *camera.preset(Preset::Front);
+        assert!(front.from.distance(Point3::new(0.0, -distance, 0.0)) < 1e-9);
+
+        let top = *camera.preset(Preset::Top);
+        assert!((top.from.distance(top.at) - distance).abs() < 1e-9);
+        assert!((top.from - top.at).normalize().dot(Vector3::unit_z()) > 0.99);
+
+        let isometric = camera.preset(Preset::Isometric).from;
+        assert!((isometric.x.abs() - isometric.y.abs()).abs() < 1e-9);
+        assert!((isometric.y.abs() - isometric.z.abs()).abs() < 1e-9);
+
+        camera.move_camera(Vector3::new(0.0, 0.0, -50.0));
+        assert!(camera.preset(Preset::Home).from == home.from);
+    }
 }
@@ -0,0 +1,120 @@
+//! Render a scene to PNGs without opening a window.
+//!
+//! The device, the scene's pipeline and the texture drawn into are set up once and kept for
+//! every capture. Change the scene between captures with `update`, such as to swap in new
+//! geometry with `Scene::replace_geometry`, to render many shapes for the cost of one.
+use std::{thread, time};
+use std::path::PathBuf;
+
+use cgmath::{Point3, Vector3};
+use log::info;
+
+use crate::Error;
+use super::show::Show;
+use super::{Initializable, Presentation, Renderable, Rot, Screenshot};
+
+/// How many times to poll the video device for the last captures before giving up on them.
+const FLUSH_ATTEMPTS: usize = 200;
+
+pub struct Offscreen<R: Renderable> {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    target: wgpu::Texture,
+    target_view: wgpu::TextureView,
+    show: Show<R>,
+    screenshots: Vec<Screenshot>,
+}
+
+impl<R: Renderable> Offscreen<R> {
+    /// Draw `scene` onto a `width` by `height` texture from the same view as a window gets.
+    pub fn new<T>(scene: T, width: u32, height: u32) -> crate::Result<Self>
+    where T: Initializable<Ready = R>,
+    {
+        let (width, height) = (width.max(1), height.max(1));
+
+        info!("Initializing the renderer.");
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+        let format = wgpu::TextureFormat::Bgra8Unorm;
+        let adapter = super::open_adapter(&instance, None)?;
+        let (device, queue) = super::open_device(&adapter, format)?;
+
+        // Never handed to a surface. Only says what the scene draws onto.
+        let config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            format,
+            width,
+            height,
+            present_mode: wgpu::PresentMode::Fifo,
+            desired_maximum_frame_latency: 2,
+            alpha_mode: wgpu::CompositeAlphaMode::Auto,
+            view_formats: Vec::new(),
+        };
+        let target = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("offscreen"),
+            size: wgpu::Extent3d { width, height, depth_or_array_layers: 1 },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: config.format,
+            usage: config.usage,
+            view_formats: &[],
+        });
+        let target_view = target.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let camera = super::default_camera(width as f32 / height as f32);
+        let show = Show::new(scene.init(&config, &device, &queue), camera);
+
+        Ok(Offscreen {
+            device,
+            queue,
+            target,
+            target_view,
+            show,
+            screenshots: Vec::new(),
+        })
+    }
+
+    /// Change the scene before the next capture.
+    pub fn update<F, V>(&mut self, change: F) -> V
+    where F: FnOnce(&mut R, &wgpu::Device, &wgpu::Queue) -> V,
+    {
+        change(self.show.scene_mut(), &self.device, &self.queue)
+    }
+
+    /// Turn the scene by `rotation` on top of however far it has already been turned.
+    pub fn rotate(&mut self, rotation: Rot) {
+        self.show.update(Vector3::new(0.0, 0.0, 0.0), rotation);
+    }
+
+    /// Put the camera at `position` looking at `target`, such as to follow a `CameraPath`
+    /// frame by frame.
+    pub fn look(&mut self, position: Point3<f32>, target: Point3<f32>) {
+        self.show.look(position, target);
+    }
+
+    /// Render the scene as it is now to be written to `path`. The PNG is only written some
+    /// time later. Call `finish` to wait for all of them.
+    pub fn capture<P: Into<PathBuf>>(&mut self, path: P) {
+        self.show.present_frame(&self.target_view, &self.device, &self.queue);
+        self.screenshots.push(Screenshot::capture_texture(
+            path, &self.target, &self.device, &self.queue,
+        ));
+        self.screenshots.retain(|screenshot| !screenshot.is_done());
+    }
+
+    /// Wait until every capture has been written.
+    pub fn finish(self) -> crate::Result<()> {
+        // The captures are only read back when the video device is polled.
+        let mut attempts = 0;
+        while self.screenshots.iter().any(|screenshot| !screenshot.is_done()) {
+            if attempts == FLUSH_ATTEMPTS {
+                return Err(Error::CaptureTimeout);
+            }
+            attempts += 1;
+            self.device.poll(wgpu::Maintain::Poll);
+            thread::sleep(time::Duration::from_millis(10));
+        }
+
+        Ok(())
+    }
+}
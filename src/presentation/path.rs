@@ -0,0 +1,155 @@
+//! Fly the camera along a set path rather than steering it by hand.
+//!
+//! A path is a list of keyframes each giving where the camera is and what it's looking at by
+//! a time since the start. In between the camera glides from one keyframe to the next with
+//! the path's easing. Before the first keyframe it waits at the first and after the last it
+//! stays at the last, unless the path loops.
+use std::time::Duration;
+
+use cgmath::Point3;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Easing {
+    /// The same speed all the way.
+    Linear,
+    /// Start slow and speed up.
+    In,
+    /// Start fast and slow down.
+    Out,
+    /// Speed up then slow down to stop at each keyframe.
+    InOut,
+}
+
+impl Easing {
+    /// How far along from 0 to 1 for `t` of the way through the time, also 0 to 1.
+    pub fn ease(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::In => t * t,
+            Easing::Out => t * (2.0 - t),
+            Easing::InOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Keyframe {
+    at: Duration,
+    position: Point3<f32>,
+    target: Point3<f32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CameraPath {
+    keyframes: Vec<Keyframe>,
+    easing: Easing,
+    looped: bool,
+}
+
+impl CameraPath {
+    /// No keyframes yet. Eases in and out of each one without looping.
+    pub fn new() -> Self {
+        CameraPath {
+            keyframes: Vec::new(),
+            easing: Easing::InOut,
+            looped: false,
+        }
+    }
+
+    /// Be at `position` looking at `target` at time `at`. A keyframe at the same time as
+    /// another replaces it.
+    pub fn keyframe(
+        mut self, at: Duration, position: Point3<f32>, target: Point3<f32>,
+    ) -> Self {
+        self.keyframes.retain(|keyframe| keyframe.at != at);
+        let index = self.keyframes
+            .iter()
+            .position(|keyframe| keyframe.at > at)
+            .unwrap_or(self.keyframes.len());
+        self.keyframes.insert(index, Keyframe { at, position, target });
+        self
+    }
+
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Start over from the first keyframe after reaching the last.
+    pub fn looped(mut self) -> Self {
+        self.looped = true;
+        self
+    }
+
+    /// The time of the last keyframe.
+    pub fn duration(&self) -> Duration {
+        self.keyframes
+            .last()
+            .map(|keyframe| keyframe.at)
+            .unwrap_or_else(|| Duration::from_secs(0))
+    }
+
+    /// Where the camera is and what it's looking at `elapsed` since the start. `None` when
+    /// there are no keyframes.
+    pub fn view_at(&self, elapsed: Duration) -> Option<(Point3<f32>, Point3<f32>)> {
+        let first = self.keyframes.first()?;
+        let duration = self.duration();
+        let elapsed = if self.looped && duration > first.at && elapsed > duration {
+            let lap = (duration - first.at).as_nanos();
+            first.at + Duration::from_nanos(((elapsed - first.at).as_nanos() % lap) as u64)
+        } else {
+            elapsed
+        };
+
+        let next = match self.keyframes.iter().position(|keyframe| keyframe.at > elapsed) {
+            Some(0) => return Some((first.position, first.target)),
+            Some(next) => next,
+            None => {
+                let last = self.keyframes.last()?;
+                return Some((last.position, last.target));
+            },
+        };
+        let (from, to) = (&self.keyframes[next - 1], &self.keyframes[next]);
+        let t = (elapsed - from.at).as_secs_f32() / (to.at - from.at).as_secs_f32();
+        let t = self.easing.ease(t);
+
+        Some((
+            from.position + (to.position - from.position) * t,
+            from.target + (to.target - from.target) * t,
+        ))
+    }
+}
+
+impl Default for CameraPath {
+    fn default() -> Self {
+        CameraPath::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn glides_between_keyframes() {
+        let origin = Point3::new(0.0, 0.0, 0.0);
+        let path = CameraPath::new()
+            .keyframe(Duration::from_secs(2), Point3::new(0.0, 0.0, 10.0), origin)
+            .keyframe(Duration::from_secs(1), Point3::new(0.0, -10.0, 0.0), origin)
+            .easing(Easing::Linear);
+        let position = |ms| path.view_at(Duration::from_millis(ms)).unwrap().0;
+
+        assert!(path.duration() == Duration::from_secs(2));
+        assert!(position(0) == Point3::new(0.0, -10.0, 0.0));
+        assert!(position(1500) == Point3::new(0.0, -5.0, 5.0));
+        assert!(position(5000) == Point3::new(0.0, 0.0, 10.0));
+
+        let looped = path.clone().easing(Easing::InOut).looped();
+        let position = |ms| looped.view_at(Duration::from_millis(ms)).unwrap().0;
+        assert!(position(1250) == Point3::new(0.0, -8.4375, 1.5625));
+        assert!(position(3500) == Point3::new(0.0, -5.0, 5.0));
+
+        assert!(CameraPath::new().view_at(Duration::from_secs(1)).is_none());
+    }
+}
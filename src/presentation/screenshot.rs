@@ -0,0 +1,152 @@
+//! Save a presented frame to a PNG file.
+//!
+//! The frame is copied from the surface into a buffer which is then mapped for reading.
+//! The video device only gets around to mapping the buffer once the copy is done and it has
+//! been polled so the PNG is written some frames after the capture. The `Screenshot` has to
+//! be kept and checked on with `is_done` until then.
+use std::cell::Cell;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+
+use log::{error, info};
+
+pub struct Screenshot {
+    buffer: wgpu::Buffer,
+    path: PathBuf,
+    width: u32,
+    height: u32,
+    /// Bytes from the start of one row in the buffer to the next. Rows copied out of a
+    /// texture have to be spaced by a multiple of 256 bytes.
+    row_pitch: u32,
+    bgra: bool,
+    mapped: mpsc::Receiver<Result<(), wgpu::BufferAsyncError>>,
+    done: Cell<bool>,
+}
+
+impl Screenshot {
+    /// Copy out `frame` once it has been rendered but before it's presented. The surface
+    /// must have been configured with `COPY_SRC` usage and an eight bit RGBA or BGRA format.
+    pub fn capture<P: Into<PathBuf>>(
+        path: P, frame: &wgpu::SurfaceTexture, device: &wgpu::Device, queue: &wgpu::Queue,
+    ) -> Self {
+        Screenshot::capture_texture(path, &frame.texture, device, queue)
+    }
+
+    /// Same as `capture` but from any eight bit RGBA or BGRA texture created with
+    /// `COPY_SRC` usage.
+    pub fn capture_texture<P: Into<PathBuf>>(
+        path: P, texture: &wgpu::Texture, device: &wgpu::Device, queue: &wgpu::Queue,
+    ) -> Self {
+        let (width, height) = (texture.width(), texture.height());
+        let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let row_pitch = (width * 4).div_ceil(align) * align;
+        let bgra = matches!(
+            texture.format(),
+            wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb
+        );
+
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("screenshot"),
+            size: (row_pitch * height) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = device.create_command_encoder(
+            &wgpu::CommandEncoderDescriptor { label: Some("screenshot") }
+        );
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(row_pitch),
+                    rows_per_image: Some(height),
+                },
+            },
+            wgpu::Extent3d { width, height, depth_or_array_layers: 1 },
+        );
+        queue.submit(Some(encoder.finish()));
+
+        let (sender, mapped) = mpsc::channel();
+        buffer.slice(..).map_async(wgpu::MapMode::Read, move |result| {
+            // Nobody is waiting on it any more when the `Screenshot` was dropped.
+            let _ = sender.send(result);
+        });
+
+        Screenshot {
+            buffer,
+            path: path.into(),
+            width,
+            height,
+            row_pitch,
+            bgra,
+            mapped,
+            done: Cell::new(false),
+        }
+    }
+
+    /// Whether the PNG has been written (or failed to be) so the `Screenshot` can be dropped.
+    /// Writes it if the frame has been read back since last asked.
+    pub fn is_done(&self) -> bool {
+        if self.done.get() {
+            return true;
+        }
+
+        match self.mapped.try_recv() {
+            Ok(Ok(())) => self.save(),
+            Ok(Err(e)) => error!("Couldn't read back the frame: {}", e),
+            Err(mpsc::TryRecvError::Empty) => return false,
+            Err(mpsc::TryRecvError::Disconnected) => error!("Couldn't read back the frame."),
+        }
+        self.done.set(true);
+        true
+    }
+
+    fn save(&self) {
+        let mapped = self.buffer.slice(..).get_mapped_range();
+        let pixels: Vec<u8> = mapped
+            .chunks(self.row_pitch as usize)
+            .flat_map(|row| &row[..(self.width * 4) as usize])
+            .cloned()
+            .collect();
+        drop(mapped);
+        self.buffer.unmap();
+
+        match write_png(&self.path, self.width, self.height, &pixels, self.bgra) {
+            Ok(()) => info!("Saved screenshot to {}.", self.path.display()),
+            Err(e) => error!("Couldn't save screenshot {}: {}", self.path.display(), e),
+        }
+    }
+}
+
+/// The red and blue channels are swapped for the PNG when the frame is `bgra`.
+fn write_png(
+    path: &Path, width: u32, height: u32, pixels: &[u8], bgra: bool,
+) -> io::Result<()> {
+    let rgba: Vec<u8> = if bgra {
+        pixels
+            .chunks(4)
+            .flat_map(|pixel| vec![pixel[2], pixel[1], pixel[0], pixel[3]])
+            .collect()
+    } else {
+        pixels.to_owned()
+    };
+
+    let file = BufWriter::new(File::create(path)?);
+    let mut encoder = png::Encoder::new(file, width, height);
+    encoder.set_color(png::ColorType::RGBA);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&rgba))
+        .map_err(io::Error::other)
+}
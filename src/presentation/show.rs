@@ -1,8 +1,9 @@
 //! Show something renderable.
 
-use cgmath::{Matrix4, Vector3, Euler};
+use cgmath::{Matrix4, Point3, Vector3, Euler, Rad};
+use cgmath::prelude::*;
 
-use super::camera::{View, Camera};
+use super::camera::{View, Camera, Preset};
 use super::{Rot, Presentation, Renderable};
 
 /// Compose the camera, scene rotation and scene.
@@ -20,6 +21,14 @@ impl<T: Renderable> Show<T> {
             scene,
         }
     }
+
+    pub fn scene_mut(&mut self) -> &mut T {
+        &mut self.scene
+    }
+
+    fn rotation(&self) -> Matrix4<f32> {
+        Matrix4::from(Euler::new(self.rotation.x, self.rotation.y, self.rotation.z))
+    }
 }
 
 impl<T: Renderable> Presentation for Show<T> {
@@ -30,13 +39,48 @@ impl<T: Renderable> Presentation for Show<T> {
 
         (self.camera.move_camera(movement), &self.rotation)
     }
+
+    fn orbit(&mut self, yaw: Rad<f32>, pitch: Rad<f32>) -> &View<f32> {
+        self.camera.orbit(yaw, pitch)
+    }
+
+    fn look(&mut self, position: Point3<f32>, target: Point3<f32>) -> &View<f32> {
+        self.camera.look(position, target)
+    }
+
+    fn preset(&mut self, preset: Preset) -> &View<f32> {
+        self.camera.preset(preset)
+    }
+
+    fn resize(&mut self, config: &wgpu::SurfaceConfiguration, device: &wgpu::Device) {
+        self.camera.aspect_ratio(config.width as f32 / config.height as f32);
+        self.scene.resize(config, device);
+    }
     
-    fn present_frame(&mut self, frame: &wgpu::SwapChainOutput, device: &mut wgpu::Device) {
-        self.scene.render(
-            &self.camera.projection(),
-            &Matrix4::from(Euler::new(self.rotation.x, self.rotation.y, self.rotation.z)),
-            frame,
-            device,
-        );
+    fn present_frame(
+        &mut self, target: &wgpu::TextureView, device: &wgpu::Device, queue: &wgpu::Queue,
+    ) {
+        let eye = self.camera.position();
+        let (projection, rotation) = (self.camera.projection(), self.rotation());
+        self.scene.render(&projection, &rotation, &eye, target, device, queue);
+    }
+
+    fn reload_shaders(&mut self, device: &wgpu::Device) {
+        self.scene.reload_shaders(device);
+    }
+
+    fn pick(&mut self, x: f32, y: f32, queue: &wgpu::Queue) -> Option<usize> {
+        // A rotation's inverse is its transpose. Takes the ray back to the unrotated scene.
+        let unrotate = self.rotation().transpose();
+        let face = self.camera
+            .ray(x, y)
+            .and_then(|(origin, direction)| {
+                self.scene.pick(
+                    unrotate.transform_point(origin), unrotate.transform_vector(direction),
+                )
+            });
+
+        self.scene.highlight(face, queue);
+        face
     }
 }
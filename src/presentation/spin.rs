@@ -0,0 +1,107 @@
+//! Slowly turn the scene round while nobody is touching it.
+//!
+//! The turn is added onto the scene rotation the same way the arrow keys do it, so an axis
+//! off the x, y or z axes gets a tumble rather than a clean turn about that axis.
+use std::time::Duration;
+
+use cgmath::{Rad, Vector3};
+use cgmath::prelude::*;
+
+use super::Rot;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Spin {
+    axis: Vector3<f32>,
+    speed: Rad<f32>,
+    idle: Duration,
+    enabled: bool,
+}
+
+impl Spin {
+    /// Turn about `axis` at `speed` per second. Switched on.
+    pub fn new(axis: Vector3<f32>, speed: Rad<f32>) -> Self {
+        Spin::default().axis(axis).speed(speed)
+    }
+
+    /// Left as is when `axis` has no length.
+    pub fn axis(mut self, axis: Vector3<f32>) -> Self {
+        if axis.magnitude2() > 0.0 {
+            self.axis = axis.normalize();
+        }
+        self
+    }
+
+    /// How far to turn per second. Negative turns the other way.
+    pub fn speed(mut self, speed: Rad<f32>) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// How long after the last input before spinning again. Defaults to two seconds.
+    pub fn idle(mut self, idle: Duration) -> Self {
+        self.idle = idle;
+        self
+    }
+
+    /// Start switched off, waiting on the toggle key.
+    pub fn disabled(mut self) -> Self {
+        self.enabled = false;
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+    }
+
+    /// How much to turn for a frame taking `frame` when the last input was `since_input`
+    /// ago, `None` if there's been none. Nothing while switched off or before the scene has
+    /// been left alone long enough.
+    pub fn increment(&self, frame: Duration, since_input: Option<Duration>) -> Rot {
+        let busy = since_input.map(|since| since < self.idle).unwrap_or(false);
+        if !self.enabled || busy {
+            return Rot::default();
+        }
+
+        let seconds = frame.as_secs() as f32 + frame.subsec_nanos() as f32 * 1e-9;
+        let turn = self.axis * (self.speed.0 * seconds);
+        Rot::new(Rad(turn.x), Rad(turn.y), Rad(turn.z))
+    }
+}
+
+/// A turn every half minute about the z axis, which is up in the default camera.
+impl Default for Spin {
+    fn default() -> Self {
+        Spin {
+            axis: Vector3::unit_z(),
+            speed: Rad(std::f32::consts::PI / 15.0),
+            idle: Duration::from_secs(2),
+            enabled: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn waits_until_idle() {
+        let spin = Spin::new(Vector3::new(0.0, 0.0, 2.0), Rad(1.0));
+        let frame = Duration::from_millis(500);
+
+        let busy = spin.increment(frame, Some(Duration::from_secs(1)));
+        assert!(busy.z == Rad(0.0));
+
+        let idle = spin.increment(frame, Some(Duration::from_secs(3)));
+        assert!(idle.x == Rad(0.0) && idle.y == Rad(0.0));
+        assert!((idle.z.0 - 0.5).abs() < 1e-6);
+
+        let mut off = spin;
+        off.toggle();
+        assert!(off.increment(frame, None).z == Rad(0.0));
+    }
+}
@@ -0,0 +1,65 @@
+//! Render the scene turning round once to a numbered sequence of PNGs without opening a
+//! window. Handy for making animations of the shapes for documentation.
+use std::fs;
+use std::f32::consts::PI;
+use std::path::PathBuf;
+
+use cgmath::Rad;
+use log::info;
+
+use super::{Initializable, Offscreen, Renderable, Rot};
+
+#[derive(Debug, Clone)]
+pub struct Turntable {
+    directory: PathBuf,
+    frames: u32,
+    width: u32,
+    height: u32,
+}
+impl Turntable {
+    /// Sixty frames of 512 by 512 written into `directory`.
+    pub fn new<P: Into<PathBuf>>(directory: P) -> Self {
+        Turntable {
+            directory: directory.into(),
+            frames: 60,
+            width: 512,
+            height: 512,
+        }
+    }
+
+    /// How many frames to split the full turn into. At least one.
+    pub fn frames(mut self, frames: u32) -> Self {
+        self.frames = frames.max(1);
+        self
+    }
+
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.width = width.max(1);
+        self.height = height.max(1);
+        self
+    }
+
+    /// Frame `n` goes to `frame-nnnn.png` in the directory, which is made if need be.
+    /// Returns the paths of all the frames in order.
+    pub fn render<T>(&self, scene: T) -> crate::Result<Vec<PathBuf>>
+    where T: Initializable,
+          T::Ready: Renderable,
+    {
+        fs::create_dir_all(&self.directory)?;
+        let mut offscreen = Offscreen::new(scene, self.width, self.height)?;
+
+        info!("Rendering {} frames into {}.", self.frames, self.directory.display());
+        let step = Rot::new(Rad(0.0), Rad(0.0), Rad(2.0 * PI / self.frames as f32));
+        let mut paths: Vec<PathBuf> = Vec::with_capacity(self.frames as usize);
+        for frame in 0..self.frames {
+            let path = self.directory.join(format!("frame-{:04}.png", frame));
+            offscreen.capture(path.clone());
+            paths.push(path);
+
+            offscreen.rotate(step);
+        }
+        offscreen.finish()?;
+
+        Ok(paths)
+    }
+}
@@ -1,39 +1,473 @@
 //! Prepare a `Polyhedron` for presentation.
 
-use crate::polyhedron::{Polyhedron, VtFc, VtFcNm};
+use std::f64::consts::PI;
+use std::ops::Range;
+
+use cgmath::{EuclideanSpace, InnerSpace, MetricSpace, Point3, Vector3};
+
+use crate::geop::Spherical;
+use crate::polyhedron::{Polyhedron, VtFc, VtFcNm32, VertexAndFaceOps};
 use crate::planar;
 use crate::scene;
 
+/// How the vertices are handed over for drawing.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Shading {
+    /// Every face gets its own copy of its vertices with the face normal so each face is
+    /// lit evenly and the edges are crisp.
+    Flat,
+
+    /// The faces meeting at a vertex share it with their normals averaged. Takes a third to
+    /// a sixth of the memory of `Flat` but the lighting blurs across the edges. Faces can't
+    /// be picked since they don't have vertices of their own.
+    Smooth,
+}
+
+/// How the vertices get their texture coordinates.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Mapping {
+    /// Each face gets the whole texture to itself.
+    Face,
+
+    /// One texture wraps the whole polyhedron like a globe. Takes equirectangular images
+    /// such as the usual maps of the Earth with north up the z axis. Faces crossing the
+    /// edge of the image are only kept whole with `Shading::Flat`.
+    Equirectangular,
+}
+
+/// Bands drawn along the inside of each face edge over the top of the faces.
+#[derive(Debug, Copy, Clone)]
+struct EdgeOverlay {
+    colour: [f32; 3],
+    width: f32,
+}
+
+/// How far the edge bands are lifted off the faces as a fraction of the polyhedron radius.
+/// Keeps them in front of the faces they lie on.
+const EDGE_BIAS: f32 = 1e-3;
+
 #[derive(Debug, Clone)]
 pub struct SingleColour {
     colour: [f32; 3],
-    polyhedron: Polyhedron<VtFcNm>,
+    shading: Shading,
+    mapping: Mapping,
+    edges: Option<EdgeOverlay>,
+    explode: f32,
+    polyhedron: Polyhedron<VtFcNm32>,
 }
 
 impl SingleColour {
     pub fn new(colour: [f32; 3], polyhedron: Polyhedron<VtFc>) -> Self {
         SingleColour {
             colour,
-            polyhedron: polyhedron.normalize(),
+            shading: Shading::Flat,
+            mapping: Mapping::Face,
+            edges: None,
+            explode: 0.0,
+            polyhedron: polyhedron
+                .normalize()
+                .cast()
+                .expect("Polyhedron doesn't fit in f32"),
         }
     }
 
+    /// Defaults to `Shading::Flat`.
+    pub fn shading(mut self, shading: Shading) -> Self {
+        self.shading = shading;
+        self
+    }
+
+    /// Defaults to `Mapping::Face`.
+    pub fn mapping(mut self, mapping: Mapping) -> Self {
+        self.mapping = mapping;
+        self
+    }
+
+    /// Outline every face edge in `colour`. The outline is `width` wide and lies just in
+    /// front of the faces. It's drawn as flat bands of triangles so it isn't anti-aliased.
+    pub fn edges(mut self, colour: [f32; 3], width: f32) -> Self {
+        self.edges = Some(EdgeOverlay { colour, width });
+        self
+    }
+
+    /// Push each face out along its normal by `factor` times the radius of the polyhedron
+    /// leaving gaps between the faces. Only works with `Shading::Flat` since the faces
+    /// need vertices of their own to come apart.
+    pub fn explode(mut self, factor: f32) -> Self {
+        self.explode = factor;
+        self
+    }
+
     pub fn to_cached(&self) -> scene::Cached {
-        let faces: Vec<planar::Polygon<f64>> = self.polyhedron
+        let (mut vertices, mut index, faces) = match self.shading {
+            Shading::Flat => flat(&self.polyhedron, |_| self.colour),
+            Shading::Smooth => {
+                let (vertices, index) = self.smooth();
+                (vertices, index, Vec::new())
+            },
+        };
+
+        if self.mapping == Mapping::Equirectangular {
+            match self.shading {
+                Shading::Flat => faces
+                    .iter()
+                    .for_each(|range| equirectangular(&mut vertices[range.clone()])),
+                Shading::Smooth => vertices
+                    .chunks_mut(1)
+                    .for_each(equirectangular),
+            }
+        }
+
+        // After the mapping so the texture coordinates are from where the faces started.
+        if self.shading == Shading::Flat {
+            explode(&mut vertices, &faces, self.explode * radius(&self.polyhedron));
+        }
+
+        if let Some(overlay) = self.edges {
+            let (v, i) = self.edge_overlay(overlay, vertices.len() as u32);
+            vertices.extend(v);
+            index.extend(i);
+        }
+
+        let index = scene::Index::fit(index, vertices.len());
+        scene::Cached::new(&vertices, index).with_faces(faces)
+    }
+
+    /// Each vertex normal is the average of the normals of the faces it's part of.
+    fn smooth(&self) -> (Vec<scene::Vertex>, Vec<u32>) {
+        let (points, faces) = self.polyhedron.vertices_and_faces();
+        let face_normals: Vec<Vector3<f32>> = self.polyhedron
             .faces()
+            .map(|polygon| polygon.normal())
             .collect();
+        let mut index: Vec<u32> = Vec::new();
 
+        for face in faces.iter() {
+            for i in 1..(face.len() - 1) {
+                index.extend_from_slice(&[face[0] as u32, face[i] as u32, face[i + 1] as u32]);
+            }
+        }
+
+        let vertices: Vec<scene::Vertex> = self.polyhedron
+            .incidence()
+            .iter()
+            .map(|(v_index, incident)| {
+                let normal: Vector3<f32> = incident
+                    .iter()
+                    .map(|f_index| face_normals[*f_index])
+                    .sum();
+                let position = points[v_index].into();
+                scene::Vertex::new(position, normal.normalize().into(), self.colour)
+            })
+            .collect();
+
+        (vertices, index)
+    }
+
+    /// A quad along each edge of each face reaching `width` in towards the face centroid.
+    /// The inner corners are placed along the corner bisectors so the bands meet neatly.
+    fn edge_overlay(
+        &self, overlay: EdgeOverlay, offset: u32,
+    ) -> (Vec<scene::Vertex>, Vec<u32>) {
+        let (points, faces) = self.polyhedron.vertices_and_faces();
+        let radius = radius(&self.polyhedron);
         let mut vertices: Vec<scene::Vertex> = Vec::new();
-        let mut index: Vec<u16> = Vec::new();
-        let mut offset = 0;
+        let mut index: Vec<u32> = Vec::new();
 
-        for face in faces {
-            let (v, i) = face.as_scene_consumable(self.colour, offset);
-            offset += v.len();
-            vertices.extend(v);
-            index.extend(i);
+        for (face, polygon) in faces.iter().zip(self.polyhedron.faces()) {
+            let normal = polygon.normal();
+            let lift = normal * (radius * (EDGE_BIAS + self.explode));
+            let outer: Vec<Point3<f32>> = face.iter().map(|v| points[*v] + lift).collect();
+            let centroid = Point3::centroid(&outer);
+            let len = outer.len();
+
+            let inner: Vec<Point3<f32>> = (0..len)
+                .map(|i| {
+                    let corner = outer[i];
+                    let before = (outer[(i + len - 1) % len] - corner).normalize();
+                    let after = (outer[(i + 1) % len] - corner).normalize();
+                    let half_angle = before.angle(after).0 / 2.0;
+                    let reach = (overlay.width / half_angle.sin())
+                        .min(corner.distance(centroid) / 2.0);
+
+                    corner + (before + after).normalize() * reach
+                })
+                .collect();
+
+            for i in 0..len {
+                let j = (i + 1) % len;
+                let start = offset + vertices.len() as u32;
+                let corners = [outer[i], outer[j], inner[j], inner[i]];
+                vertices.extend(corners.iter().map(|p| {
+                    scene::Vertex::new((*p).into(), normal.into(), overlay.colour)
+                }));
+                index.extend([0, 1, 2, 0, 2, 3].iter().map(|i| start + i));
+            }
         }
 
-        scene::Cached::new(&vertices, &index)
+        (vertices, index)
+    }
+}
+
+/// Each face gets its own copy of its vertices in the colour `colour` gives it. Also returns
+/// which vertices belong to which face.
+fn flat<F: Fn(&planar::Polygon<f32>) -> [f32; 3]>(
+    polyhedron: &Polyhedron<VtFcNm32>, colour: F,
+) -> (Vec<scene::Vertex>, Vec<u32>, Vec<Range<usize>>) {
+    let mut vertices: Vec<scene::Vertex> = Vec::new();
+    let mut index: Vec<u32> = Vec::new();
+    let mut faces: Vec<Range<usize>> = Vec::new();
+    let mut offset = 0;
+
+    for face in polyhedron.faces() {
+        let (v, i) = face.as_scene_consumable(colour(&face), offset);
+        faces.push(offset..(offset + v.len()));
+        offset += v.len();
+        vertices.extend(v);
+        index.extend(i);
+    }
+
+    (vertices, index, faces)
+}
+
+/// Distance of the furthest vertex from the centre.
+fn radius(polyhedron: &Polyhedron<VtFcNm32>) -> f32 {
+    let (points, _) = polyhedron.vertices_and_faces();
+    points.iter().map(|p| p.to_vec().magnitude()).fold(0.0, f32::max)
+}
+
+/// Move the vertices of each face `distance` along their normal. The vertices of a face
+/// all have the face normal when the faces are flat shaded.
+fn explode(vertices: &mut [scene::Vertex], faces: &[Range<usize>], distance: f32) {
+    if distance == 0.0 {
+        return;
+    }
+
+    for range in faces {
+        for vertex in vertices[range.clone()].iter_mut() {
+            let normal = Vector3::from(*vertex.normal());
+            let position = Point3::from(*vertex.position()) + normal * distance;
+            *vertex = scene::Vertex::textured(
+                position.into(), *vertex.normal(), *vertex.colour(), *vertex.texcoord(),
+            );
+        }
+    }
+}
+
+/// Texture coordinates for the `vertices` of a face from their longitude and latitude. A
+/// face crossing the edge of the image where the longitude wraps around goes on past the
+/// right edge so it isn't stretched back across the whole image. A vertex on a pole takes the
+/// longitude of the rest of the face.
+fn equirectangular(vertices: &mut [scene::Vertex]) {
+    let spherical: Vec<Spherical> = vertices
+        .iter()
+        .map(|v| Spherical::from_cartesian(&Point3::from(*v.position()).cast().unwrap()))
+        .collect();
+    let on_pole = |s: &Spherical| s.latitude().0.cos() < 1e-6;
+
+    let mut us: Vec<f64> = spherical
+        .iter()
+        .map(|s| (s.longitude().0 + PI) / (2.0 * PI))
+        .collect();
+    let (min, max) = us
+        .iter()
+        .zip(spherical.iter())
+        .filter(|(_, s)| !on_pole(s))
+        .fold((1.0, 0.0), |(min, max), (u, _)| (u.min(min), u.max(max)));
+    if max - min > 0.5 {
+        us.iter_mut().filter(|u| **u < 0.5).for_each(|u| *u += 1.0);
+    }
+
+    let rest: Vec<f64> = us
+        .iter()
+        .zip(spherical.iter())
+        .filter(|(_, s)| !on_pole(s))
+        .map(|(u, _)| *u)
+        .collect();
+    let rest = match rest.len() {
+        0 => 0.5,
+        len => rest.iter().sum::<f64>() / len as f64,
+    };
+
+    for ((vertex, s), u) in vertices.iter_mut().zip(spherical.iter()).zip(us) {
+        let u = if on_pole(s) { rest } else { u };
+        let v = (PI / 2.0 - s.latitude().0) / PI;
+        *vertex = scene::Vertex::textured(
+            *vertex.position(), *vertex.normal(), *vertex.colour(), [u as f32, v as f32],
+        );
+    }
+}
+
+/// Triangles, squares, pentagons, hexagons, heptagons and octagons. Pentagons stand out
+/// from the hexagons around them since they mark the corners of Goldberg polyhedra.
+const DEGREE_PALETTE: [[f32; 3]; 6] = [
+    [0.20, 0.60, 0.85],
+    [0.55, 0.75, 0.30],
+    [0.90, 0.30, 0.20],
+    [0.85, 0.85, 0.80],
+    [0.60, 0.35, 0.75],
+    [0.95, 0.70, 0.20],
+];
+
+/// Colours each face by how many sides it has. Faces with more sides than the palette
+/// covers go back around to the start of the palette.
+#[derive(Debug, Clone)]
+pub struct DegreeColour {
+    palette: Vec<[f32; 3]>,
+    explode: f32,
+    polyhedron: Polyhedron<VtFcNm32>,
+}
+
+impl DegreeColour {
+    pub fn new(polyhedron: Polyhedron<VtFc>) -> Self {
+        DegreeColour {
+            palette: DEGREE_PALETTE.to_vec(),
+            explode: 0.0,
+            polyhedron: polyhedron
+                .normalize()
+                .cast()
+                .expect("Polyhedron doesn't fit in f32"),
+        }
+    }
+
+    /// Replace the palette. The first colour is for triangles, the next for squares and so
+    /// on. An empty palette is ignored.
+    pub fn palette(mut self, palette: &[[f32; 3]]) -> Self {
+        if !palette.is_empty() {
+            self.palette = palette.to_vec();
+        }
+        self
+    }
+
+    pub fn colour(&self, sides: usize) -> [f32; 3] {
+        self.palette[sides.saturating_sub(3) % self.palette.len()]
+    }
+
+    /// Same as `SingleColour::explode`.
+    pub fn explode(mut self, factor: f32) -> Self {
+        self.explode = factor;
+        self
+    }
+
+    pub fn to_cached(&self) -> scene::Cached {
+        let (mut vertices, index, faces) = flat(&self.polyhedron, |face| {
+            self.colour(face.sides())
+        });
+        explode(&mut vertices, &faces, self.explode * radius(&self.polyhedron));
+        let index = scene::Index::fit(index, vertices.len());
+        scene::Cached::new(&vertices, index).with_faces(faces)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::polyhedron::Specification;
+    use crate::scene::Geometry;
+
+    #[test]
+    fn pentagons_stand_out() {
+        let presenter = DegreeColour::new(crate::polyhedron::goldberg(2, 0, 1.0).unwrap());
+        let (vertices, _) = presenter.to_cached().geometry();
+        let pentagon = presenter.colour(5);
+
+        let pentagon_vertices = vertices.iter().filter(|v| v.colour() == &pentagon).count();
+        assert!(pentagon_vertices == 12 * 5);
+        assert!(presenter.colour(6) != pentagon);
+        assert!(presenter.colour(3 + DEGREE_PALETTE.len()) == presenter.colour(3));
+    }
+
+    #[test]
+    fn equirectangular_seam() {
+        let p = Specification::parse("tI").unwrap().produce();
+        let cached = SingleColour::new([1.0; 3], p)
+            .mapping(Mapping::Equirectangular)
+            .to_cached();
+        let (vertices, _) = cached.geometry();
+
+        for range in cached.face_vertices() {
+            // A face around a pole spans every longitude.
+            let face = &vertices[range];
+            let centroid: Vector3<f32> = face
+                .iter()
+                .map(|v| Vector3::from(*v.position()))
+                .sum();
+            if centroid.normalize().z.abs() > 0.9 {
+                continue;
+            }
+
+            let us: Vec<f32> = face.iter().map(|v| v.texcoord()[0]).collect();
+            let min = us.iter().cloned().fold(2.0, f32::min);
+            let max = us.iter().cloned().fold(-1.0, f32::max);
+            assert!(max - min < 0.5);
+            assert!(min >= 0.0 && max <= 1.5);
+        }
+        assert!(vertices.iter().any(|v| v.texcoord()[0] > 1.0));
+        assert!(vertices.iter().all(|v| v.texcoord()[1] >= 0.0 && v.texcoord()[1] <= 1.0));
+    }
+
+    #[test]
+    fn edge_bands() {
+        let p = Specification::parse("C").unwrap().produce();
+        let (vertices, index) = SingleColour::new([1.0; 3], p)
+            .edges([0.0; 3], 0.05)
+            .to_cached()
+            .geometry();
+
+        // Faces then four corners per band on each of the four edges of the six faces.
+        assert!(vertices.len() == 24 + 6 * 4 * 4);
+        assert!(index.len() == 36 + 6 * 4 * 6);
+        assert!(vertices[24..].iter().all(|v| v.colour() == &[0.0; 3]));
+    }
+
+    #[test]
+    fn face_vertices() {
+        let p = Specification::parse("tT").unwrap().produce();
+        let cached = DegreeColour::new(p).to_cached();
+        let faces = cached.face_vertices();
+
+        assert!(faces.len() == 8);
+        assert!(faces.iter().map(|range| range.len()).sum::<usize>() == 4 * 3 + 4 * 6);
+        assert!(faces.windows(2).all(|pair| pair[0].end == pair[1].start));
+    }
+
+    #[test]
+    fn exploded_faces_move_out() {
+        let p = Specification::parse("C").unwrap().produce();
+        let (whole, _) = DegreeColour::new(p.clone()).to_cached().geometry();
+        let (exploded, _) = DegreeColour::new(p).explode(0.5).to_cached().geometry();
+        let radius = whole
+            .iter()
+            .map(|v| Vector3::from(*v.position()).magnitude())
+            .fold(0.0, f32::max);
+
+        assert!(whole.len() == exploded.len());
+        for (before, after) in whole.iter().zip(exploded.iter()) {
+            let moved = Vector3::from(*after.position()) - Vector3::from(*before.position());
+            let expected = Vector3::from(*before.normal()) * 0.5 * radius;
+            assert!((moved - expected).magnitude() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn smooth_shares_vertices() {
+        let p = Specification::parse("tI").unwrap().produce();
+        let (flat_vertices, flat_index) = SingleColour::new([1.0; 3], p.clone())
+            .to_cached()
+            .geometry();
+        let (vertices, index) = SingleColour::new([1.0; 3], p)
+            .shading(Shading::Smooth)
+            .to_cached()
+            .geometry();
+
+        assert!(flat_vertices.len() == 12 * 5 + 20 * 6);
+        assert!(vertices.len() == 60);
+        assert!(index.len() == flat_index.len());
+
+        // Averaged normals on a sphere point straight out.
+        for v in vertices.iter() {
+            let (p, n) = (Vector3::from(*v.position()), Vector3::from(*v.normal()));
+            assert!((p.normalize().dot(n) - 1.0).abs() < 1e-3);
+        }
     }
 }
@@ -1,28 +1,52 @@
 //! Typestate that holds render pipelines, perspectives and assets.
-use std::mem;
+use std::{error, fmt, mem};
+use std::ops::Range;
 
+use bytemuck::{Pod, Zeroable};
 use derive_getters::Getters;
-use num_traits::identities::Zero;
-use cgmath::Matrix4;
+#[cfg(feature = "shaderc")]
+use log::info;
+use log::warn;
+use cgmath::{Matrix4, Point3, Vector3, SquareMatrix, Transform};
+use wgpu::util::DeviceExt;
 
+use crate::geop;
 use crate::shader::CompiledShaders;
+#[cfg(feature = "shaderc")]
+use crate::shader::Watch;
 use crate::presentation::{Initializable, Renderable};
 use crate::light::{Light, LightRaw};
+use crate::material::Material;
 
 const MAX_LIGHTS: usize = 10;
 
+/// Light reaching every face even when no light shines on it.
+const AMBIENT: wgpu::Color = wgpu::Color { r: 0.05, g: 0.05, b: 0.05, a: 1.0 };
+
+/// Colour of the picked face.
+const HIGHLIGHT: [f32; 3] = [1.0, 0.85, 0.1];
+
 /// Final vertex data ready for consumption by the video device. A vector of these will be
 /// the last step in getting some arbitrary geometry loaded in video memory for rendering.
-#[derive(Debug, Copy, Clone, Getters)]
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Getters, Pod, Zeroable)]
 pub struct Vertex {
     position: [f32; 3],
     normal: [f32; 3],
     colour: [f32; 3], // Consider removing this in the upcoming refactor ?? really??
+    texcoord: [f32; 2],
 }
 
 impl Vertex {
+    /// Untextured. Takes the colour of the top left of the texture if there is one.
     pub fn new(position: [f32; 3], normal: [f32; 3], colour: [f32; 3]) -> Self {
-        Vertex { position, normal, colour }
+        Vertex { position, normal, colour, texcoord: [0.0; 2] }
+    }
+
+    pub fn textured(
+        position: [f32; 3], normal: [f32; 3], colour: [f32; 3], texcoord: [f32; 2],
+    ) -> Self {
+        Vertex { position, normal, colour, texcoord }
     }
 
     pub const fn sizeof() -> usize {
@@ -30,32 +54,162 @@ impl Vertex {
     }
 }
 
+/// Indexes into the vertices tracing out the triangles. `U16` takes half the memory of
+/// `U32` but can only reach the first 65536 vertices.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Index {
+    U16(Vec<u16>),
+    U32(Vec<u32>),
+}
+
+impl Index {
+    /// Use the smallest index type that can reach all `vertex_count` vertices.
+    pub fn fit(index: Vec<u32>, vertex_count: usize) -> Self {
+        if vertex_count <= (u16::MAX as usize) + 1 {
+            Index::U16(index.into_iter().map(|i| i as u16).collect())
+        } else {
+            Index::U32(index)
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Index::U16(index) => index.len(),
+            Index::U32(index) => index.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn to_u32(&self) -> Vec<u32> {
+        match self {
+            Index::U16(index) => index.iter().map(|i| *i as u32).collect(),
+            Index::U32(index) => index.clone(),
+        }
+    }
+
+    pub fn format(&self) -> wgpu::IndexFormat {
+        match self {
+            Index::U16(_) => wgpu::IndexFormat::Uint16,
+            Index::U32(_) => wgpu::IndexFormat::Uint32,
+        }
+    }
+}
+
+/// One copy of the geometry placed by `model` and with its colours multiplied by `tint`.
+#[derive(Debug, Copy, Clone, PartialEq, Getters)]
+pub struct Instance {
+    model: Matrix4<f32>,
+    tint: [f32; 3],
+}
+
+impl Instance {
+    pub fn new(model: Matrix4<f32>, tint: [f32; 3]) -> Self {
+        Instance { model, tint }
+    }
+
+    fn to_raw(self) -> InstanceRaw {
+        InstanceRaw {
+            model: *self.model.as_ref(),
+            tint: self.tint,
+        }
+    }
+}
+
+/// The geometry as it is.
+impl Default for Instance {
+    fn default() -> Self {
+        Instance::new(Matrix4::identity(), [1.0; 3])
+    }
+}
+
+/// Used only for final transfer to the video device.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Pod, Zeroable)]
+#[allow(dead_code)] // Only read by the video device.
+struct InstanceRaw {
+    model: [[f32; 4]; 4],
+    tint: [f32; 3],
+}
+
+impl InstanceRaw {
+    const fn sizeof() -> usize {
+        mem::size_of::<InstanceRaw>()
+    }
+}
+
+/// An image to wrap onto the geometry by the texture coordinates of the vertices. The
+/// texture colours multiply the vertex colours.
+#[derive(Debug, Clone, Getters)]
+pub struct Texture {
+    width: u32,
+    height: u32,
+    /// Four bytes per pixel, red, green, blue and alpha, a row at a time from the top.
+    rgba: Vec<u8>,
+}
+
+impl Texture {
+    /// `None` if there aren't exactly four bytes for each pixel.
+    pub fn new(width: u32, height: u32, rgba: Vec<u8>) -> Option<Self> {
+        if width == 0 || height == 0 || rgba.len() != (width * height * 4) as usize {
+            return None;
+        }
+
+        Some(Texture { width, height, rgba })
+    }
+
+    /// One white pixel that leaves the vertex colours as they are.
+    pub fn white() -> Self {
+        Texture { width: 1, height: 1, rgba: vec![255; 4] }
+    }
+}
+
 /// Vertex data (triangles) and indexes and colours for slurping into video memory.
 ///
 /// TODO: Need to sort the geometry faces from back to front relative to the viewpoint.
 pub trait Geometry {
-    fn geometry(&self) -> (Vec<Vertex>, Vec<u16>);
+    fn geometry(&self) -> (Vec<Vertex>, Index);
+
+    /// The vertices belonging to each face. Faces can only be picked and highlighted when
+    /// each has vertices of its own. Vertices after the last face belong to no face.
+    fn face_vertices(&self) -> Vec<Range<usize>> {
+        Vec::new()
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct Cached {
     vertices: Vec<Vertex>,
-    index: Vec<u16>,
+    index: Index,
+    faces: Vec<Range<usize>>,
 }
 
 impl Cached {
-    pub fn new(vertices: &[Vertex], index: &[u16]) -> Self {
+    pub fn new(vertices: &[Vertex], index: Index) -> Self {
         Cached {
             vertices: vertices.to_owned(),
-            index: index.to_owned(),
+            index,
+            faces: Vec::new(),
         }
     }
+
+    /// Set which vertices belong to which face so the faces can be picked.
+    pub fn with_faces(mut self, faces: Vec<Range<usize>>) -> Self {
+        self.faces = faces;
+        self
+    }
 }
 
 impl Geometry for Cached {
-    fn geometry(&self) -> (Vec<Vertex>, Vec<u16>) {
+    fn geometry(&self) -> (Vec<Vertex>, Index) {
         (self.vertices.to_owned(), self.index.to_owned())
     }
+
+    fn face_vertices(&self) -> Vec<Range<usize>> {
+        self.faces.to_owned()
+    }
 }
 
 /// Begin construction of a new `Scene`.
@@ -64,26 +218,110 @@ pub struct Begin;
 pub struct Lights {
     frag: Vec<u8>,
     vert: Vec<u8>,
+    #[cfg(feature = "shaderc")]
+    watch: Option<Watch>,
     lights: Vec<Light>,
+    ambient: wgpu::Color,
+    texture: Option<Texture>,
+    instances: Vec<Instance>,
+    sample_count: u32,
 }
 
 pub struct Prepare<T: Geometry> {
     frag: Vec<u8>,
     vert: Vec<u8>,
+    #[cfg(feature = "shaderc")]
+    watch: Option<Watch>,
     lights: Vec<Light>,
+    ambient: wgpu::Color,
+    texture: Option<Texture>,
+    instances: Vec<Instance>,
+    sample_count: u32,
     geometry: T,
+    material: Material,
+    model: Matrix4<f32>,
+    objects: Vec<Object>,
 }
 
-pub struct Ready {
-    //light_buf: wgpu::Buffer,
-    //light_count_buf: wgpu::Buffer,
-    projection_buf: wgpu::Buffer,
-    rotation_buf: wgpu::Buffer,
+/// Another geometry to draw alongside the first.
+struct Object {
+    geometry: Cached,
+    model: Matrix4<f32>,
+    material: Material,
+}
+
+/// One geometry loaded onto the video device. Each has its own bind group for its material.
+struct Drawn {
     vertex_buf: wgpu::Buffer,
     index_buf: wgpu::Buffer,
     index_len: usize,
+    index_format: wgpu::IndexFormat,
+    instance_buf: wgpu::Buffer,
+    instances: Vec<Matrix4<f32>>,
     bind_group: wgpu::BindGroup,
+
+    // Kept for picking and for rewriting the vertex colours to highlight a face.
+    vertices: Vec<Vertex>,
+    index: Vec<u32>,
+    vertex_faces: Vec<Option<usize>>,
+    faces: Range<usize>,
+}
+
+impl Drawn {
+    /// Vertex and index buffers holding `vertices` and `index`.
+    fn buffers(
+        device: &wgpu::Device, vertices: &[Vertex], index: &Index,
+    ) -> (wgpu::Buffer, wgpu::Buffer) {
+        let vertex_buf = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("vertices"),
+            contents: bytemuck::cast_slice(vertices),
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let contents = match index {
+            Index::U16(index) => bytemuck::cast_slice(index),
+            Index::U32(index) => bytemuck::cast_slice(index),
+        };
+        let index_buf = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("index"),
+            contents,
+            usage: wgpu::BufferUsages::INDEX,
+        });
+
+        (vertex_buf, index_buf)
+    }
+
+    /// The face of each vertex counting from the first face of this geometry.
+    fn vertex_faces(
+        vertex_count: usize, face_vertices: &[Range<usize>],
+    ) -> Vec<Option<usize>> {
+        let mut vertex_faces: Vec<Option<usize>> = vec![None; vertex_count];
+        for (face, range) in face_vertices.iter().enumerate() {
+            for v_index in range.clone() {
+                vertex_faces[v_index] = Some(face);
+            }
+        }
+        vertex_faces
+    }
+}
+
+pub struct Ready {
+    projection_buf: wgpu::Buffer,
+    rotation_buf: wgpu::Buffer,
+    eye_buf: wgpu::Buffer,
+    objects: Vec<Drawn>,
     pipeline: wgpu::RenderPipeline,
+    // Only needed to rebuild the pipeline when the shader files change.
+    #[cfg(feature = "shaderc")]
+    pipeline_layout: wgpu::PipelineLayout,
+    #[cfg(feature = "shaderc")]
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+    // Drawn into instead of the target and resolved into it when multisampling.
+    multisampled: Option<wgpu::TextureView>,
+    #[cfg(feature = "shaderc")]
+    watch: Option<Watch>,
+    highlighted: Option<usize>,
 }
 
 /// Holds all pertinent data and configuration for rendering a scene onto the video device.
@@ -97,6 +335,16 @@ impl Scene<Begin> {
         Scene { state: Begin }
     }
 
+    /// Shaders that say which files they came from are recompiled when those files change
+    /// with the `shaderc` feature.
+    #[cfg(feature = "shaderc")]
+    pub fn shaders<T: CompiledShaders>(self, shaders: &T) -> Scene<Lights> {
+        let mut scene = self.manual_shaders(shaders.vertex(), shaders.fragment());
+        scene.state.watch = Watch::from_shaders(shaders);
+        scene
+    }
+
+    #[cfg(not(feature = "shaderc"))]
     pub fn shaders<T: CompiledShaders>(self, shaders: &T) -> Scene<Lights> {
         self.manual_shaders(shaders.vertex(), shaders.fragment())
     }
@@ -106,7 +354,13 @@ impl Scene<Begin> {
             state: Lights {
                 frag: frag.to_owned(),
                 vert: vert.to_owned(),
+                #[cfg(feature = "shaderc")]
+                watch: None,
                 lights: Vec::new(),
+                ambient: AMBIENT,
+                texture: None,
+                instances: Vec::new(),
+                sample_count: 1,
             }
         }
     }
@@ -115,15 +369,53 @@ impl Scene<Begin> {
 impl Scene<Lights> {
     /// Add a light. Don't add more than `MAX_LIGHTS` as they'll be ignored. If no lights
     /// are added the shape won't be visible.
-    ///
-    /// TODO: Signal to the fragment shader the number of lights loaded.
-    ///       Shader currently assumes exactly two.
     pub fn add_light(mut self, light: Light) -> Self {
         self.state.lights.push(light);
         self
     }
 
+    /// Light added to every face so the faces facing away from all the lights aren't
+    /// black. Defaults to a dim grey.
+    pub fn ambient(mut self, colour: wgpu::Color) -> Self {
+        self.state.ambient = colour;
+        self
+    }
+
     pub fn geometry<T: Geometry>(self, geometry: T) -> Scene<Prepare<T>> {
+        self.geometry_with_material(geometry, Material::default())
+    }
+
+    /// Draw another copy of the geometry. All the copies are drawn at once from the same
+    /// vertices. Without any instances the geometry is drawn once as it is.
+    pub fn add_instance(mut self, instance: Instance) -> Self {
+        self.state.instances.push(instance);
+        self
+    }
+
+    /// Wrap `texture` onto the geometry. Needs geometry with texture coordinates such as
+    /// from `planar::Polygon::as_scene_consumable`.
+    pub fn texture(mut self, texture: Texture) -> Self {
+        self.state.texture = Some(texture);
+        self
+    }
+
+    /// Smooth the jagged edges of the geometry by drawing with `sample_count` samples per
+    /// pixel. 4 works everywhere while 8 falls back on 4 when the video device can't do
+    /// it. Other counts are taken down to the next of 1, 4 or 8. Defaults to 1 which
+    /// doesn't multisample at all.
+    pub fn multisample(mut self, sample_count: u32) -> Self {
+        self.state.sample_count = match sample_count {
+            0..=3 => 1,
+            4..=7 => 4,
+            _ => 8,
+        };
+        self
+    }
+
+    /// The `material` covers the whole of the `geometry`.
+    pub fn geometry_with_material<T: Geometry>(
+        self, geometry: T, material: Material,
+    ) -> Scene<Prepare<T>> {
         let mut lights = self.state.lights;
         lights.truncate(MAX_LIGHTS);
         lights.shrink_to_fit();
@@ -131,8 +423,21 @@ impl Scene<Lights> {
         let p = Prepare {
             frag: self.state.frag,
             vert: self.state.vert,
+            #[cfg(feature = "shaderc")]
+            watch: self.state.watch,
             lights,
+            ambient: self.state.ambient,
+            texture: self.state.texture,
+            instances: if self.state.instances.is_empty() {
+                vec![Instance::default()]
+            } else {
+                self.state.instances
+            },
+            sample_count: self.state.sample_count,
             geometry,
+            material,
+            model: Matrix4::identity(),
+            objects: Vec::new(),
         };
 
         Scene { state: p }
@@ -140,294 +445,707 @@ impl Scene<Lights> {
 }
 
 impl<T: Geometry> Scene<Prepare<T>> {
+    /// Place the geometry with `model` instead of leaving it where it is.
+    pub fn model(mut self, model: Matrix4<f32>) -> Self {
+        self.state.model = model;
+        self
+    }
+
+    /// Draw `geometry` as well, placed with `model` and covered by `material`. When picking,
+    /// its faces are numbered on from the faces of the geometry added before it.
+    pub fn add_object<G: Geometry>(
+        mut self, geometry: G, model: Matrix4<f32>, material: Material,
+    ) -> Self {
+        let (vertices, index) = geometry.geometry();
+        let geometry = Cached::new(&vertices, index).with_faces(geometry.face_vertices());
+        self.state.objects.push(Object { geometry, model, material });
+        self
+    }
+
     pub fn prepare(
-        &self, desc: &wgpu::SwapChainDescriptor, device: &mut wgpu::Device,
+        &self, config: &wgpu::SurfaceConfiguration, device: &wgpu::Device, queue: &wgpu::Queue,
     ) -> Scene<Ready> {
-        let cmd_encoder = device
-            .create_command_encoder(
-                &wgpu::CommandEncoderDescriptor { todo: 0 }
-            );
-        
-        let m_vert = device.create_shader_module(&self.state.vert);
-        let m_frag = device.create_shader_module(&self.state.frag);
-       
-        let projection = Matrix4::zero();
-        let p_ref: &[f32; 16] = projection.as_ref();
-        let projection_buf = device
-            .create_buffer_mapped(
-                16,
-                wgpu::BufferUsageFlags::UNIFORM | wgpu::BufferUsageFlags::TRANSFER_DST,
-            )
-            .fill_from_slice(p_ref);
+        let projection_buf = uniform_buffer(device, "projection", &[0u8; 64]);
 
         // Add rotation uniform buffer here (like the projection uniform buffer)
-        let rotation = Matrix4::zero();
-        let r_ref: &[f32; 16] = rotation.as_ref();
-        let rotation_buf = device
-            .create_buffer_mapped(
-                16,
-                wgpu::BufferUsageFlags::UNIFORM | wgpu::BufferUsageFlags::TRANSFER_DST,
-            )
-            .fill_from_slice(r_ref);
+        let rotation_buf = uniform_buffer(device, "rotation", &[0u8; 64]);
 
-        let (vertices, index) = self.state.geometry.geometry();
-        
-        let vertex_buf = device
-            .create_buffer_mapped(vertices.len(), wgpu::BufferUsageFlags::VERTEX)
-            .fill_from_slice(&vertices);
-
-        let index_buf = device
-            .create_buffer_mapped(index.len(), wgpu::BufferUsageFlags::INDEX)
-            .fill_from_slice(&index);
-
-        let light_buf_size = (MAX_LIGHTS * LightRaw::sizeof()) as u32;
-        let light_buf_builder = device
-            .create_buffer_mapped(
-                light_buf_size as usize,
-                wgpu::BufferUsageFlags::UNIFORM | wgpu::BufferUsageFlags::TRANSFER_DST,
-            );
-        
-        self.state.lights
-            .iter()
-            .take(MAX_LIGHTS)
-            .enumerate()
-            .for_each(|(num, light)| light_buf_builder.data[num] = light.to_raw());
-                    
-        let light_buf = light_buf_builder.finish();
-
-        let light_count = self.state.lights.len() as u32;
-        let light_count_buf = device
-            .create_buffer_mapped(
-                1,
-                wgpu::BufferUsageFlags::UNIFORM | wgpu::BufferUsageFlags::TRANSFER_DST,
-            )
-            .fill_from_slice(&[light_count]);
+        let first = (
+            self.state.geometry.geometry(),
+            self.state.geometry.face_vertices(),
+            self.state.model,
+            &self.state.material,
+        );
+        let parts: Vec<_> = std::iter::once(first)
+            .chain(self.state.objects.iter().map(|object| (
+                object.geometry.geometry(),
+                object.geometry.face_vertices(),
+                object.model,
+                &object.material,
+            )))
+            .collect();
 
-        let bg_layout = device.create_bind_group_layout(
-            &wgpu::BindGroupLayoutDescriptor { bindings: &[
-                // Projection uniform buffer layout
-                wgpu::BindGroupLayoutBinding {
-                    binding: 0,
-                    visibility: wgpu::ShaderStageFlags::VERTEX,
-                    ty: wgpu::BindingType::UniformBuffer,
+        let mut lights = [LightRaw::zeroed(); MAX_LIGHTS];
+        for (raw, light) in lights.iter_mut().zip(&self.state.lights) {
+            *raw = light.to_raw();
+        }
+        let light_buf = uniform_buffer(device, "lights", bytemuck::cast_slice(&lights));
+
+        // Padded out to the 16 bytes a uniform buffer takes at the least.
+        let light_count = [self.state.lights.len() as u32, 0, 0, 0];
+        let light_count_buf = uniform_buffer(
+            device, "light count", bytemuck::cast_slice(&light_count),
+        );
+
+        let ambient = self.state.ambient;
+        let ambient = [ambient.r as f32, ambient.g as f32, ambient.b as f32, 1.0];
+        let ambient_buf = uniform_buffer(device, "ambient", bytemuck::cast_slice(&ambient));
+
+        let eye_buf = uniform_buffer(device, "eye", &[0u8; 16]);
+
+        // Without a texture a white one keeps the shaders the same.
+        let white = Texture::white();
+        let image = self.state.texture.as_ref().unwrap_or(&white);
+        let texture = device.create_texture_with_data(
+            queue,
+            &wgpu::TextureDescriptor {
+                label: Some("texture"),
+                size: wgpu::Extent3d {
+                    width: image.width,
+                    height: image.height,
+                    depth_or_array_layers: 1,
                 },
-                
-                // Rotation uniform buffer layout
-                wgpu::BindGroupLayoutBinding {
-                    binding: 1,
-                    visibility: wgpu::ShaderStageFlags::VERTEX,
-                    ty: wgpu::BindingType::UniformBuffer,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            &image.rgba,
+        );
+        let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("texture sampler"),
+            // Repeats across so faces running past the right edge of an equirectangular map
+            // carry on from the left.
+            address_mode_u: wgpu::AddressMode::Repeat,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Nearest,
+            lod_min_clamp: 0.0,
+            lod_max_clamp: 100.0,
+            ..Default::default()
+        });
+
+        let uniform = |binding: u32, visibility: wgpu::ShaderStages| {
+            wgpu::BindGroupLayoutEntry {
+                binding,
+                visibility,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
                 },
-                
+                count: None,
+            }
+        };
+        let bg_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("scene"),
+            entries: &[
+                // Projection uniform buffer layout
+                uniform(0, wgpu::ShaderStages::VERTEX),
+
+                // Rotation uniform buffer layout
+                uniform(1, wgpu::ShaderStages::VERTEX),
+
                 // Lights
-                wgpu::BindGroupLayoutBinding {
-                    binding: 2,
-                    visibility: wgpu::ShaderStageFlags::FRAGMENT,
-                    ty: wgpu::BindingType::UniformBuffer,
-                },
+                uniform(2, wgpu::ShaderStages::FRAGMENT),
 
                 // Light Count
-                wgpu::BindGroupLayoutBinding {
-                    binding: 3,
-                    visibility: wgpu::ShaderStageFlags::FRAGMENT,
-                    ty: wgpu::BindingType::UniformBuffer,
-                },
-            ]}            
-        );
+                uniform(3, wgpu::ShaderStages::FRAGMENT),
 
-        let pipeline_layout = device.create_pipeline_layout(
-            &wgpu::PipelineLayoutDescriptor { bind_group_layouts: &[&bg_layout], }
-        );
+                // Ambient light
+                uniform(4, wgpu::ShaderStages::FRAGMENT),
 
-        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            layout: &bg_layout,
-            bindings: &[
-                // Projection uniform buffer binding
-                wgpu::Binding {
-                    binding: 0,
-                    resource: wgpu::BindingResource::Buffer {
-                        buffer: &projection_buf,
-                        range: 0..64,
-                    }
-                },
-                
-                // Rotation uniform buffer binding
-                wgpu::Binding {
-                    binding: 1,
-                    resource: wgpu::BindingResource::Buffer {
-                        buffer: &rotation_buf,
-                        range: 0..64
-                    }
-                },
-                
-                // Light uniform buffer binding
-                wgpu::Binding {
-                    binding: 2,
-                    resource: wgpu::BindingResource::Buffer {
-                        buffer: &light_buf,
-                        range: 0..light_buf_size,
-                    }
+                // Material
+                uniform(5, wgpu::ShaderStages::FRAGMENT),
+
+                // Camera position
+                uniform(6, wgpu::ShaderStages::FRAGMENT),
+
+                // Texture
+                wgpu::BindGroupLayoutEntry {
+                    binding: 7,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
                 },
 
-                // Light count buffer binding (just a single byte!)
-                wgpu::Binding {
-                    binding: 3,
-                    resource: wgpu::BindingResource::Buffer {
-                        buffer: &light_count_buf,
-                        range: 0..1,
-                    }
+                // Texture sampler
+                wgpu::BindGroupLayoutEntry {
+                    binding: 8,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
                 },
             ],
         });
-        
-        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            layout: &pipeline_layout,
-            vertex_stage: wgpu::PipelineStageDescriptor {
-                module: &m_vert,
-                entry_point: "main",
-            },
-            fragment_stage: wgpu::PipelineStageDescriptor {
-                module: &m_frag,
-                entry_point: "main",
-            },
-            rasterization_state: wgpu::RasterizationStateDescriptor {
-                front_face: wgpu::FrontFace::Cw,
-                cull_mode: wgpu::CullMode::Front,
-                depth_bias: 2,
-                depth_bias_slope_scale: 2.0,
-                depth_bias_clamp: 0.0,
-            },
-            primitive_topology: wgpu::PrimitiveTopology::TriangleList,
-            color_states: &[wgpu::ColorStateDescriptor {
-                format: desc.format,
-                color: wgpu::BlendDescriptor::REPLACE,
-                alpha: wgpu::BlendDescriptor::REPLACE,
-                write_mask: wgpu::ColorWriteFlags::ALL,
-            }],
-            depth_stencil_state: None,
-            index_format: wgpu::IndexFormat::Uint16,
-            vertex_buffers: &[wgpu::VertexBufferDescriptor {
-                stride: Vertex::sizeof() as u32,
-                step_mode: wgpu::InputStepMode::Vertex,
-                attributes: &[
-                    // These are the vertexes. Location 0.
-                    wgpu::VertexAttributeDescriptor { 
-                        attribute_index: 0,
-                        format: wgpu::VertexFormat::Float3,
-                        offset: 0,
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("scene"),
+            bind_group_layouts: &[&bg_layout],
+            push_constant_ranges: &[],
+        });
+
+        let create_bind_group = |material_buf: &wgpu::Buffer| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("scene"),
+                layout: &bg_layout,
+                entries: &[
+                    // Projection uniform buffer binding
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: projection_buf.as_entire_binding(),
                     },
-                    
-                    // Our per vertex normal. Location 1.
-                    wgpu::VertexAttributeDescriptor {
-                        attribute_index: 1,
-                        format: wgpu::VertexFormat::Float3,
-                        offset: 4 * 3,
+
+                    // Rotation uniform buffer binding
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: rotation_buf.as_entire_binding(),
                     },
-                    
-                    // This is the colour. Location 2.
-                    wgpu::VertexAttributeDescriptor { 
-                        attribute_index: 2,
-                        format: wgpu::VertexFormat::Float3,
-                        offset: 4 * 6,
+
+                    // Light uniform buffer binding
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: light_buf.as_entire_binding(),
+                    },
+
+                    // Light count buffer binding
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: light_count_buf.as_entire_binding(),
+                    },
+
+                    // Ambient light buffer binding
+                    wgpu::BindGroupEntry {
+                        binding: 4,
+                        resource: ambient_buf.as_entire_binding(),
+                    },
+
+                    // Material buffer binding
+                    wgpu::BindGroupEntry {
+                        binding: 5,
+                        resource: material_buf.as_entire_binding(),
+                    },
+
+                    // Camera position buffer binding
+                    wgpu::BindGroupEntry {
+                        binding: 6,
+                        resource: eye_buf.as_entire_binding(),
+                    },
+
+                    // Texture binding
+                    wgpu::BindGroupEntry {
+                        binding: 7,
+                        resource: wgpu::BindingResource::TextureView(&texture_view),
+                    },
+
+                    // Texture sampler binding
+                    wgpu::BindGroupEntry {
+                        binding: 8,
+                        resource: wgpu::BindingResource::Sampler(&sampler),
                     },
                 ],
-            }],
-            sample_count: 1,
-        });
-        
-        let cmd_buf = cmd_encoder.finish();
-        
-        device.get_queue()
-            .submit(&[cmd_buf]);
+            })
+        };
+
+        // Eight samples need the adapter's own format features, see `multisample`.
+        let specific = wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES;
+        let sample_count = self.state.sample_count;
+        let sample_count = if sample_count > 4 && !device.features().contains(specific) {
+            warn!("Can't draw with {} samples per pixel. Using 4.", sample_count);
+            4
+        } else {
+            sample_count
+        };
+        let multisampled = multisampled_target(device, config, sample_count);
+
+        let pipeline = create_pipeline(
+            device,
+            &pipeline_layout,
+            &self.state.vert,
+            &self.state.frag,
+            config.format,
+            sample_count,
+        );
+
+        let mut objects: Vec<Drawn> = Vec::with_capacity(parts.len());
+        for ((vertices, index), face_vertices, model, material) in parts {
+            let index = Index::fit(index.to_u32(), vertices.len());
+            let (vertex_buf, index_buf) = Drawn::buffers(device, &vertices, &index);
+
+            // Every instance of the scene holds a copy of every geometry.
+            let instances: Vec<Instance> = self.state.instances
+                .iter()
+                .map(|instance| Instance::new(instance.model * model, instance.tint))
+                .collect();
+            let instance_raws: Vec<InstanceRaw> = instances
+                .iter()
+                .map(|instance| instance.to_raw())
+                .collect();
+            let instance_buf = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("instances"),
+                contents: bytemuck::cast_slice(&instance_raws),
+                usage: wgpu::BufferUsages::VERTEX,
+            });
+
+            let material_buf = uniform_buffer(
+                device, "material", bytemuck::bytes_of(&material.to_raw()),
+            );
+            let bind_group = create_bind_group(&material_buf);
+
+            // Faces are numbered across all the geometries.
+            let first_face = objects.last().map(|drawn| drawn.faces.end).unwrap_or(0);
+            let vertex_faces = Drawn::vertex_faces(vertices.len(), &face_vertices);
+
+            objects.push(Drawn {
+                vertex_buf,
+                index_buf,
+                index_len: index.len(),
+                index_format: index.format(),
+                instance_buf,
+                instances: instances.iter().map(|instance| instance.model).collect(),
+                bind_group,
+                vertices,
+                index: index.to_u32(),
+                vertex_faces,
+                faces: first_face..first_face + face_vertices.len(),
+            });
+        }
 
-        let index_len = index.len();
-        
         let ready = Ready {
-            //light_buf,
-            //light_count_buf,
             projection_buf,
             rotation_buf,
-            vertex_buf,
-            index_buf,
-            index_len,
-            bind_group,
+            eye_buf,
+            objects,
             pipeline,
+            #[cfg(feature = "shaderc")]
+            pipeline_layout,
+            #[cfg(feature = "shaderc")]
+            format: config.format,
+            sample_count,
+            multisampled,
+            #[cfg(feature = "shaderc")]
+            watch: self.state.watch.clone(),
+            highlighted: None,
         };
 
         Scene { state: ready }
     }
 }
 
+impl Scene<Ready> {
+    /// Swap the first geometry for `geometry` keeping everything else including the
+    /// placement and material of the old one. Clears the highlight.
+    pub fn replace_geometry<G: Geometry>(
+        &mut self, geometry: &G, device: &wgpu::Device, queue: &wgpu::Queue,
+    ) -> Result<(), ReplaceError> {
+        self.replace_object(0, geometry, device, queue)
+    }
+
+    /// Same as `replace_geometry` but for the `object`th geometry counting the first as 0.
+    /// The faces of the geometries after it are renumbered when the face count changes.
+    pub fn replace_object<G: Geometry>(
+        &mut self, object: usize, geometry: &G, device: &wgpu::Device, queue: &wgpu::Queue,
+    ) -> Result<(), ReplaceError> {
+        if object >= self.state.objects.len() {
+            return Err(ReplaceError::NoSuchObject(object));
+        }
+
+        self.highlight(None, queue);
+
+        let (vertices, index) = geometry.geometry();
+        let index = Index::fit(index.to_u32(), vertices.len());
+        let face_vertices = geometry.face_vertices();
+        let (vertex_buf, index_buf) = Drawn::buffers(device, &vertices, &index);
+        let drawn = &mut self.state.objects[object];
+        drawn.vertex_buf = vertex_buf;
+        drawn.index_buf = index_buf;
+        drawn.index_len = index.len();
+        drawn.index_format = index.format();
+        drawn.vertex_faces = Drawn::vertex_faces(vertices.len(), &face_vertices);
+        drawn.vertices = vertices;
+        drawn.index = index.to_u32();
+        drawn.faces = drawn.faces.start..drawn.faces.start + face_vertices.len();
+
+        let mut first_face = drawn.faces.end;
+        for drawn in self.state.objects.iter_mut().skip(object + 1) {
+            let face_count = drawn.faces.len();
+            drawn.faces = first_face..first_face + face_count;
+            first_face = drawn.faces.end;
+        }
+
+        Ok(())
+    }
+}
+
+/// Geometry that can't replace what's already on the video device.
+#[derive(Debug, Copy, Clone)]
+pub enum ReplaceError {
+    NoSuchObject(usize),
+}
+
+impl fmt::Display for ReplaceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReplaceError::NoSuchObject(n) => write!(f, "There is no geometry number {}.", n),
+        }
+    }
+}
+
+impl error::Error for ReplaceError {
+    fn description(&self) -> &str {
+        "Error replacing geometry."
+    }
+}
+
 impl Renderable for Scene<Ready> {
+    /// The multisampled texture has to stay the same size as the frames it's resolved into.
+    fn resize(&mut self, config: &wgpu::SurfaceConfiguration, device: &wgpu::Device) {
+        self.state.multisampled = multisampled_target(device, config, self.state.sample_count);
+    }
+
     fn render(
         &mut self,
         projection: &Matrix4<f32>,
         rotation: &Matrix4<f32>,
-        frame: &wgpu::SwapChainOutput,
-        device: &mut wgpu::Device,
+        eye: &Point3<f32>,
+        target: &wgpu::TextureView,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
     ) {
-        let mut encoder = device.create_command_encoder(
-            &wgpu::CommandEncoderDescriptor { todo: 0 }
-        );
-
-        // Update with the sent projection
-        {
-            let p_ref: &[f32; 16] = projection.as_ref();
-            let new_projection_buf = device
-                .create_buffer_mapped(
-                    16,
-                    wgpu::BufferUsageFlags::UNIFORM | wgpu::BufferUsageFlags::TRANSFER_SRC,
-                )
-                .fill_from_slice(p_ref);
-            
-            encoder.copy_buffer_to_buffer(
-                &new_projection_buf, 0, &self.state.projection_buf, 0, 16 * 4
-            );
-        }
+        // The cameras were written for clip space with y pointing down the screen. wgpu has
+        // it pointing up.
+        let projection = Matrix4::from_nonuniform_scale(1.0, -1.0, 1.0) * projection;
+        let p_ref: &[f32; 16] = projection.as_ref();
+        queue.write_buffer(&self.state.projection_buf, 0, bytemuck::bytes_of(p_ref));
 
         // Ditto with the rotation
-        {
-            let r_ref: &[f32; 16] = rotation.as_ref();
-            let new_rotation_buf = device
-                .create_buffer_mapped(
-                    16,
-                    wgpu::BufferUsageFlags::UNIFORM | wgpu::BufferUsageFlags::TRANSFER_SRC,
-                )
-                .fill_from_slice(r_ref);
-
-            encoder.copy_buffer_to_buffer(
-                &new_rotation_buf, 0, &self.state.rotation_buf, 0, 16 * 4
-            );
-        }
+        let r_ref: &[f32; 16] = rotation.as_ref();
+        queue.write_buffer(&self.state.rotation_buf, 0, bytemuck::bytes_of(r_ref));
+
+        // And where the camera is for the highlights
+        let eye = [eye.x, eye.y, eye.z, 1.0];
+        queue.write_buffer(&self.state.eye_buf, 0, bytemuck::bytes_of(&eye));
+
+        let mut encoder = device.create_command_encoder(
+            &wgpu::CommandEncoderDescriptor { label: Some("scene") }
+        );
 
-        // Render
+        // Render. Only the resolved samples are kept when multisampling.
+        let attachment = match &self.state.multisampled {
+            Some(multisampled) => wgpu::RenderPassColorAttachment {
+                view: multisampled,
+                resolve_target: Some(target),
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Discard,
+                },
+            },
+            None => wgpu::RenderPassColorAttachment {
+                view: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+            },
+        };
         {
             let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
-                    attachment: &frame.view,
-                    load_op: wgpu::LoadOp::Clear,
-                    store_op: wgpu::StoreOp::Store,
-                    clear_color: wgpu::Color::BLACK,
-                }],
+                label: Some("scene"),
+                color_attachments: &[Some(attachment)],
                 depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
             });
             rpass.set_pipeline(&self.state.pipeline);
-            rpass.set_bind_group(0, &self.state.bind_group);
-            rpass.set_index_buffer(&self.state.index_buf, 0);
-            rpass.set_vertex_buffers(&[(&self.state.vertex_buf, 0)]);
-            rpass.draw_indexed(0..self.state.index_len as u32, 0, 0..1);
+            for object in &self.state.objects {
+                rpass.set_bind_group(0, &object.bind_group, &[]);
+                rpass.set_index_buffer(object.index_buf.slice(..), object.index_format);
+                rpass.set_vertex_buffer(0, object.vertex_buf.slice(..));
+                rpass.set_vertex_buffer(1, object.instance_buf.slice(..));
+                rpass.draw_indexed(
+                    0..object.index_len as u32, 0, 0..object.instances.len() as u32,
+                );
+            }
+        }
+
+        queue.submit(Some(encoder.finish()));
+    }
+
+    /// Tries every instance of every geometry. The ray is taken into the space of each
+    /// instance without being normalized so the distances along it can be compared between
+    /// them. The faces of later geometries are numbered on from those before.
+    fn pick(&self, origin: Point3<f32>, direction: Vector3<f32>) -> Option<usize> {
+        self.state.objects
+            .iter()
+            .flat_map(|object| object.instances
+                .iter()
+                .filter_map(|model| model.invert())
+                .map(move |inverse| (object, inverse))
+            )
+            .flat_map(|(object, inverse)| {
+                let origin = inverse.transform_point(origin);
+                let direction = inverse.transform_vector(direction);
+                let position = move |v_index: u32| {
+                    Point3::from(object.vertices[v_index as usize].position)
+                };
+                object.index
+                    .chunks(3)
+                    .filter_map(move |t| {
+                        let face = object.vertex_faces[t[0] as usize]?;
+                        let triangle = [position(t[0]), position(t[1]), position(t[2])];
+                        geop::ray_triangle_intersection(origin, direction, triangle)
+                            .map(|distance| (object.faces.start + face, distance))
+                    })
+            })
+            .fold(None, |nearest: Option<(usize, f32)>, (face, distance)| match nearest {
+                Some((_, nearest_distance)) if nearest_distance <= distance => nearest,
+                _ => Some((face, distance)),
+            })
+            .map(|(face, _)| face)
+    }
+
+    /// Keeps drawing with the old shaders when the new ones don't compile.
+    #[cfg(feature = "shaderc")]
+    fn reload_shaders(&mut self, device: &wgpu::Device) {
+        let changed = self.state.watch.as_mut().map(|watch| watch.changed()).unwrap_or(false);
+        if !changed {
+            return;
+        }
+
+        match self.state.watch.as_ref().map(|watch| watch.compile()) {
+            Some(Ok((vert, frag))) => {
+                info!("Shaders changed. Rebuilding the pipeline.");
+                self.state.pipeline = create_pipeline(
+                    device,
+                    &self.state.pipeline_layout,
+                    &vert,
+                    &frag,
+                    self.state.format,
+                    self.state.sample_count,
+                );
+            },
+            Some(Err(e)) => warn!("Shaders changed but failed to compile: {}", e),
+            None => (),
+        }
+    }
+
+    fn highlight(&mut self, face: Option<usize>, queue: &wgpu::Queue) {
+        if face == self.state.highlighted {
+            return;
+        }
+        let previous = mem::replace(&mut self.state.highlighted, face);
+
+        // Only the geometries losing or gaining the highlight need rewriting.
+        let changed = |object: &&Drawn| {
+            [previous, face]
+                .iter()
+                .any(|f| f.map(|f| object.faces.contains(&f)).unwrap_or(false))
+        };
+
+        for object in self.state.objects.iter().filter(changed) {
+            let local_face = face
+                .filter(|face| object.faces.contains(face))
+                .map(|face| face - object.faces.start);
+            let vertices: Vec<Vertex> = object.vertices
+                .iter()
+                .zip(object.vertex_faces.iter())
+                .map(|(vertex, vertex_face)| match vertex_face {
+                    Some(_) if *vertex_face == local_face => {
+                        Vertex { colour: HIGHLIGHT, ..*vertex }
+                    },
+                    _ => *vertex,
+                })
+                .collect();
+
+            queue.write_buffer(&object.vertex_buf, 0, bytemuck::cast_slice(&vertices));
         }
+    }
+}
+
+/// A uniform buffer starting out holding `contents` that can be written to later.
+fn uniform_buffer(device: &wgpu::Device, label: &str, contents: &[u8]) -> wgpu::Buffer {
+    device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some(label),
+        contents,
+        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+    })
+}
 
-        device.get_queue().submit(&[encoder.finish()]);
+/// The texture to draw `sample_count` samples per pixel into before they're resolved into
+/// the frames described by `config`. `None` when there's only the one sample.
+fn multisampled_target(
+    device: &wgpu::Device, config: &wgpu::SurfaceConfiguration, sample_count: u32,
+) -> Option<wgpu::TextureView> {
+    if sample_count == 1 {
+        return None;
     }
+
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("multisampled"),
+        size: wgpu::Extent3d {
+            width: config.width,
+            height: config.height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count,
+        dimension: wgpu::TextureDimension::D2,
+        format: config.format,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    });
+
+    Some(texture.create_view(&wgpu::TextureViewDescriptor::default()))
+}
+
+/// The pipeline drawing the geometry with the `vert` and `frag` shaders into images of
+/// `format` with `sample_count` samples per pixel.
+fn create_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    vert: &[u8],
+    frag: &[u8],
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+) -> wgpu::RenderPipeline {
+    let m_vert = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("vertex shader"),
+        source: wgpu::util::make_spirv(vert),
+    });
+    let m_frag = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("fragment shader"),
+        source: wgpu::util::make_spirv(frag),
+    });
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("scene"),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: &m_vert,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            buffers: &[wgpu::VertexBufferLayout {
+                array_stride: Vertex::sizeof() as wgpu::BufferAddress,
+                step_mode: wgpu::VertexStepMode::Vertex,
+                attributes: &[
+                    // These are the vertexes. Location 0.
+                    wgpu::VertexAttribute {
+                        shader_location: 0,
+                        format: wgpu::VertexFormat::Float32x3,
+                        offset: 0,
+                    },
+
+                    // Our per vertex normal. Location 1.
+                    wgpu::VertexAttribute {
+                        shader_location: 1,
+                        format: wgpu::VertexFormat::Float32x3,
+                        offset: 4 * 3,
+                    },
+
+                    // This is the colour. Location 2.
+                    wgpu::VertexAttribute {
+                        shader_location: 2,
+                        format: wgpu::VertexFormat::Float32x3,
+                        offset: 4 * 6,
+                    },
+
+                    // Texture coordinates. Location 3.
+                    wgpu::VertexAttribute {
+                        shader_location: 3,
+                        format: wgpu::VertexFormat::Float32x2,
+                        offset: 4 * 9,
+                    },
+                ],
+            },
+            wgpu::VertexBufferLayout {
+                array_stride: InstanceRaw::sizeof() as wgpu::BufferAddress,
+                step_mode: wgpu::VertexStepMode::Instance,
+                attributes: &[
+                    // The model matrix a column at a time. Locations 4 to 7.
+                    wgpu::VertexAttribute {
+                        shader_location: 4,
+                        format: wgpu::VertexFormat::Float32x4,
+                        offset: 0,
+                    },
+                    wgpu::VertexAttribute {
+                        shader_location: 5,
+                        format: wgpu::VertexFormat::Float32x4,
+                        offset: 4 * 4,
+                    },
+                    wgpu::VertexAttribute {
+                        shader_location: 6,
+                        format: wgpu::VertexFormat::Float32x4,
+                        offset: 4 * 8,
+                    },
+                    wgpu::VertexAttribute {
+                        shader_location: 7,
+                        format: wgpu::VertexFormat::Float32x4,
+                        offset: 4 * 12,
+                    },
+
+                    // The tint. Location 8.
+                    wgpu::VertexAttribute {
+                        shader_location: 8,
+                        format: wgpu::VertexFormat::Float32x3,
+                        offset: 4 * 16,
+                    },
+                ],
+            }],
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: &m_frag,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(wgpu::BlendState::REPLACE),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            front_face: wgpu::FrontFace::Cw,
+            cull_mode: Some(wgpu::Face::Front),
+            ..Default::default()
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState {
+            count: sample_count,
+            ..Default::default()
+        },
+        multiview: None,
+        cache: None,
+    })
 }
 
 impl<T: Geometry> Initializable for Scene<Prepare<T>> {
     type Ready = Scene<Ready>;
     
     fn init(
-        self, desc: &wgpu::SwapChainDescriptor, device: &mut wgpu::Device
+        self, config: &wgpu::SurfaceConfiguration, device: &wgpu::Device, queue: &wgpu::Queue,
     ) -> Self::Ready {
-        self.prepare(desc, device)
+        self.prepare(config, device, queue)
     }
 }
@@ -1,15 +1,32 @@
 //! Shader handling stuff
-use std::{fs, path};
+//!
+//! The shaders in `shaders/` are compiled to SPIR-V along with the crate. With the `shaderc`
+//! feature they're compiled again when the program starts and whenever they're saved.
+use std::{error, fmt, fs, io, path};
+#[cfg(feature = "shaderc")]
+use std::time;
 
+#[cfg(feature = "shaderc")]
+use log::warn;
+#[cfg(feature = "shaderc")]
 use shaderc::{ShaderKind, Error, Compiler};
 
+/// SPIR-V of the shaders in `shaders/` compiled when the crate was built.
+mod embedded {
+    pub const FLAT_VERT: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/flat.vert.spv"));
+    pub const FLAT_FRAG: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/flat.frag.spv"));
+    pub const PBR_FRAG: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/pbr.frag.spv"));
+}
+
+/// First word of every SPIR-V module.
+const SPIRV_MAGIC: u32 = 0x0723_0203;
+
+#[cfg(feature = "shaderc")]
 pub fn load(name: &str, entry: &str, kind: ShaderKind) -> Result<Vec<u8>, Error> {
     let mut compiler = Compiler::new()
         .ok_or(Error::NullResultObject("Can't create compiler.".to_owned()))?;
 
-    let filepath = path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-        .join("shaders")
-        .join(name);
+    let filepath = filepath(name);
 
     let contents = fs::read_to_string(&filepath)
         .map_err(|e| Error::NullResultObject(format!("{}", &e)))?;
@@ -19,18 +36,122 @@ pub fn load(name: &str, entry: &str, kind: ShaderKind) -> Result<Vec<u8>, Error>
     Ok(artifact.as_binary_u8().to_owned())
 }
 
+#[cfg(feature = "shaderc")]
+fn filepath(name: &str) -> path::PathBuf {
+    path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("shaders")
+        .join(name)
+}
+
+#[cfg(feature = "shaderc")]
 pub fn load_vert(name: &str, entry: &str) -> Result<Vec<u8>, Error> {
     load(name, entry, ShaderKind::Vertex)
 }
 
+#[cfg(feature = "shaderc")]
 pub fn load_frag(name: &str, entry: &str) -> Result<Vec<u8>, Error> {
     load(name, entry, ShaderKind::Fragment)
 }
 
+/// Whether `bytes` look like a SPIR-V module. Only checks the size and magic number.
+pub fn is_spirv(bytes: &[u8]) -> bool {
+    if bytes.len() < 4 || !bytes.len().is_multiple_of(4) {
+        return false;
+    }
+    let magic = [bytes[0], bytes[1], bytes[2], bytes[3]];
+    u32::from_le_bytes(magic) == SPIRV_MAGIC || u32::from_be_bytes(magic) == SPIRV_MAGIC
+}
+
+/// Read an already compiled shader from a `.spv` file.
+pub fn load_spirv<P: AsRef<path::Path>>(filepath: P) -> Result<Vec<u8>, SpirvError> {
+    let bytes = fs::read(filepath).map_err(SpirvError::Io)?;
+    if is_spirv(&bytes) {
+        Ok(bytes)
+    } else {
+        Err(SpirvError::NotSpirv)
+    }
+}
+
+#[derive(Debug)]
+pub enum SpirvError {
+    Io(io::Error),
+    NotSpirv,
+}
+
+impl fmt::Display for SpirvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SpirvError::Io(e) => write!(f, "Can't read SPIR-V: {}", e),
+            SpirvError::NotSpirv => write!(f, "Not a SPIR-V module."),
+        }
+    }
+}
+
+impl error::Error for SpirvError {
+    fn description(&self) -> &str {
+        "Error loading SPIR-V."
+    }
+}
+
 /// Encapsulated shaders.
 pub trait CompiledShaders {
     fn fragment(&self) -> &[u8];
     fn vertex(&self) -> &[u8];
+
+    /// Names of the vertex and fragment shader files in `shaders/` these were compiled from.
+    /// Only shaders that say where they came from can be reloaded when the files change.
+    fn sources(&self) -> Option<(&str, &str)> {
+        None
+    }
+}
+
+/// Keeps an eye on a pair of shader files to recompile them when either changes.
+#[cfg(feature = "shaderc")]
+#[derive(Debug, Clone)]
+pub struct Watch {
+    vert: String,
+    frag: String,
+    modified: Option<time::SystemTime>,
+}
+
+#[cfg(feature = "shaderc")]
+impl Watch {
+    pub fn new(vert: &str, frag: &str) -> Self {
+        let mut watch = Watch {
+            vert: vert.to_owned(),
+            frag: frag.to_owned(),
+            modified: None,
+        };
+        watch.modified = watch.last_modified();
+        watch
+    }
+
+    pub fn from_shaders<T: CompiledShaders>(shaders: &T) -> Option<Self> {
+        shaders.sources().map(|(vert, frag)| Watch::new(vert, frag))
+    }
+
+    /// Whether either file was saved since the last time this was asked. Files that can't
+    /// be read don't count as changed.
+    pub fn changed(&mut self) -> bool {
+        let modified = self.last_modified();
+        if modified.is_some() && modified != self.modified {
+            self.modified = modified;
+            return true;
+        }
+        false
+    }
+
+    /// Vertex then fragment shader.
+    pub fn compile(&self) -> Result<(Vec<u8>, Vec<u8>), Error> {
+        Ok((load_vert(&self.vert, "main")?, load_frag(&self.frag, "main")?))
+    }
+
+    fn last_modified(&self) -> Option<time::SystemTime> {
+        [&self.vert, &self.frag]
+            .iter()
+            .filter_map(|name| fs::metadata(filepath(name)).and_then(|m| m.modified()).ok())
+            .max()
+    }
 }
 
 /// Basic flat shader.
@@ -54,11 +175,126 @@ impl CompiledShaders for FlatShaders {
     fn vertex(&self) -> &[u8] {
         self.vertex.as_slice()
     }
+
+    fn sources(&self) -> Option<(&str, &str)> {
+        Some(("flat.vert", "flat.frag"))
+    }
 }
 
-pub fn load_flat_shaders() -> Result<impl CompiledShaders, Error> {
-    let vert = load_vert("flat.vert", "main")?;
-    let frag = load_frag("flat.frag", "main")?;
+/// Falls back on the shaders compiled with the crate when they can't be compiled now, such
+/// as when the `shaders/` directory isn't around or the `shaderc` feature is off.
+pub fn load_flat_shaders() -> crate::Result<impl CompiledShaders> {
+    #[cfg(feature = "shaderc")]
+    let (vert, frag) = load_pair("flat.vert", "flat.frag")
+        .unwrap_or_else(|e| {
+            warn!("Using the built in flat shaders. Can't compile them: {}", e);
+            (embedded::FLAT_VERT.to_owned(), embedded::FLAT_FRAG.to_owned())
+        });
+    #[cfg(not(feature = "shaderc"))]
+    let (vert, frag) = (embedded::FLAT_VERT.to_owned(), embedded::FLAT_FRAG.to_owned());
 
     Ok(FlatShaders::new(frag, vert))
 }
+
+#[cfg(feature = "shaderc")]
+fn load_pair(vert: &str, frag: &str) -> Result<(Vec<u8>, Vec<u8>), Error> {
+    Ok((load_vert(vert, "main")?, load_frag(frag, "main")?))
+}
+
+/// Physically based shader using the roughness and metallic of the scene `Material`.
+#[derive(Debug, Clone)]
+pub struct PbrShaders {
+    fragment: Vec<u8>,
+    vertex: Vec<u8>,
+}
+
+impl PbrShaders {
+    fn new(fragment: Vec<u8>, vertex: Vec<u8>) -> Self {
+        PbrShaders { fragment, vertex }
+    }
+}
+
+impl CompiledShaders for PbrShaders {
+    fn fragment(&self) -> &[u8] {
+        self.fragment.as_slice()
+    }
+
+    fn vertex(&self) -> &[u8] {
+        self.vertex.as_slice()
+    }
+
+    fn sources(&self) -> Option<(&str, &str)> {
+        Some(("flat.vert", "pbr.frag"))
+    }
+}
+
+/// The vertices are handled the same as the flat shaders. Only the lighting differs. Falls
+/// back on the built in shaders the same as `load_flat_shaders`.
+pub fn load_pbr_shaders() -> crate::Result<impl CompiledShaders> {
+    #[cfg(feature = "shaderc")]
+    let (vert, frag) = load_pair("flat.vert", "pbr.frag")
+        .unwrap_or_else(|e| {
+            warn!("Using the built in PBR shaders. Can't compile them: {}", e);
+            (embedded::FLAT_VERT.to_owned(), embedded::PBR_FRAG.to_owned())
+        });
+    #[cfg(not(feature = "shaderc"))]
+    let (vert, frag) = (embedded::FLAT_VERT.to_owned(), embedded::PBR_FRAG.to_owned());
+
+    Ok(PbrShaders::new(frag, vert))
+}
+
+/// Shaders compiled beforehand, read from `.spv` files or embedded in the program.
+#[derive(Debug, Clone)]
+pub struct SpirvShaders {
+    fragment: Vec<u8>,
+    vertex: Vec<u8>,
+}
+
+impl SpirvShaders {
+    /// `None` unless both are SPIR-V.
+    pub fn from_bytes(vertex: &[u8], fragment: &[u8]) -> Option<Self> {
+        if is_spirv(vertex) && is_spirv(fragment) {
+            Some(SpirvShaders { fragment: fragment.to_owned(), vertex: vertex.to_owned() })
+        } else {
+            None
+        }
+    }
+
+    pub fn from_files<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+        vertex: P, fragment: Q,
+    ) -> Result<Self, SpirvError> {
+        Ok(SpirvShaders {
+            fragment: load_spirv(fragment)?,
+            vertex: load_spirv(vertex)?,
+        })
+    }
+}
+
+impl CompiledShaders for SpirvShaders {
+    fn fragment(&self) -> &[u8] {
+        self.fragment.as_slice()
+    }
+
+    fn vertex(&self) -> &[u8] {
+        self.vertex.as_slice()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn embedded_are_spirv() {
+        assert!(is_spirv(embedded::FLAT_VERT));
+        assert!(is_spirv(embedded::FLAT_FRAG));
+        assert!(is_spirv(embedded::PBR_FRAG));
+    }
+
+    #[test]
+    fn not_spirv() {
+        assert!(!is_spirv(b"#version 450"));
+        assert!(!is_spirv(&[0x03, 0x02, 0x23]));
+        assert!(SpirvShaders::from_bytes(b"void", embedded::FLAT_FRAG).is_none());
+    }
+}
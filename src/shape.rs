@@ -1,10 +1,17 @@
 //! Various 2d shapes used as building blocks for solids.
 //!
-//! Being kept in repo just in case I'll need this code.
+//! Being kept in repo just in case I'll need this code. Also has spheres to compare
+//! polyhedra against.
 use std::ops::Neg;
 
 use cgmath::Point2;
 
+#[cfg(feature = "render")]
+mod sphere;
+
+#[cfg(feature = "render")]
+pub use self::sphere::{Icosphere, UvSphere};
+
 /// Create an equilateral triangle centered on (0, 0). It's up to consumers to
 /// translate/scale/rotate the triangle for their needs.
 ///
@@ -0,0 +1,191 @@
+//! Smooth spheres made of many triangles to set beside a polyhedron as a reference, such as
+//! to see how round a Goldberg polyhedron is. Each vertex normal points straight out from
+//! the center so the lighting hides the edges.
+use std::collections::HashMap;
+use std::f32::consts::PI;
+
+use cgmath::{Point3, Vector3};
+use cgmath::prelude::*;
+
+use crate::platonic_solid::Icosahedron2;
+use crate::polyhedron::VertexAndFaceOps;
+use crate::scene;
+
+/// An icosahedron with each triangle split into four again and again, puffing the new
+/// vertices out onto the sphere each time. The triangles are all close to the same size.
+#[derive(Debug, Copy, Clone)]
+pub struct Icosphere {
+    subdivisions: usize,
+    radius: f32,
+    colour: [f32; 3],
+}
+
+impl Icosphere {
+    /// Has `20 * 4^subdivisions` triangles.
+    pub fn new(subdivisions: usize, radius: f32, colour: [f32; 3]) -> Self {
+        Icosphere { subdivisions, radius, colour }
+    }
+}
+
+impl scene::Geometry for Icosphere {
+    fn geometry(&self) -> (Vec<scene::Vertex>, scene::Index) {
+        let icosahedron = Icosahedron2::new(1.0).generate();
+        let (vertices, faces) = icosahedron.vertices_and_faces();
+        let mut points: Vec<Vector3<f32>> = vertices
+            .iter()
+            .map(|v| v.cast::<f32>().unwrap().to_vec().normalize())
+            .collect();
+        let mut triangles: Vec<[u32; 3]> = faces
+            .iter()
+            .map(|face| [face[0] as u32, face[1] as u32, face[2] as u32])
+            .collect();
+
+        for _ in 0..self.subdivisions {
+            // Neighbouring triangles share the vertex in the middle of their edge.
+            let mut middles: HashMap<(u32, u32), u32> = HashMap::new();
+            let mut middle = |v1: u32, v2: u32| -> u32 {
+                let key = (v1.min(v2), v1.max(v2));
+                *middles.entry(key).or_insert_with(|| {
+                    let point = (points[v1 as usize] + points[v2 as usize]).normalize();
+                    points.push(point);
+                    (points.len() - 1) as u32
+                })
+            };
+
+            triangles = triangles
+                .iter()
+                .flat_map(|&[a, b, c]| {
+                    let (ab, bc, ca) = (middle(a, b), middle(b, c), middle(c, a));
+                    vec![[a, ab, ca], [ab, b, bc], [ca, bc, c], [ab, bc, ca]]
+                })
+                .collect();
+        }
+
+        let vertices: Vec<scene::Vertex> = points
+            .iter()
+            .map(|p| {
+                let position = p * self.radius;
+                scene::Vertex::new(position.into(), (*p).into(), self.colour)
+            })
+            .collect();
+        let index = triangles.into_iter().flat_map(|t| t.to_vec()).collect();
+
+        let count = vertices.len();
+        (vertices, scene::Index::fit(index, count))
+    }
+}
+
+/// Rings of latitude and segments of longitude like a globe. Triangles bunch up at the
+/// poles. Textures wrap around it like a world map, the same as `Mapping::Equirectangular`.
+#[derive(Debug, Copy, Clone)]
+pub struct UvSphere {
+    rings: usize,
+    segments: usize,
+    radius: f32,
+    colour: [f32; 3],
+}
+
+impl UvSphere {
+    /// `rings` bands from pole to pole each split into `segments` around. Has at least two
+    /// rings and three segments.
+    pub fn new(rings: usize, segments: usize, radius: f32, colour: [f32; 3]) -> Self {
+        UvSphere {
+            rings: rings.max(2),
+            segments: segments.max(3),
+            radius,
+            colour,
+        }
+    }
+}
+
+impl scene::Geometry for UvSphere {
+    fn geometry(&self) -> (Vec<scene::Vertex>, scene::Index) {
+        // A grid of vertices including a column on both sides of where the longitude wraps
+        // around and a row at each pole so each gets its own texture coordinates.
+        let (rings, segments) = (self.rings, self.segments);
+        let mut vertices: Vec<scene::Vertex> =
+            Vec::with_capacity((rings + 1) * (segments + 1));
+        for ring in 0..=rings {
+            let v = ring as f32 / rings as f32;
+            let latitude = PI / 2.0 - v * PI;
+            for segment in 0..=segments {
+                let u = segment as f32 / segments as f32;
+                let longitude = u * 2.0 * PI - PI;
+                let normal = Vector3::new(
+                    latitude.cos() * longitude.cos(),
+                    latitude.cos() * longitude.sin(),
+                    latitude.sin(),
+                );
+                let position = Point3::from_vec(normal * self.radius);
+                vertices.push(scene::Vertex::textured(
+                    position.into(), normal.into(), self.colour, [u, v],
+                ));
+            }
+        }
+
+        // Going down and then around keeps the triangles wound anticlockwise seen from
+        // outside. The bands at the poles only need one triangle per segment.
+        let at = |ring: usize, segment: usize| (ring * (segments + 1) + segment) as u32;
+        let mut index: Vec<u32> = Vec::with_capacity(6 * rings * segments);
+        for ring in 0..rings {
+            for segment in 0..segments {
+                let (top_left, top_right) = (at(ring, segment), at(ring, segment + 1));
+                let (bottom_left, bottom_right) =
+                    (at(ring + 1, segment), at(ring + 1, segment + 1));
+                if ring != 0 {
+                    index.extend(&[top_left, bottom_left, top_right]);
+                }
+                if ring != rings - 1 {
+                    index.extend(&[top_right, bottom_left, bottom_right]);
+                }
+            }
+        }
+
+        let count = vertices.len();
+        (vertices, scene::Index::fit(index, count))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::scene::Geometry;
+
+    fn triangles(index: &scene::Index) -> Vec<[usize; 3]> {
+        let index: Vec<usize> = match index {
+            scene::Index::U16(index) => index.iter().map(|i| *i as usize).collect(),
+            scene::Index::U32(index) => index.iter().map(|i| *i as usize).collect(),
+        };
+        index.chunks(3).map(|t| [t[0], t[1], t[2]]).collect()
+    }
+
+    /// Every triangle has some area and faces away from the center.
+    fn outwards(vertices: &[scene::Vertex], triangles: &[[usize; 3]]) -> bool {
+        let position = |v: usize| Vector3::from(*vertices[v].position());
+        triangles.iter().all(|&[a, b, c]| {
+            let normal = (position(b) - position(a)).cross(position(c) - position(a));
+            normal.dot(position(a)) > 1e-9
+        })
+    }
+
+    #[test]
+    fn icosphere() {
+        let (vertices, index) = Icosphere::new(2, 3.0, [1.0; 3]).geometry();
+        let triangles = triangles(&index);
+        assert!(triangles.len() == 320);
+        assert!(vertices.len() == 162);
+        assert!(vertices
+            .iter()
+            .all(|v| (Vector3::from(*v.position()).magnitude() - 3.0).abs() < 1e-5));
+        assert!(outwards(&vertices, &triangles));
+    }
+
+    #[test]
+    fn uv_sphere() {
+        let (vertices, index) = UvSphere::new(4, 8, 2.0, [1.0; 3]).geometry();
+        let triangles = triangles(&index);
+        assert!(vertices.len() == 5 * 9);
+        assert!(triangles.len() == 2 * 4 * 8 - 2 * 8);
+        assert!(outwards(&vertices, &triangles));
+    }
+}